@@ -0,0 +1,47 @@
+-- DROP FUNCTION fn_get_all_oauth_providers;
+CREATE OR REPLACE FUNCTION fn_get_all_oauth_providers() RETURNS TABLE (
+    id_provider INT,
+    provider_name VARCHAR(255),
+    redirect_uri TEXT,
+    auth_url TEXT,
+    token_url TEXT,
+    revocation_url TEXT,
+    device_authorization_url TEXT,
+    issuer TEXT,
+    scopes TEXT [],
+    scope_profiles JSONB,
+    default_scope_profiles TEXT [],
+    include_granted_scopes BOOLEAN,
+    app_token_url TEXT,
+    app_scopes TEXT [],
+    api_base_url TEXT,
+    auth_params JSONB,
+    token_params JSONB,
+    client_id_secret_key TEXT,
+    client_secret_secret_key TEXT
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_oauth_providers.id_provider,
+    tb_oauth_providers.provider_name,
+    tb_oauth_providers.redirect_uri,
+    tb_oauth_providers.auth_url,
+    tb_oauth_providers.token_url,
+    tb_oauth_providers.revocation_url,
+    tb_oauth_providers.device_authorization_url,
+    tb_oauth_providers.issuer,
+    tb_oauth_providers.scopes,
+    tb_oauth_providers.scope_profiles,
+    tb_oauth_providers.default_scope_profiles,
+    tb_oauth_providers.include_granted_scopes,
+    tb_oauth_providers.app_token_url,
+    tb_oauth_providers.app_scopes,
+    tb_oauth_providers.api_base_url,
+    tb_oauth_providers.auth_params,
+    tb_oauth_providers.token_params,
+    tb_oauth_providers.client_id_secret_key,
+    tb_oauth_providers.client_secret_secret_key
+FROM tb_oauth_providers
+WHERE tb_oauth_providers.auth_url <> ''
+    AND tb_oauth_providers.token_url <> ''
+ORDER BY tb_oauth_providers.id_provider;
+END;
+$$ LANGUAGE plpgsql;
@@ -1,18 +1,26 @@
--- DROP FUNCTION fn_get_first_oauth_tokens_by_last_expiry_date;
+-- O tipo de retorno mudou: CREATE OR REPLACE não altera o de uma função existente
+DROP FUNCTION IF EXISTS fn_get_first_oauth_tokens_by_last_expiry_date();
 CREATE OR REPLACE FUNCTION fn_get_first_oauth_tokens_by_last_expiry_date() RETURNS TABLE (
     id_oauth_tokens UUID,
     id_provider INT,
     access_token TEXT,
     refresh_token TEXT,
-    expiry_date TIMESTAMP WITH TIME ZONE
+    expiry_date TIMESTAMP WITH TIME ZONE,
+    granted_scopes TEXT [],
+    account_subject TEXT,
+    account_email TEXT
 ) AS $$ BEGIN RETURN QUERY
 SELECT tb_oauth_tokens.id_oauth_tokens,
     tb_oauth_tokens.id_provider,
     tb_oauth_tokens.access_token,
     tb_oauth_tokens.refresh_token,
-    tb_oauth_tokens.expiry_date
+    tb_oauth_tokens.expiry_date,
+    tb_oauth_tokens.granted_scopes,
+    tb_oauth_tokens.account_subject,
+    tb_oauth_tokens.account_email
 FROM tb_oauth_tokens
+WHERE tb_oauth_tokens.revoked_at IS NULL
 ORDER BY tb_oauth_tokens.expiry_date DESC
 LIMIT 1;
 END;
-$$ LANGUAGE plpgsql;
\ No newline at end of file
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,23 @@
+-- DROP FUNCTION fn_get_oauth_tokens_by_id;
+CREATE OR REPLACE FUNCTION fn_get_oauth_tokens_by_id(p_id_oauth_tokens UUID) RETURNS TABLE (
+    id_oauth_tokens UUID,
+    id_provider INT,
+    access_token TEXT,
+    refresh_token TEXT,
+    expiry_date TIMESTAMP WITH TIME ZONE,
+    granted_scopes TEXT [],
+    account_subject TEXT,
+    account_email TEXT
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_oauth_tokens.id_oauth_tokens,
+    tb_oauth_tokens.id_provider,
+    tb_oauth_tokens.access_token,
+    tb_oauth_tokens.refresh_token,
+    tb_oauth_tokens.expiry_date,
+    tb_oauth_tokens.granted_scopes,
+    tb_oauth_tokens.account_subject,
+    tb_oauth_tokens.account_email
+FROM tb_oauth_tokens
+WHERE tb_oauth_tokens.id_oauth_tokens = p_id_oauth_tokens;
+END;
+$$ LANGUAGE plpgsql;
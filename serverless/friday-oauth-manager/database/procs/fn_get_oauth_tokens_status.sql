@@ -0,0 +1,30 @@
+-- DROP FUNCTION fn_get_oauth_tokens_status;
+-- Nunca retorna access_token ou refresh_token
+CREATE OR REPLACE FUNCTION fn_get_oauth_tokens_status(p_id_provider INT) RETURNS TABLE (
+    id_oauth_tokens UUID,
+    id_provider INT,
+    account_subject TEXT,
+    account_email TEXT,
+    granted_scopes TEXT [],
+    expiry_date TIMESTAMP WITH TIME ZONE,
+    last_refresh_at TIMESTAMP WITH TIME ZONE,
+    last_refresh_error TEXT,
+    revoked_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_oauth_tokens.id_oauth_tokens,
+    tb_oauth_tokens.id_provider,
+    tb_oauth_tokens.account_subject,
+    tb_oauth_tokens.account_email,
+    tb_oauth_tokens.granted_scopes,
+    tb_oauth_tokens.expiry_date,
+    tb_oauth_tokens.last_refresh_at,
+    tb_oauth_tokens.last_refresh_error,
+    tb_oauth_tokens.revoked_at
+FROM tb_oauth_tokens
+WHERE p_id_provider IS NULL
+    OR tb_oauth_tokens.id_provider = p_id_provider
+ORDER BY tb_oauth_tokens.id_provider,
+    tb_oauth_tokens.account_email,
+    tb_oauth_tokens.expiry_date DESC;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,16 @@
+-- DROP FUNCTION fn_get_service_client_by_id;
+CREATE OR REPLACE FUNCTION fn_get_service_client_by_id(p_id_service_client UUID) RETURNS TABLE (
+    id_service_client UUID,
+    client_name VARCHAR(255),
+    client_secret_hash TEXT,
+    allowed_scopes TEXT []
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_service_clients.id_service_client,
+    tb_service_clients.client_name,
+    tb_service_clients.client_secret_hash,
+    tb_service_clients.allowed_scopes
+FROM tb_service_clients
+WHERE tb_service_clients.id_service_client = p_id_service_client
+    AND tb_service_clients.disabled_at IS NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,8 @@
+-- DROP PROCEDURE pr_del_oauth_tokens_by_refresh_token
+CREATE OR REPLACE PROCEDURE pr_del_oauth_tokens_by_refresh_token(
+        IN p_refresh_token TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+DELETE FROM tb_oauth_tokens
+WHERE refresh_token = p_refresh_token;
+END;
+$$;
@@ -1,21 +1,31 @@
--- DROP PROCEDURE pr_ins_oauth_tokens
+-- Remove a assinatura anterior, que ficaria como sobrecarga
+DROP PROCEDURE IF EXISTS pr_ins_oauth_tokens(TEXT, TEXT, TIMESTAMP WITH TIME ZONE, INT);
 CREATE OR REPLACE PROCEDURE pr_ins_oauth_tokens(
         IN p_access_token TEXT,
         IN p_refresh_token TEXT,
         IN p_expiry_date TIMESTAMP WITH TIME ZONE,
-        IN p_id_provider INT
+        IN p_id_provider INT,
+        IN p_account_subject TEXT,
+        IN p_account_email TEXT,
+        IN p_granted_scopes TEXT []
     ) LANGUAGE plpgsql AS $$ BEGIN
 INSERT INTO tb_oauth_tokens (
         access_token,
         refresh_token,
         expiry_date,
-        id_provider
+        id_provider,
+        account_subject,
+        account_email,
+        granted_scopes
     )
 VALUES (
         p_access_token,
         p_refresh_token,
         p_expiry_date,
-        p_id_provider
+        p_id_provider,
+        p_account_subject,
+        p_account_email,
+        p_granted_scopes
     );
 END;
-$$;
\ No newline at end of file
+$$;
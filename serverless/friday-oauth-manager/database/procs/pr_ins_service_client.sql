@@ -0,0 +1,21 @@
+-- DROP PROCEDURE pr_ins_service_client
+CREATE OR REPLACE PROCEDURE pr_ins_service_client(
+        IN p_id_service_client UUID,
+        IN p_client_name VARCHAR(255),
+        IN p_client_secret_hash TEXT,
+        IN p_allowed_scopes TEXT []
+    ) LANGUAGE plpgsql AS $$ BEGIN
+INSERT INTO tb_service_clients (
+        id_service_client,
+        client_name,
+        client_secret_hash,
+        allowed_scopes
+    )
+VALUES (
+        p_id_service_client,
+        p_client_name,
+        p_client_secret_hash,
+        p_allowed_scopes
+    );
+END;
+$$;
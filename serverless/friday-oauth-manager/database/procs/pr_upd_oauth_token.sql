@@ -1,12 +1,20 @@
--- DROP PROCEDURE pr_upd_oauth_tokens_by_refresh_token
+-- Remove a assinatura anterior, que ficaria como sobrecarga
+DROP PROCEDURE IF EXISTS pr_upd_oauth_tokens_by_refresh_token(TEXT, TEXT, TIMESTAMP WITH TIME ZONE);
 CREATE OR REPLACE PROCEDURE pr_upd_oauth_tokens_by_refresh_token(
         IN p_access_token TEXT,
         IN p_refresh_token TEXT,
-        IN p_expiry_date TIMESTAMP WITH TIME ZONE
+        IN p_new_refresh_token TEXT,
+        IN p_expiry_date TIMESTAMP WITH TIME ZONE,
+        IN p_granted_scopes TEXT []
     ) LANGUAGE plpgsql AS $$ BEGIN
 UPDATE tb_oauth_tokens
 SET access_token = p_access_token,
-    expiry_date = p_expiry_date
+    refresh_token = p_new_refresh_token,
+    expiry_date = p_expiry_date,
+    granted_scopes = p_granted_scopes,
+    revoked_at = NULL,
+    last_refresh_at = NOW(),
+    last_refresh_error = NULL
 WHERE refresh_token = p_refresh_token;
 END;
-$$;
\ No newline at end of file
+$$;
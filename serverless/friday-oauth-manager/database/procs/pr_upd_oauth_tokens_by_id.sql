@@ -0,0 +1,21 @@
+-- DROP PROCEDURE pr_upd_oauth_tokens_by_id
+CREATE OR REPLACE PROCEDURE pr_upd_oauth_tokens_by_id(
+        IN p_id_oauth_tokens UUID,
+        IN p_access_token TEXT,
+        IN p_refresh_token TEXT,
+        IN p_expiry_date TIMESTAMP WITH TIME ZONE,
+        IN p_granted_scopes TEXT [],
+        IN p_account_subject TEXT,
+        IN p_account_email TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_oauth_tokens
+SET access_token = p_access_token,
+    refresh_token = p_refresh_token,
+    expiry_date = p_expiry_date,
+    granted_scopes = p_granted_scopes,
+    account_subject = p_account_subject,
+    account_email = p_account_email,
+    revoked_at = NULL
+WHERE id_oauth_tokens = p_id_oauth_tokens;
+END;
+$$;
@@ -0,0 +1,11 @@
+-- DROP PROCEDURE pr_upd_oauth_tokens_refresh_error_by_refresh_token
+CREATE OR REPLACE PROCEDURE pr_upd_oauth_tokens_refresh_error_by_refresh_token(
+        IN p_refresh_token TEXT,
+        IN p_refresh_error TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_oauth_tokens
+SET last_refresh_at = NOW(),
+    last_refresh_error = p_refresh_error
+WHERE refresh_token = p_refresh_token;
+END;
+$$;
@@ -0,0 +1,10 @@
+-- DROP PROCEDURE pr_upd_oauth_tokens_revoked_by_refresh_token
+CREATE OR REPLACE PROCEDURE pr_upd_oauth_tokens_revoked_by_refresh_token(
+        IN p_refresh_token TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_oauth_tokens
+SET revoked_at = NOW()
+WHERE refresh_token = p_refresh_token
+    AND revoked_at IS NULL;
+END;
+$$;
@@ -1,8 +1,54 @@
 --DROP TABLE tb_oauth_providers;
 CREATE TABLE IF NOT EXISTS tb_oauth_providers (
     id_provider INT PRIMARY KEY,
-    provider_name varchar(255) NOT NULL,
-    client_id text NOT NULL,
-    client_secret_value text NOT NULL,
-    redirect_uri text NOT NULL
+    provider_name varchar(255) NOT NULL UNIQUE,
+    client_id text NULL,
+    client_secret_value text NULL,
+    redirect_uri text NOT NULL,
+    auth_url text NOT NULL,
+    token_url text NOT NULL,
+    revocation_url text NULL,
+    device_authorization_url text NULL,
+    issuer text NULL,
+    scopes text [] NOT NULL DEFAULT '{}',
+    scope_profiles jsonb NOT NULL DEFAULT '{}',
+    default_scope_profiles text [] NOT NULL DEFAULT '{}',
+    include_granted_scopes boolean NOT NULL DEFAULT false,
+    app_token_url text NULL,
+    app_scopes text [] NOT NULL DEFAULT '{}',
+    api_base_url text NULL,
+    auth_params jsonb NOT NULL DEFAULT '{}',
+    token_params jsonb NOT NULL DEFAULT '{}',
+    client_id_secret_key text NOT NULL,
+    client_secret_secret_key text NOT NULL
 );
+
+-- Migração da versão anterior: as credenciais passam a ficar no secret manager
+ALTER TABLE tb_oauth_providers ALTER COLUMN client_id DROP NOT NULL;
+ALTER TABLE tb_oauth_providers ALTER COLUMN client_secret_value DROP NOT NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS auth_url text NOT NULL DEFAULT '';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS token_url text NOT NULL DEFAULT '';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS revocation_url text NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS device_authorization_url text NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS issuer text NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS scopes text [] NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS scope_profiles jsonb NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS default_scope_profiles text [] NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS include_granted_scopes boolean NOT NULL DEFAULT false;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS app_token_url text NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS app_scopes text [] NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS api_base_url text NULL;
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS auth_params jsonb NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS token_params jsonb NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS client_id_secret_key text NOT NULL DEFAULT 'OAUTH_CLIENT_ID';
+ALTER TABLE tb_oauth_providers ADD COLUMN IF NOT EXISTS client_secret_secret_key text NOT NULL DEFAULT 'OAUTH_SECRET_VALUE';
+
+-- Exemplo de provedor adicional (sem recompilar o serviço):
+-- INSERT INTO tb_oauth_providers (id_provider, provider_name, redirect_uri, auth_url, token_url, scopes, scope_profiles, default_scope_profiles, client_id_secret_key, client_secret_secret_key)
+-- VALUES (3, 'github', 'http://localhost:5000/callback', 'https://github.com/login/oauth/authorize', 'https://github.com/login/oauth/access_token', '{read:user}', '{"repo": ["repo"]}', '{repo}', 'GITHUB_OAUTH_CLIENT_ID', 'GITHUB_OAUTH_SECRET_VALUE');
+
+-- Tokens de aplicação (client credentials) da Microsoft exigem o endpoint do tenant:
+-- UPDATE tb_oauth_providers
+-- SET app_token_url = 'https://login.microsoftonline.com/<tenant-id>/oauth2/v2.0/token',
+--     app_scopes = '{https://graph.microsoft.com/.default}'
+-- WHERE provider_name = 'microsoft';
@@ -4,5 +4,18 @@ CREATE TABLE IF NOT EXISTS tb_oauth_tokens (
     id_provider INT NOT NULL REFERENCES tb_oauth_providers(id_provider),
     access_token TEXT NOT NULL,
     refresh_token TEXT NOT NULL,
-    expiry_date TIMESTAMP WITH TIME ZONE NOT NULL
+    expiry_date TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked_at TIMESTAMP WITH TIME ZONE NULL,
+    granted_scopes TEXT [] NOT NULL DEFAULT '{}',
+    account_subject TEXT NULL,
+    account_email TEXT NULL,
+    last_refresh_at TIMESTAMP WITH TIME ZONE NULL,
+    last_refresh_error TEXT NULL
 );
+
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP WITH TIME ZONE NULL;
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS granted_scopes TEXT [] NOT NULL DEFAULT '{}';
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS account_subject TEXT NULL;
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS account_email TEXT NULL;
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS last_refresh_at TIMESTAMP WITH TIME ZONE NULL;
+ALTER TABLE tb_oauth_tokens ADD COLUMN IF NOT EXISTS last_refresh_error TEXT NULL;
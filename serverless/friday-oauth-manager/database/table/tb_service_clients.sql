@@ -0,0 +1,9 @@
+--DROP TABLE tb_service_clients;
+CREATE TABLE IF NOT EXISTS tb_service_clients (
+    id_service_client UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    client_name varchar(255) NOT NULL UNIQUE,
+    client_secret_hash TEXT NOT NULL,
+    allowed_scopes TEXT [] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    disabled_at TIMESTAMP WITH TIME ZONE NULL
+);
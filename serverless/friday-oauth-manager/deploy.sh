@@ -35,7 +35,8 @@ sed -i "s|image: z33p/friday-oauth-manager:.*|image: z33p/friday-oauth-manager:$
 echo "Image tag atualizada com sucesso!"
 
 # Build da imagem
-docker build --pull --rm -f "FridayOAuthManager.Dockerfile" -t friday-oauth-manager:$dockerTag "."
+# Contexto em serverless/ para incluir a dependência friday-service-auth
+docker build --pull --rm -f "FridayOAuthManager.Dockerfile" -t friday-oauth-manager:$dockerTag ".."
 
 # Tag para Docker Hub
 docker tag friday-oauth-manager:$dockerTag docker.io/z33p/friday-oauth-manager:$dockerTag
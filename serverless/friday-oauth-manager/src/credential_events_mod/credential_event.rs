@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde_derive::Serialize;
+use uuid::Uuid;
+
+use crate::{oauth_provider::OAuthProvider, oauth_tokens_mod::oauth_tokens::OAuthTokens};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialEventType {
+    /// A credential was stored (authorization code, incremental consent or device flow)
+    TokenAcquired,
+    TokenRefreshed,
+    TokenRefreshFailed,
+    TokenRevoked,
+}
+
+impl CredentialEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CredentialEventType::TokenAcquired => "token_acquired",
+            CredentialEventType::TokenRefreshed => "token_refreshed",
+            CredentialEventType::TokenRefreshFailed => "token_refresh_failed",
+            CredentialEventType::TokenRevoked => "token_revoked",
+        }
+    }
+}
+
+/// Event published to the configured webhooks and to the RabbitMQ queue whenever a stored
+/// credential changes. Never carries tokens.
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialEvent {
+    /// Unique identifier of the event, for idempotent consumers
+    pub id_event: Uuid,
+    pub event_type: CredentialEventType,
+    pub provider: OAuthProvider,
+    pub id_oauth_tokens: Option<Uuid>,
+    /// Account e-mail of the credential (OpenID Connect providers only)
+    pub email: Option<String>,
+    /// Error returned by the provider when the refresh failed
+    pub error: Option<String>,
+    /// The provider revoked the grant (`invalid_grant`) and the user must consent again
+    pub requires_reconsent: bool,
+    /// Authorization URL to consent again, present when `requires_reconsent` is set
+    pub reconsent_url: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl CredentialEvent {
+    pub fn new(
+        event_type: CredentialEventType,
+        provider: OAuthProvider,
+        oauth_tokens: Option<&OAuthTokens>,
+    ) -> Self {
+        Self {
+            id_event: Uuid::new_v4(),
+            event_type,
+            provider,
+            id_oauth_tokens: oauth_tokens.and_then(|tokens| tokens.id_oauth_tokens),
+            email: oauth_tokens.and_then(|tokens| tokens.email.clone()),
+            error: None,
+            requires_reconsent: false,
+            reconsent_url: None,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Refresh failure; `reconsent_url` is set when the provider revoked the grant
+    pub fn refresh_failed(
+        provider: OAuthProvider,
+        oauth_tokens: Option<&OAuthTokens>,
+        error: String,
+        reconsent_url: Option<String>,
+    ) -> Self {
+        Self {
+            error: Some(error),
+            requires_reconsent: reconsent_url.is_some(),
+            reconsent_url,
+            ..Self::new(
+                CredentialEventType::TokenRefreshFailed,
+                provider,
+                oauth_tokens,
+            )
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::hmac;
+use tokio::sync::OnceCell;
+use tracing::{debug, error, info};
+
+use super::{credential_event::CredentialEvent, message_broker};
+use crate::secret_manager_mod;
+
+/// Fila do RabbitMQ que recebe os eventos de credenciais
+const CREDENTIAL_EVENTS_QUEUE: &str = "OAUTH.CREDENTIAL.EVENTS";
+const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+
+/// Destinations of the credential events, read once from the secret manager
+struct CredentialEventsSettings {
+    webhook_urls: Vec<String>,
+    webhook_secret: Option<String>,
+    /// (host, user, password)
+    rabbit_credentials: Option<(String, String, String)>,
+}
+
+/// Carregadas no primeiro evento; falhas de leitura não ficam em cache
+static CREDENTIAL_EVENTS_SETTINGS: OnceCell<CredentialEventsSettings> = OnceCell::const_new();
+
+/// Publishes the event in the background to the configured webhooks and RabbitMQ queue.
+/// Delivery failures are only logged, they never fail the token flow that raised the event.
+pub fn publish_credential_event(event: CredentialEvent) {
+    actix_web::rt::spawn(dispatch_credential_event(event));
+}
+
+async fn dispatch_credential_event(event: CredentialEvent) {
+    let settings = match get_credential_events_settings().await {
+        Ok(settings) => settings,
+        Err(e) => {
+            error!(
+                "Erro ao carregar configuração dos eventos de credenciais: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    if settings.webhook_urls.is_empty() && settings.rabbit_credentials.is_none() {
+        debug!(
+            "Nenhum destino configurado para o evento {}",
+            event.event_type.as_str()
+        );
+        return;
+    }
+
+    let payload = match serde_json::to_string(&event) {
+        Ok(payload) => payload,
+        Err(e) => {
+            error!("Erro ao serializar evento de credencial: {}", e);
+            return;
+        }
+    };
+
+    for webhook_url in &settings.webhook_urls {
+        match send_webhook(
+            webhook_url,
+            &event,
+            &payload,
+            settings.webhook_secret.as_deref(),
+        )
+        .await
+        {
+            Ok(_) => info!(
+                provider = %event.provider,
+                "Evento {} enviado ao webhook {}",
+                event.event_type.as_str(),
+                webhook_url
+            ),
+            Err(e) => error!(
+                provider = %event.provider,
+                "Erro ao enviar evento {} ao webhook {}: {}",
+                event.event_type.as_str(),
+                webhook_url,
+                e
+            ),
+        }
+    }
+
+    if let Some((host, user, password)) = &settings.rabbit_credentials {
+        match publish_to_queue(host, user, password, &payload).await {
+            Ok(_) => info!(
+                provider = %event.provider,
+                "Evento {} publicado na fila {}",
+                event.event_type.as_str(),
+                CREDENTIAL_EVENTS_QUEUE
+            ),
+            Err(e) => error!(
+                provider = %event.provider,
+                "Erro ao publicar evento {} na fila {}: {}",
+                event.event_type.as_str(),
+                CREDENTIAL_EVENTS_QUEUE,
+                e
+            ),
+        }
+    }
+}
+
+async fn get_credential_events_settings(
+) -> Result<&'static CredentialEventsSettings, Box<dyn std::error::Error>> {
+    CREDENTIAL_EVENTS_SETTINGS
+        .get_or_try_init(|| async {
+            let webhook_urls = secret_manager_mod::get_credential_webhook_urls()
+                .await?
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| url.trim().to_string())
+                        .filter(|url| !url.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Ok(CredentialEventsSettings {
+                webhook_urls,
+                webhook_secret: secret_manager_mod::get_credential_webhook_secret().await?,
+                rabbit_credentials: secret_manager_mod::get_rabbit_credentials().await?,
+            })
+        })
+        .await
+}
+
+/// POSTs the event as JSON; with a webhook secret the body is signed in
+/// `X-Friday-Signature: sha256=<base64 HMAC-SHA256>`
+async fn send_webhook(
+    webhook_url: &str,
+    event: &CredentialEvent,
+    payload: &str,
+    webhook_secret: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut request = reqwest::Client::new()
+        .post(webhook_url)
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Friday-Event", event.event_type.as_str())
+        .header("X-Friday-Event-Id", event.id_event.to_string());
+
+    if let Some(webhook_secret) = webhook_secret {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, webhook_secret.as_bytes());
+        let signature = hmac::sign(&key, payload.as_bytes());
+        request = request.header(
+            "X-Friday-Signature",
+            format!("sha256={}", STANDARD.encode(signature.as_ref())),
+        );
+    }
+
+    request
+        .body(payload.to_string())
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+async fn publish_to_queue(
+    host: &str,
+    user: &str,
+    password: &str,
+    payload: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let connection = message_broker::connect(user, password, host).await?;
+
+    let result = message_broker::queue_publish(&connection, CREDENTIAL_EVENTS_QUEUE, payload).await;
+
+    if let Err(e) = connection.close(0, "").await {
+        debug!("Erro ao fechar conexão com o RabbitMq: {}", e);
+    }
+
+    result
+}
@@ -0,0 +1,7 @@
+pub mod credential_event;
+pub mod credential_events_logic;
+mod message_broker;
+
+// Re-export for easier access
+pub use credential_event::{CredentialEvent, CredentialEventType};
+pub use credential_events_logic::publish_credential_event;
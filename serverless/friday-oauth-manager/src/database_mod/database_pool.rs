@@ -0,0 +1,83 @@
+use std::{sync::Arc, sync::RwLock, time::Duration};
+
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tracing::{error, info};
+
+use crate::secret_manager_mod;
+
+/// How often the connection string is read again from the secret manager
+const ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_CONNECTIONS: u32 = 10;
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Postgres pool shared by every request, created at startup from the connection string
+/// of the secret manager. The pool is replaced when that connection string is rotated.
+pub struct DatabasePool {
+    /// (connection string, pool created with it)
+    current: RwLock<(String, PgPool)>,
+}
+
+impl DatabasePool {
+    pub async fn connect() -> Result<Self, Box<dyn std::error::Error>> {
+        let database_url = secret_manager_mod::get_database_url().await?;
+        let pool = create_pool(&database_url)?;
+
+        info!("Pool do Postgres criado");
+
+        Ok(Self {
+            current: RwLock::new((database_url, pool)),
+        })
+    }
+
+    /// Handle to the current pool; cheap to clone and keep for the whole request
+    pub fn get(&self) -> PgPool {
+        self.current.read().unwrap().1.clone()
+    }
+
+    /// Reads the connection string again and swaps the pool when it changed. Connections of
+    /// the previous pool are closed once the requests using them finish.
+    pub async fn reconnect_if_rotated(&self) -> Result<bool, Box<dyn std::error::Error>> {
+        let database_url = secret_manager_mod::get_database_url().await?;
+
+        if self.current.read().unwrap().0 == database_url {
+            return Ok(false);
+        }
+
+        let pool = create_pool(&database_url)?;
+        let (_, previous_pool) =
+            std::mem::replace(&mut *self.current.write().unwrap(), (database_url, pool));
+
+        info!("Connection string do Postgres rotacionada, pool recriado");
+
+        previous_pool.close().await;
+
+        Ok(true)
+    }
+
+    /// Checks for a rotated connection string in the background while the server runs
+    pub fn watch_rotation(self: Arc<Self>) {
+        actix_web::rt::spawn(async move {
+            let mut interval = tokio::time::interval(ROTATION_CHECK_INTERVAL);
+            // O primeiro tick é imediato e o pool acabou de ser criado
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = self.reconnect_if_rotated().await {
+                    error!("Erro ao verificar rotação da connection string: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Connections are opened on first use, so a database outage does not prevent startup
+fn create_pool(database_url: &str) -> Result<PgPool, Box<dyn std::error::Error>> {
+    let pool = PgPoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .acquire_timeout(ACQUIRE_TIMEOUT)
+        .connect_lazy(database_url)?;
+
+    Ok(pool)
+}
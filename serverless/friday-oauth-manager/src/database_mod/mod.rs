@@ -0,0 +1,3 @@
+pub mod database_pool;
+
+pub use database_pool::DatabasePool;
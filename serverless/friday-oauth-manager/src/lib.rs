@@ -0,0 +1,82 @@
+pub mod business_response;
+pub mod credential_events_mod;
+pub mod database_mod;
+pub mod load_env;
+pub mod oauth_manager_error;
+pub mod oauth_provider;
+pub mod oauth_tokens_mod;
+pub mod openapi;
+pub mod provider_proxy_mod;
+pub mod secret_manager_mod;
+pub mod service_auth_mod;
+
+use actix_web::{get, web, Responder};
+use load_env::{load_env_variables, EnvVariables};
+use oauth_provider::{
+    oauth_provider_registry, FridayOAuthClient, OAuthClientProvider, OAuthProvider,
+    OAuthProviderFactory,
+};
+use once_cell::sync::Lazy;
+use std::error::Error;
+
+use crate::{
+    oauth_tokens_mod::oauth_tokens_controller, provider_proxy_mod::provider_proxy_controller,
+    service_auth_mod::service_auth_controller,
+};
+
+pub static ENV_CONFIG: Lazy<EnvVariables> = Lazy::new(load_env_variables);
+
+#[get("/")]
+async fn index() -> impl Responder {
+    "Friday OAuth API - OK"
+}
+
+/// Registers every endpoint of the service; shared by the HTTP server and the integration tests
+pub fn configure_services(cfg: &mut web::ServiceConfig) {
+    cfg.service(index)
+        .service(oauth_tokens_controller::generate_access_token)
+        .service(oauth_tokens_controller::generate_app_token)
+        .service(oauth_tokens_controller::refresh_access_token)
+        .service(oauth_tokens_controller::revoke_oauth_tokens)
+        .service(oauth_tokens_controller::generate_oauth_url_endpoint)
+        .service(oauth_tokens_controller::generate_google_oauth_url)
+        .service(oauth_tokens_controller::generate_microsoft_oauth_url)
+        .service(oauth_tokens_controller::generate_provider_oauth_url)
+        .service(oauth_tokens_controller::generate_incremental_consent_url)
+        .service(oauth_tokens_controller::start_device_authorization)
+        .service(oauth_tokens_controller::check_device_authorization)
+        .service(oauth_tokens_controller::get_oauth_tokens)
+        .service(oauth_tokens_controller::get_oauth_tokens_status)
+        .service(oauth_tokens_controller::health_check)
+        .service(service_auth_controller::register_service_client)
+        .service(service_auth_controller::generate_service_token)
+        .service(service_auth_controller::get_jwks)
+        .service(provider_proxy_controller::proxy_provider_request)
+        .service(openapi::swagger_config());
+}
+
+pub async fn get_oauth_client(
+    provider: OAuthProvider,
+) -> Result<FridayOAuthClient, Box<dyn Error>> {
+    create_oauth_provider(&provider).await?.create_client()
+}
+
+/// OAuth client for app-only tokens (client-credentials grant)
+pub async fn get_oauth_app_client(
+    provider: OAuthProvider,
+) -> Result<FridayOAuthClient, Box<dyn Error>> {
+    create_oauth_provider(&provider).await?.create_app_client()
+}
+
+async fn create_oauth_provider(
+    provider: &OAuthProvider,
+) -> Result<Box<dyn OAuthClientProvider>, Box<dyn Error>> {
+    let config = oauth_provider_registry().get(provider)?;
+    let (client_id, secret_value) = secret_manager_mod::get_oauth_credentials(
+        &config.client_id_secret_key,
+        &config.client_secret_secret_key,
+    )
+    .await?;
+
+    OAuthProviderFactory::create_provider(provider, client_id, secret_value)
+}
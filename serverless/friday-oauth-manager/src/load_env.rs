@@ -7,10 +7,12 @@ pub fn load_env_variables() -> EnvVariables {
     let mut config = if is_prod {
         envy::from_env::<EnvVariables>().expect("Error loading .env file or environment variables")
     } else {
-        // Valores padrão para desenvolvimento
+        // Valores padrão para desenvolvimento; SECRET_MANAGER_URL permite apontar para um
+        // secret manager local (testes de integração)
         EnvVariables {
             is_prod: false,
-            secret_manager_url: "https://k8s.z33p.com/api/friday-secret-manager".to_string(),
+            secret_manager_url: std::env::var("SECRET_MANAGER_URL")
+                .unwrap_or_else(|_| "https://k8s.z33p.com/api/friday-secret-manager".to_string()),
         }
     };
 
@@ -0,0 +1,169 @@
+use std::{error::Error, fmt, time::Duration};
+
+use actix_web::{
+    http::{header, StatusCode},
+    HttpResponse, ResponseError,
+};
+use friday_service_auth::ServiceAuthError;
+use serde_json::json;
+
+use crate::business_response::BusinessResponse;
+
+/// Errors of the OAuth manager, mapped to the HTTP status returned to the caller
+#[derive(Debug)]
+pub enum OAuthManagerError {
+    /// Invalid input the caller must fix (malformed callback URL, unknown profile, ...)
+    BadRequest(String),
+    /// The provider redirected to the callback with `error`/`error_description`
+    AuthorizationDenied {
+        error: String,
+        error_description: Option<String>,
+    },
+    /// Missing or invalid internal service token
+    Unauthorized(String),
+    /// The provider revoked the grant (`invalid_grant`); the user must consent again
+    Revoked {
+        message: String,
+        provider: String,
+        reconsent_url: String,
+    },
+    /// Valid service token without the scope the endpoint requires
+    Forbidden(String),
+    /// The stored credential was not granted the scopes the caller requires
+    MissingScopes {
+        provider: String,
+        missing_scopes: Vec<String>,
+    },
+    NotFound(String),
+    /// The resource already exists (duplicate service client name, ...)
+    Conflict(String),
+    /// The caller exceeded a rate limit; the response carries `Retry-After`
+    TooManyRequests {
+        message: String,
+        retry_after: Duration,
+    },
+    /// The provider rejected the request or could not be reached
+    Provider(String),
+    /// Database, secret manager and other infrastructure failures
+    Internal(Box<dyn Error>),
+}
+
+impl fmt::Display for OAuthManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OAuthManagerError::BadRequest(message)
+            | OAuthManagerError::Unauthorized(message)
+            | OAuthManagerError::Forbidden(message)
+            | OAuthManagerError::NotFound(message)
+            | OAuthManagerError::Conflict(message)
+            | OAuthManagerError::Revoked { message, .. }
+            | OAuthManagerError::TooManyRequests { message, .. } => write!(f, "{}", message),
+            OAuthManagerError::AuthorizationDenied {
+                error,
+                error_description: Some(description),
+            } => write!(
+                f,
+                "Autorização negada pelo provedor: {} ({})",
+                error, description
+            ),
+            OAuthManagerError::AuthorizationDenied { error, .. } => {
+                write!(f, "Autorização negada pelo provedor: {}", error)
+            }
+            OAuthManagerError::MissingScopes { missing_scopes, .. } => write!(
+                f,
+                "Escopos não concedidos ao token: {}",
+                missing_scopes.join(" ")
+            ),
+            OAuthManagerError::Provider(message) => {
+                write!(f, "Erro do provedor OAuth: {}", message)
+            }
+            OAuthManagerError::Internal(e) => write!(f, "Erro interno: {}", e),
+        }
+    }
+}
+
+impl Error for OAuthManagerError {}
+
+impl From<Box<dyn Error>> for OAuthManagerError {
+    fn from(e: Box<dyn Error>) -> Self {
+        OAuthManagerError::Internal(e)
+    }
+}
+
+impl From<ServiceAuthError> for OAuthManagerError {
+    fn from(e: ServiceAuthError) -> Self {
+        match e {
+            ServiceAuthError::MissingToken => {
+                OAuthManagerError::Unauthorized("Token de serviço não informado".to_string())
+            }
+            ServiceAuthError::InvalidToken(reason) => {
+                OAuthManagerError::Unauthorized(format!("Token de serviço inválido: {}", reason))
+            }
+            ServiceAuthError::MissingScope(scope) => OAuthManagerError::Forbidden(format!(
+                "O token de serviço não possui o escopo {}",
+                scope
+            )),
+            ServiceAuthError::KeysUnavailable(_) => OAuthManagerError::Internal(Box::new(e)),
+        }
+    }
+}
+
+impl ResponseError for OAuthManagerError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            OAuthManagerError::BadRequest(_) | OAuthManagerError::AuthorizationDenied { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            OAuthManagerError::Unauthorized(_) | OAuthManagerError::Revoked { .. } => {
+                StatusCode::UNAUTHORIZED
+            }
+            OAuthManagerError::Forbidden(_) | OAuthManagerError::MissingScopes { .. } => {
+                StatusCode::FORBIDDEN
+            }
+            OAuthManagerError::NotFound(_) => StatusCode::NOT_FOUND,
+            OAuthManagerError::Conflict(_) => StatusCode::CONFLICT,
+            OAuthManagerError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            OAuthManagerError::Provider(_) => StatusCode::BAD_GATEWAY,
+            OAuthManagerError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let data = match self {
+            OAuthManagerError::AuthorizationDenied {
+                error,
+                error_description,
+            } => Some(json!({
+                "error": error,
+                "error_description": error_description
+            })),
+            OAuthManagerError::Revoked {
+                provider,
+                reconsent_url,
+                ..
+            } => Some(json!({
+                "revoked": true,
+                "provider": provider,
+                "reconsent_url": reconsent_url
+            })),
+            OAuthManagerError::MissingScopes {
+                provider,
+                missing_scopes,
+            } => Some(json!({
+                "provider": provider,
+                "missing_scopes": missing_scopes
+            })),
+            _ => None,
+        };
+
+        let mut response = HttpResponse::build(self.status_code());
+
+        if let OAuthManagerError::TooManyRequests { retry_after, .. } = self {
+            // Arredonda para cima: Retry-After só aceita segundos inteiros
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response.insert_header((header::RETRY_AFTER, seconds.to_string()));
+        }
+
+        response.json(BusinessResponse::new(false, data, vec![self.to_string()]))
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use tracing::{info, warn};
+
+use super::OAuthProvider;
+
+/// Consecutive failed requests (after retries) that open the circuit
+const FAILURE_THRESHOLD: u32 = 5;
+/// How long an open circuit rejects requests before letting a trial request through
+const OPEN_DURATION: Duration = Duration::from_secs(30);
+
+enum CircuitState {
+    Closed {
+        consecutive_failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A trial request is in flight; a trial that never reports back is replaced after
+    /// `OPEN_DURATION`
+    HalfOpen {
+        since: Instant,
+    },
+}
+
+/// Estado do circuito de cada provedor
+static CIRCUIT_BREAKERS: Lazy<Mutex<HashMap<OAuthProvider, CircuitState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Whether a request to the provider may be sent; returns the reason when the circuit is open
+pub fn acquire(provider: &OAuthProvider) -> Result<(), String> {
+    let mut circuit_breakers = CIRCUIT_BREAKERS.lock().unwrap();
+    let now = Instant::now();
+
+    match circuit_breakers.get(provider) {
+        None | Some(CircuitState::Closed { .. }) => Ok(()),
+        Some(CircuitState::Open { until }) if *until > now => Err(format!(
+            "Circuito aberto para o provedor {}: chamadas suspensas por mais {}s",
+            provider,
+            (*until - now).as_secs() + 1
+        )),
+        Some(CircuitState::HalfOpen { since }) if now - *since < OPEN_DURATION => Err(format!(
+            "Circuito semiaberto para o provedor {}: aguardando a requisição de teste",
+            provider
+        )),
+        Some(_) => {
+            info!(provider = %provider, "Circuito semiaberto, liberando requisição de teste");
+            circuit_breakers.insert(provider.clone(), CircuitState::HalfOpen { since: now });
+            Ok(())
+        }
+    }
+}
+
+pub fn record_success(provider: &OAuthProvider) {
+    let previous = CIRCUIT_BREAKERS.lock().unwrap().insert(
+        provider.clone(),
+        CircuitState::Closed {
+            consecutive_failures: 0,
+        },
+    );
+
+    if matches!(previous, Some(CircuitState::HalfOpen { .. })) {
+        info!(provider = %provider, "Circuito fechado, provedor respondeu normalmente");
+    }
+}
+
+pub fn record_failure(provider: &OAuthProvider) {
+    let mut circuit_breakers = CIRCUIT_BREAKERS.lock().unwrap();
+
+    let consecutive_failures = match circuit_breakers.get(provider) {
+        Some(CircuitState::Closed {
+            consecutive_failures,
+        }) => consecutive_failures + 1,
+        // A trial request failed or requests were already rejected: keep it open
+        Some(_) => FAILURE_THRESHOLD,
+        None => 1,
+    };
+
+    let state = if consecutive_failures >= FAILURE_THRESHOLD {
+        warn!(
+            provider = %provider,
+            "Circuito aberto após {} falhas consecutivas, suspendendo chamadas por {}s",
+            consecutive_failures,
+            OPEN_DURATION.as_secs()
+        );
+        CircuitState::Open {
+            until: Instant::now() + OPEN_DURATION,
+        }
+    } else {
+        CircuitState::Closed {
+            consecutive_failures,
+        }
+    };
+
+    circuit_breakers.insert(provider.clone(), state);
+}
@@ -1,3 +1,16 @@
 pub use oauth_provider_api::*;
+pub use oauth_provider_config::*;
+pub use oauth_provider_registry::*;
+pub use oidc_user_claims::OidcUserClaims;
+pub use provider_http_client::provider_http_client;
 
+mod circuit_breaker;
 mod oauth_provider_api;
+mod oauth_provider_config;
+mod oauth_provider_data;
+mod oauth_provider_registry;
+mod oidc_discovery_response;
+pub mod oidc_logic;
+mod oidc_provider_api;
+mod oidc_user_claims;
+mod provider_http_client;
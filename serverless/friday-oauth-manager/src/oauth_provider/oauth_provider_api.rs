@@ -1,112 +1,169 @@
-use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, RedirectUrl, Scope, TokenUrl};
+use oauth2::{
+    basic::{
+        BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+        BasicTokenType,
+    },
+    AuthUrl, Client, ClientId, ClientSecret, DeviceAuthorizationUrl, ExtraTokenFields, RedirectUrl,
+    RevocationUrl, StandardRevocableToken, StandardTokenResponse, TokenUrl,
+};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum OAuthProvider {
-    Microsoft = 1,
-    Google = 2,
+use super::{oauth_provider_registry, OAuthProviderConfig};
+
+/// Name of a provider registered in the OAuth provider registry.
+/// Accepted case-insensitively ("MICROSOFT", "microsoft") and serialized in upper case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(from = "String", into = "String")]
+#[schema(value_type = String, example = "MICROSOFT")]
+pub struct OAuthProvider(String);
+
+impl OAuthProvider {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Resolves the provider stored under `tb_oauth_tokens.id_provider`
+    pub fn from_id(id_provider: i32) -> Result<Self, String> {
+        oauth_provider_registry()
+            .get_by_id(id_provider)
+            .map(|config| OAuthProvider::from(config.provider_name.as_str()))
+    }
+
+    pub fn id(&self) -> Result<i32, String> {
+        oauth_provider_registry()
+            .get(self)
+            .map(|config| config.id_provider)
+    }
+}
+
+impl From<String> for OAuthProvider {
+    fn from(name: String) -> Self {
+        OAuthProvider(name.to_lowercase())
+    }
+}
+
+impl From<&str> for OAuthProvider {
+    fn from(name: &str) -> Self {
+        OAuthProvider(name.to_lowercase())
+    }
+}
+
+impl From<OAuthProvider> for String {
+    fn from(provider: OAuthProvider) -> Self {
+        provider.0.to_uppercase()
+    }
 }
 
 impl std::fmt::Display for OAuthProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            OAuthProvider::Google => write!(f, "google"),
-            OAuthProvider::Microsoft => write!(f, "microsoft"),
-        }
+        write!(f, "{}", self.0)
     }
 }
 
+/// Extra token response fields; `id_token` is only present for OpenID Connect providers
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IdTokenFields {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+pub type FridayTokenResponse = StandardTokenResponse<IdTokenFields, BasicTokenType>;
+
+/// Same as oauth2's `BasicClient`, but keeping the `id_token` of the token response
+pub type FridayOAuthClient = Client<
+    BasicErrorResponse,
+    FridayTokenResponse,
+    BasicTokenType,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+>;
+
 pub trait OAuthClientProvider {
-    fn create_client(&self) -> Result<BasicClient, Box<dyn Error>>;
-    fn get_auth_scopes(&self) -> Vec<Scope>;
-    fn get_additional_auth_params(&self) -> Vec<(&'static str, &'static str)>;
-    fn get_additional_token_params(&self) -> Vec<(&'static str, &'static str)>;
+    fn create_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>>;
+    /// Client for the client-credentials grant, pointing at the app token endpoint
+    fn create_app_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>>;
+    /// Base scopes plus the scopes of the requested profiles (default profiles when empty)
+    fn get_auth_scopes(&self, profiles: &[String]) -> Result<Vec<String>, String>;
+    /// Scopes requested for app-only tokens when the caller does not ask for any
+    fn get_app_scopes(&self) -> Vec<String>;
+    fn get_additional_auth_params(&self) -> Vec<(String, String)>;
+    fn get_additional_token_params(&self) -> Vec<(String, String)>;
 
     #[allow(dead_code)]
-    fn provider_name(&self) -> &'static str;
-}
-
-pub struct GoogleOAuthProvider {
-    pub client_id: String,
-    pub client_secret: String,
-    pub redirect_url: String,
+    fn provider_name(&self) -> &str;
 }
 
-pub struct MicrosoftOAuthProvider {
+/// OAuth client built from an `OAuthProviderConfig` entry of the registry
+pub struct ConfiguredOAuthProvider {
+    pub config: OAuthProviderConfig,
     pub client_id: String,
     pub client_secret: String,
-    pub redirect_url: String,
 }
 
-impl OAuthClientProvider for GoogleOAuthProvider {
-    fn create_client(&self) -> Result<BasicClient, Box<dyn Error>> {
+impl OAuthClientProvider for ConfiguredOAuthProvider {
+    fn create_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>> {
         let client_id = ClientId::new(self.client_id.clone());
         let client_secret = ClientSecret::new(self.client_secret.clone());
-        let auth_url = AuthUrl::new("https://accounts.google.com/o/oauth2/v2/auth".to_string())?;
-        let token_url = TokenUrl::new("https://oauth2.googleapis.com/token".to_string())?;
-        let redirect_url = RedirectUrl::new(self.redirect_url.clone())?;
+        let auth_url = AuthUrl::new(self.config.auth_url.clone())?;
+        let token_url = TokenUrl::new(self.config.token_url.clone())?;
+        let redirect_url = RedirectUrl::new(self.config.redirect_url.clone())?;
 
-        let client = BasicClient::new(client_id, Some(client_secret), auth_url, Some(token_url))
-            .set_redirect_uri(redirect_url);
+        let mut client =
+            FridayOAuthClient::new(client_id, Some(client_secret), auth_url, Some(token_url))
+                .set_redirect_uri(redirect_url);
 
-        Ok(client)
-    }
-
-    fn get_auth_scopes(&self) -> Vec<Scope> {
-        vec![Scope::new("https://mail.google.com/".to_string())]
-    }
+        if let Some(revocation_url) = &self.config.revocation_url {
+            client = client.set_revocation_uri(RevocationUrl::new(revocation_url.clone())?);
+        }
 
-    fn get_additional_auth_params(&self) -> Vec<(&'static str, &'static str)> {
-        vec![("access_type", "offline")]
-    }
+        if let Some(device_authorization_url) = &self.config.device_authorization_url {
+            client = client.set_device_authorization_url(DeviceAuthorizationUrl::new(
+                device_authorization_url.clone(),
+            )?);
+        }
 
-    fn get_additional_token_params(&self) -> Vec<(&'static str, &'static str)> {
-        vec![("access_type", "offline")]
+        Ok(client)
     }
 
-    fn provider_name(&self) -> &'static str {
-        "google"
+    fn create_app_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>> {
+        let app_token_url = self.config.app_token_url.as_ref().ok_or_else(|| {
+            format!(
+                "Provider {} does not support the client-credentials grant",
+                self.config.provider_name
+            )
+        })?;
+
+        Ok(FridayOAuthClient::new(
+            ClientId::new(self.client_id.clone()),
+            Some(ClientSecret::new(self.client_secret.clone())),
+            AuthUrl::new(self.config.auth_url.clone())?,
+            Some(TokenUrl::new(app_token_url.clone())?),
+        ))
     }
-}
-
-impl OAuthClientProvider for MicrosoftOAuthProvider {
-    fn create_client(&self) -> Result<BasicClient, Box<dyn Error>> {
-        let client_id = ClientId::new(self.client_id.clone());
-        let client_secret = ClientSecret::new(self.client_secret.clone());
-        let auth_url = AuthUrl::new(
-            "https://login.microsoftonline.com/common/oauth2/v2.0/authorize".to_string(),
-        )?;
-        let token_url = TokenUrl::new(
-            "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
-        )?;
-        let redirect_url = RedirectUrl::new(self.redirect_url.clone())?;
-
-        let client = BasicClient::new(client_id, Some(client_secret), auth_url, Some(token_url))
-            .set_redirect_uri(redirect_url);
 
-        Ok(client)
+    fn get_auth_scopes(&self, profiles: &[String]) -> Result<Vec<String>, String> {
+        self.config.resolve_scopes(profiles)
     }
 
-    fn get_auth_scopes(&self) -> Vec<Scope> {
-        vec![
-            Scope::new("offline_access".to_string()),
-            Scope::new("https://graph.microsoft.com/Tasks.ReadWrite".to_string()),
-        ]
+    fn get_app_scopes(&self) -> Vec<String> {
+        self.config.app_scopes.clone()
     }
 
-    fn get_additional_auth_params(&self) -> Vec<(&'static str, &'static str)> {
-        vec![] // Microsoft doesn't need additional auth params
+    fn get_additional_auth_params(&self) -> Vec<(String, String)> {
+        self.config.auth_params.clone()
     }
 
-    fn get_additional_token_params(&self) -> Vec<(&'static str, &'static str)> {
-        vec![] // Microsoft doesn't need additional token params
+    fn get_additional_token_params(&self) -> Vec<(String, String)> {
+        self.config.token_params.clone()
     }
 
-    fn provider_name(&self) -> &'static str {
-        "microsoft"
+    fn provider_name(&self) -> &str {
+        &self.config.provider_name
     }
 }
 
@@ -117,19 +174,13 @@ impl OAuthProviderFactory {
         provider: &OAuthProvider,
         client_id: String,
         client_secret: String,
-        redirect_url: String,
-    ) -> Box<dyn OAuthClientProvider> {
-        match provider {
-            OAuthProvider::Google => Box::new(GoogleOAuthProvider {
-                client_id,
-                client_secret,
-                redirect_url,
-            }),
-            OAuthProvider::Microsoft => Box::new(MicrosoftOAuthProvider {
-                client_id,
-                client_secret,
-                redirect_url,
-            }),
-        }
+    ) -> Result<Box<dyn OAuthClientProvider>, Box<dyn Error>> {
+        let config = oauth_provider_registry().get(provider)?.clone();
+
+        Ok(Box::new(ConfiguredOAuthProvider {
+            config,
+            client_id,
+            client_secret,
+        }))
     }
 }
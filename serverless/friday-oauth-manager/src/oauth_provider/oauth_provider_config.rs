@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Configuration of an OAuth provider loaded into the registry
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct OAuthProviderConfig {
+    /// Identifier stored in `tb_oauth_tokens.id_provider`
+    pub id_provider: i32,
+    /// Provider key used in requests and URLs (e.g. "microsoft", "github")
+    #[schema(example = "microsoft")]
+    pub provider_name: String,
+    pub auth_url: String,
+    pub token_url: String,
+    pub revocation_url: Option<String>,
+    /// RFC 8628 device authorization endpoint; providers without it cannot use the device flow
+    pub device_authorization_url: Option<String>,
+    /// OpenID Connect issuer; when set, `.well-known/openid-configuration` is used to
+    /// validate the ID token returned by the provider
+    pub issuer: Option<String>,
+    pub redirect_url: String,
+    /// Scopes requested on every authorization, regardless of the profiles
+    pub scopes: Vec<String>,
+    /// Named scope sets (e.g. "todo", "mail", "calendar") selectable at URL generation
+    pub scope_profiles: HashMap<String, Vec<String>>,
+    /// Profiles used when the caller does not ask for any
+    pub default_scope_profiles: Vec<String>,
+    /// Whether incremental consent can request only the new scopes with
+    /// `include_granted_scopes=true` (Google); otherwise all scopes are requested again
+    pub include_granted_scopes: bool,
+    /// Token endpoint for the client-credentials grant (app-only tokens); providers without
+    /// it cannot issue tokens with the application identity
+    pub app_token_url: Option<String>,
+    /// Scopes requested on the client-credentials grant when the caller does not ask for any
+    pub app_scopes: Vec<String>,
+    /// Base URL of the provider APIs reachable through the proxy (e.g. Microsoft Graph);
+    /// providers without it cannot be proxied
+    #[schema(example = "https://graph.microsoft.com")]
+    pub api_base_url: Option<String>,
+    /// Extra query params appended to the authorization URL
+    pub auth_params: Vec<(String, String)>,
+    /// Extra form params sent to the token endpoint
+    pub token_params: Vec<(String, String)>,
+    /// Secret manager key holding the client id
+    pub client_id_secret_key: String,
+    /// Secret manager key holding the client secret
+    pub client_secret_secret_key: String,
+}
+
+const DEFAULT_REDIRECT_URL: &str = "http://localhost:5000/callback";
+
+impl OAuthProviderConfig {
+    /// Base scopes plus the scopes of the given profiles (or of the default profiles
+    /// when none is given), without duplicates
+    pub fn resolve_scopes(&self, profiles: &[String]) -> Result<Vec<String>, String> {
+        let profiles = if profiles.is_empty() {
+            &self.default_scope_profiles
+        } else {
+            profiles
+        };
+
+        let mut scopes = self.scopes.clone();
+        merge_scopes(&mut scopes, &self.resolve_profile_scopes(profiles)?);
+
+        Ok(scopes)
+    }
+
+    /// Scopes of the given profiles only, without duplicates
+    pub fn resolve_profile_scopes(&self, profiles: &[String]) -> Result<Vec<String>, String> {
+        let mut scopes = vec![];
+
+        for profile in profiles {
+            let profile_scopes = self.scope_profiles.get(profile).ok_or_else(|| {
+                format!(
+                    "Unknown scope profile '{}' for provider {}",
+                    profile, self.provider_name
+                )
+            })?;
+
+            merge_scopes(&mut scopes, profile_scopes);
+        }
+
+        Ok(scopes)
+    }
+
+    /// Providers available even when `tb_oauth_providers` cannot be read
+    pub fn defaults() -> Vec<OAuthProviderConfig> {
+        vec![
+            OAuthProviderConfig {
+                id_provider: 1,
+                provider_name: "microsoft".to_string(),
+                auth_url: "https://login.microsoftonline.com/common/oauth2/v2.0/authorize"
+                    .to_string(),
+                token_url: "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
+                // Microsoft identity platform has no RFC 7009 revocation endpoint
+                revocation_url: None,
+                device_authorization_url: Some(
+                    "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode".to_string(),
+                ),
+                issuer: Some("https://login.microsoftonline.com/common/v2.0".to_string()),
+                redirect_url: DEFAULT_REDIRECT_URL.to_string(),
+                scopes: vec![
+                    "openid".to_string(),
+                    "profile".to_string(),
+                    "email".to_string(),
+                    "offline_access".to_string(),
+                ],
+                scope_profiles: HashMap::from([
+                    (
+                        "todo".to_string(),
+                        vec!["https://graph.microsoft.com/Tasks.ReadWrite".to_string()],
+                    ),
+                    (
+                        "mail".to_string(),
+                        vec![
+                            "https://graph.microsoft.com/Mail.ReadWrite".to_string(),
+                            "https://graph.microsoft.com/Mail.Send".to_string(),
+                        ],
+                    ),
+                    (
+                        "calendar".to_string(),
+                        vec!["https://graph.microsoft.com/Calendars.ReadWrite".to_string()],
+                    ),
+                ]),
+                default_scope_profiles: vec!["todo".to_string()],
+                include_granted_scopes: false,
+                // O client credentials exige o endpoint do tenant
+                // (https://login.microsoftonline.com/{tenant}/oauth2/v2.0/token),
+                // configurado em tb_oauth_providers.app_token_url
+                app_token_url: None,
+                app_scopes: vec!["https://graph.microsoft.com/.default".to_string()],
+                api_base_url: Some("https://graph.microsoft.com".to_string()),
+                auth_params: vec![],
+                token_params: vec![],
+                client_id_secret_key: "MICROSOFT_OAUTH_CLIENT_ID".to_string(),
+                client_secret_secret_key: "MICROSOFT_OAUTH_SECRET_VALUE".to_string(),
+            },
+            OAuthProviderConfig {
+                id_provider: 2,
+                provider_name: "google".to_string(),
+                auth_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+                token_url: "https://oauth2.googleapis.com/token".to_string(),
+                revocation_url: Some("https://oauth2.googleapis.com/revoke".to_string()),
+                device_authorization_url: Some(
+                    "https://oauth2.googleapis.com/device/code".to_string(),
+                ),
+                issuer: Some("https://accounts.google.com".to_string()),
+                redirect_url: DEFAULT_REDIRECT_URL.to_string(),
+                scopes: vec!["openid".to_string(), "email".to_string()],
+                scope_profiles: HashMap::from([
+                    (
+                        "todo".to_string(),
+                        vec!["https://www.googleapis.com/auth/tasks".to_string()],
+                    ),
+                    (
+                        "mail".to_string(),
+                        vec!["https://mail.google.com/".to_string()],
+                    ),
+                    (
+                        "calendar".to_string(),
+                        vec!["https://www.googleapis.com/auth/calendar".to_string()],
+                    ),
+                ]),
+                default_scope_profiles: vec!["mail".to_string()],
+                include_granted_scopes: true,
+                // Google usa contas de serviço em vez do client credentials
+                app_token_url: None,
+                app_scopes: vec![],
+                api_base_url: Some("https://www.googleapis.com".to_string()),
+                // prompt=consent garante que o Google devolva um refresh_token em toda autorização
+                auth_params: vec![
+                    ("access_type".to_string(), "offline".to_string()),
+                    ("prompt".to_string(), "consent".to_string()),
+                ],
+                token_params: vec![("access_type".to_string(), "offline".to_string())],
+                client_id_secret_key: "GOOGLE_OAUTH_CLIENT_ID".to_string(),
+                client_secret_secret_key: "GOOGLE_OAUTH_SECRET_VALUE".to_string(),
+            },
+        ]
+    }
+}
+
+/// Appends to `scopes` the entries of `additional` it does not contain yet
+pub fn merge_scopes(scopes: &mut Vec<String>, additional: &[String]) {
+    for scope in additional {
+        if !scopes.contains(scope) {
+            scopes.push(scope.clone());
+        }
+    }
+}
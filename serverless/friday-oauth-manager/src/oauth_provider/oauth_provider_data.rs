@@ -0,0 +1,57 @@
+use sqlx::{postgres::PgRow, PgPool, Row};
+use tracing::debug;
+
+use super::OAuthProviderConfig;
+
+pub async fn get_all_oauth_providers(
+    pool: &PgPool,
+) -> Result<Vec<OAuthProviderConfig>, Box<dyn std::error::Error>> {
+    let rows = sqlx::query("SELECT * FROM fn_get_all_oauth_providers()")
+        .fetch_all(pool)
+        .await?;
+
+    debug!(
+        "{} provedores OAuth encontrados no banco de dados",
+        rows.len()
+    );
+
+    rows.iter().map(provider_from_row).collect()
+}
+
+fn provider_from_row(row: &PgRow) -> Result<OAuthProviderConfig, Box<dyn std::error::Error>> {
+    let auth_params: serde_json::Value = row.try_get("auth_params")?;
+    let token_params: serde_json::Value = row.try_get("token_params")?;
+    let scope_profiles: serde_json::Value = row.try_get("scope_profiles")?;
+
+    Ok(OAuthProviderConfig {
+        id_provider: row.try_get("id_provider")?,
+        provider_name: row.try_get::<String, _>("provider_name")?.to_lowercase(),
+        auth_url: row.try_get("auth_url")?,
+        token_url: row.try_get("token_url")?,
+        revocation_url: row.try_get("revocation_url")?,
+        device_authorization_url: row.try_get("device_authorization_url")?,
+        issuer: row.try_get("issuer")?,
+        redirect_url: row.try_get("redirect_uri")?,
+        scopes: row.try_get("scopes")?,
+        scope_profiles: serde_json::from_value(scope_profiles)?,
+        default_scope_profiles: row.try_get("default_scope_profiles")?,
+        include_granted_scopes: row.try_get("include_granted_scopes")?,
+        app_token_url: row.try_get("app_token_url")?,
+        app_scopes: row.try_get("app_scopes")?,
+        api_base_url: row.try_get("api_base_url")?,
+        auth_params: params_from_json(auth_params),
+        token_params: params_from_json(token_params),
+        client_id_secret_key: row.try_get("client_id_secret_key")?,
+        client_secret_secret_key: row.try_get("client_secret_secret_key")?,
+    })
+}
+
+fn params_from_json(value: serde_json::Value) -> Vec<(String, String)> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .filter_map(|(key, value)| value.as_str().map(|v| (key, v.to_string())))
+            .collect(),
+        _ => vec![],
+    }
+}
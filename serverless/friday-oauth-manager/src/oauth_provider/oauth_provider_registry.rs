@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use once_cell::sync::OnceCell;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use super::{oauth_provider_data, OAuthProvider, OAuthProviderConfig};
+
+static OAUTH_PROVIDER_REGISTRY: OnceCell<OAuthProviderRegistry> = OnceCell::new();
+
+/// Registry of the OAuth providers known to the service, indexed by provider name
+pub struct OAuthProviderRegistry {
+    providers: HashMap<String, OAuthProviderConfig>,
+}
+
+impl OAuthProviderRegistry {
+    fn new(configs: Vec<OAuthProviderConfig>) -> Self {
+        let providers = configs
+            .into_iter()
+            .map(|config| (config.provider_name.clone(), config))
+            .collect();
+
+        Self { providers }
+    }
+
+    pub fn get(&self, provider: &OAuthProvider) -> Result<&OAuthProviderConfig, String> {
+        self.providers
+            .get(provider.name())
+            .ok_or_else(|| format!("Unknown OAuth provider: {}", provider))
+    }
+
+    pub fn get_by_id(&self, id_provider: i32) -> Result<&OAuthProviderConfig, String> {
+        self.providers
+            .values()
+            .find(|config| config.id_provider == id_provider)
+            .ok_or_else(|| format!("Unknown OAuth provider id: {}", id_provider))
+    }
+}
+
+/// Returns the loaded registry, falling back to the built-in providers if
+/// `load_oauth_provider_registry` has not run
+pub fn oauth_provider_registry() -> &'static OAuthProviderRegistry {
+    OAUTH_PROVIDER_REGISTRY
+        .get_or_init(|| OAuthProviderRegistry::new(OAuthProviderConfig::defaults()))
+}
+
+/// Loads providers from `tb_oauth_providers` on top of the built-in defaults.
+/// Must be called once at startup, before the HTTP server accepts requests.
+pub async fn load_oauth_provider_registry(pool: &PgPool) {
+    let mut configs = OAuthProviderConfig::defaults();
+
+    match oauth_provider_data::get_all_oauth_providers(pool).await {
+        Ok(db_configs) => {
+            for db_config in db_configs {
+                configs.retain(|config| config.provider_name != db_config.provider_name);
+                configs.push(db_config);
+            }
+        }
+        Err(e) => {
+            warn!(
+                "Falha ao carregar provedores OAuth do banco, usando apenas os padrões: {}",
+                e
+            );
+        }
+    }
+
+    let registry = OAuthProviderRegistry::new(configs);
+    info!(
+        "Provedores OAuth registrados: {:?}",
+        registry.providers.keys().collect::<Vec<_>>()
+    );
+
+    if OAUTH_PROVIDER_REGISTRY.set(registry).is_err() {
+        warn!("Registro de provedores OAuth já havia sido inicializado");
+    }
+}
@@ -0,0 +1,12 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Subset of the `.well-known/openid-configuration` document used by the service
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OidcDiscoveryResponse {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    pub revocation_endpoint: Option<String>,
+    pub device_authorization_endpoint: Option<String>,
+}
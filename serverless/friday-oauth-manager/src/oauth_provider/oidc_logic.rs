@@ -0,0 +1,159 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use once_cell::sync::Lazy;
+use tracing::{debug, info, warn};
+
+use super::{
+    oidc_discovery_response::OidcDiscoveryResponse, oidc_provider_api::OidcProviderApi,
+    oidc_user_claims::OidcUserClaims,
+};
+
+const DISCOVERY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const JWKS_TTL: Duration = Duration::from_secs(60 * 60);
+
+struct CachedOidcMetadata {
+    discovery: OidcDiscoveryResponse,
+    discovery_fetched_at: Instant,
+    jwks: JwkSet,
+    jwks_fetched_at: Instant,
+}
+
+/// Discovery e JWKS em cache, indexados pelo issuer
+static OIDC_METADATA: Lazy<Mutex<HashMap<String, CachedOidcMetadata>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+async fn get_oidc_metadata(
+    issuer: &str,
+    force_jwks_refresh: bool,
+) -> Result<(OidcDiscoveryResponse, JwkSet), Box<dyn std::error::Error>> {
+    let cached = OIDC_METADATA.lock().unwrap().get(issuer).map(|metadata| {
+        (
+            metadata.discovery.clone(),
+            metadata.discovery_fetched_at,
+            metadata.jwks.clone(),
+            metadata.jwks_fetched_at,
+        )
+    });
+
+    let api = OidcProviderApi::new(issuer);
+
+    let (discovery, discovery_fetched_at) = match &cached {
+        Some((discovery, fetched_at, _, _)) if fetched_at.elapsed() < DISCOVERY_TTL => {
+            (discovery.clone(), *fetched_at)
+        }
+        _ => (api.get_discovery_document().await?, Instant::now()),
+    };
+
+    let (jwks, jwks_fetched_at) = match &cached {
+        Some((_, _, jwks, fetched_at))
+            if !force_jwks_refresh && fetched_at.elapsed() < JWKS_TTL =>
+        {
+            (jwks.clone(), *fetched_at)
+        }
+        _ => (api.get_jwks(&discovery.jwks_uri).await?, Instant::now()),
+    };
+
+    OIDC_METADATA.lock().unwrap().insert(
+        issuer.to_string(),
+        CachedOidcMetadata {
+            discovery: discovery.clone(),
+            discovery_fetched_at,
+            jwks: jwks.clone(),
+            jwks_fetched_at,
+        },
+    );
+
+    Ok((discovery, jwks))
+}
+
+/// Nonce an ID token must carry
+#[derive(Debug, Clone, Copy)]
+pub enum ExpectedNonce<'a> {
+    /// Sent in the authorization URL; `None` means the authorization never issued one
+    Authorization(Option<&'a str>),
+    /// The device flow (RFC 8628) has no authorization URL to send a nonce in
+    DeviceFlow,
+}
+
+/// Validates signature, issuer, audience, expiry and nonce of an ID token
+pub async fn validate_id_token(
+    issuer: &str,
+    client_id: &str,
+    id_token: &str,
+    expected_nonce: ExpectedNonce<'_>,
+) -> Result<OidcUserClaims, Box<dyn std::error::Error>> {
+    let header = decode_header(id_token)?;
+    let kid = header.kid.ok_or("ID token header has no kid")?;
+
+    if !matches!(
+        header.alg,
+        Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512
+            | Algorithm::ES256
+            | Algorithm::ES384
+    ) {
+        return Err(format!("Unsupported ID token algorithm: {:?}", header.alg).into());
+    }
+
+    let (mut discovery, mut jwks) = get_oidc_metadata(issuer, false).await?;
+
+    if jwks.find(&kid).is_none() {
+        // O provedor pode ter rotacionado as chaves desde a última busca
+        info!(
+            "kid {} não encontrado no JWKS em cache, buscando novamente",
+            kid
+        );
+        (discovery, jwks) = get_oidc_metadata(issuer, true).await?;
+    }
+
+    let jwk = jwks
+        .find(&kid)
+        .ok_or_else(|| format!("No JWKS key found for kid {}", kid))?;
+    let decoding_key = DecodingKey::from_jwk(jwk)?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_audience(&[client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = decode::<OidcUserClaims>(id_token, &decoding_key, &validation)?.claims;
+
+    // O issuer multi-tenant da Microsoft vem como ".../{tenantid}/v2.0"
+    let expected_issuer = discovery
+        .issuer
+        .replace("{tenantid}", claims.tid.as_deref().unwrap_or_default());
+
+    if claims.iss != expected_issuer {
+        warn!(
+            "Issuer do ID token inválido: esperado {}, recebido {}",
+            expected_issuer, claims.iss
+        );
+        return Err(format!("Invalid ID token issuer: {}", claims.iss).into());
+    }
+
+    match expected_nonce {
+        ExpectedNonce::Authorization(Some(expected_nonce)) => {
+            if claims.nonce.as_deref() != Some(expected_nonce) {
+                warn!("Nonce do ID token não confere com o da URL de autorização");
+                return Err("Invalid ID token nonce".into());
+            }
+        }
+        ExpectedNonce::Authorization(None) => {
+            warn!("Autorização sem nonce para um provedor OpenID Connect");
+            return Err("No nonce was issued for this authorization".into());
+        }
+        ExpectedNonce::DeviceFlow => {}
+    }
+
+    debug!("ID token validado para sub={}", claims.sub);
+
+    Ok(claims)
+}
@@ -0,0 +1,47 @@
+use jsonwebtoken::jwk::JwkSet;
+use tracing::{error, info};
+
+use super::oidc_discovery_response::OidcDiscoveryResponse;
+
+pub struct OidcProviderApi {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OidcProviderApi {
+    pub fn new(issuer: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: issuer.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn get_discovery_document(
+        &self,
+    ) -> Result<OidcDiscoveryResponse, Box<dyn std::error::Error>> {
+        let url = format!("{}/.well-known/openid-configuration", self.base_url);
+        info!("Buscando documento de discovery OIDC: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            error!("Discovery OIDC falhou com status: {}", response.status());
+            return Err(format!("OIDC discovery failed with status: {}", response.status()).into());
+        }
+
+        Ok(response.json::<OidcDiscoveryResponse>().await?)
+    }
+
+    pub async fn get_jwks(&self, jwks_uri: &str) -> Result<JwkSet, Box<dyn std::error::Error>> {
+        info!("Buscando JWKS: {}", jwks_uri);
+
+        let response = self.client.get(jwks_uri).send().await?;
+
+        if !response.status().is_success() {
+            error!("Busca do JWKS falhou com status: {}", response.status());
+            return Err(format!("JWKS request failed with status: {}", response.status()).into());
+        }
+
+        Ok(response.json::<JwkSet>().await?)
+    }
+}
@@ -0,0 +1,27 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Verified claims of the ID token returned by an OpenID Connect provider
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct OidcUserClaims {
+    /// Stable identifier of the user at the provider
+    pub sub: String,
+    pub iss: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub name: Option<String>,
+    /// Microsoft sign-in name (UPN), used when `email` is absent
+    pub preferred_username: Option<String>,
+    /// Microsoft tenant id
+    pub tid: Option<String>,
+    #[serde(skip_serializing)]
+    pub nonce: Option<String>,
+}
+
+impl OidcUserClaims {
+    pub fn account_email(&self) -> Option<String> {
+        self.email
+            .clone()
+            .or_else(|| self.preferred_username.clone())
+    }
+}
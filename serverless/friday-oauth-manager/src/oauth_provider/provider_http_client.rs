@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use oauth2::{http::StatusCode, HttpRequest, HttpResponse};
+use once_cell::sync::Lazy;
+use ring::rand::{SecureRandom, SystemRandom};
+use tracing::warn;
+
+use super::{circuit_breaker, OAuthProvider};
+
+pub type ProviderHttpError = oauth2::reqwest::Error<reqwest::Error>;
+
+/// Attempts per request, including the first one
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+/// Longer `Retry-After` values are not waited for; the throttled response is returned
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        // Seguir redirecionamentos abre espaço para SSRF (mesma política do oauth2)
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to build the provider HTTP client")
+});
+
+/// HTTP client for the oauth2 requests sent to a provider. Transient failures (timeouts,
+/// connection errors, 5xx and 429) are retried with exponential backoff and jitter, honouring
+/// `Retry-After`, and requests are rejected while the provider's circuit is open.
+///
+/// The refresh grant is only retried when the connection was never established: after a
+/// timeout the provider may already have rotated the refresh token, and replaying the old one
+/// would be answered with `invalid_grant` and flag the credential as revoked.
+pub async fn provider_http_client(
+    provider: &OAuthProvider,
+    request: HttpRequest,
+) -> Result<HttpResponse, ProviderHttpError> {
+    circuit_breaker::acquire(provider).map_err(|message| {
+        warn!(provider = %provider, "{}", message);
+        ProviderHttpError::Other(message)
+    })?;
+
+    let is_refresh_grant = is_refresh_token_grant(&request);
+    let mut attempt = 1;
+
+    loop {
+        let outcome = send_request(&request).await;
+
+        let is_transient = match &outcome {
+            Ok(response) => is_transient_status(response.status_code),
+            Err(e) => is_transient_error(e),
+        };
+
+        if !is_transient {
+            circuit_breaker::record_success(provider);
+            return outcome;
+        }
+
+        let delay = match &outcome {
+            Ok(response) => get_retry_after(response),
+            Err(_) => None,
+        }
+        .unwrap_or_else(|| get_backoff(attempt));
+
+        let is_replay_safe = match &outcome {
+            Err(e) if is_refresh_grant => is_connect_error(e),
+            _ => true,
+        };
+
+        if attempt >= MAX_ATTEMPTS || delay > MAX_RETRY_AFTER || !is_replay_safe {
+            circuit_breaker::record_failure(provider);
+            return outcome;
+        }
+
+        warn!(
+            provider = %provider,
+            "Falha transitória no provedor ({}), tentativa {}/{}; nova tentativa em {}ms",
+            describe_outcome(&outcome),
+            attempt,
+            MAX_ATTEMPTS,
+            delay.as_millis()
+        );
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+async fn send_request(request: &HttpRequest) -> Result<HttpResponse, ProviderHttpError> {
+    let mut request_builder = HTTP_CLIENT
+        .request(request.method.clone(), request.url.as_str())
+        .body(request.body.clone());
+
+    for (name, value) in &request.headers {
+        request_builder = request_builder.header(name.as_str(), value.as_bytes());
+    }
+
+    let response = request_builder
+        .send()
+        .await
+        .map_err(ProviderHttpError::Reqwest)?;
+
+    let status_code = response.status();
+    let headers = response.headers().to_owned();
+    let body = response.bytes().await.map_err(ProviderHttpError::Reqwest)?;
+
+    Ok(HttpResponse {
+        status_code,
+        headers,
+        body: body.to_vec(),
+    })
+}
+
+fn is_transient_status(status_code: StatusCode) -> bool {
+    status_code.is_server_error() || status_code == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn is_transient_error(e: &ProviderHttpError) -> bool {
+    match e {
+        ProviderHttpError::Reqwest(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        ProviderHttpError::Io(_) => true,
+        ProviderHttpError::Http(_) | ProviderHttpError::Other(_) => false,
+    }
+}
+
+/// The request never reached the provider, so it is safe to send again
+fn is_connect_error(e: &ProviderHttpError) -> bool {
+    matches!(e, ProviderHttpError::Reqwest(e) if e.is_connect())
+}
+
+fn is_refresh_token_grant(request: &HttpRequest) -> bool {
+    oauth2::url::form_urlencoded::parse(&request.body)
+        .any(|(key, value)| key == "grant_type" && value == "refresh_token")
+}
+
+/// `Retry-After` in seconds or as an HTTP date
+fn get_retry_after(response: &HttpResponse) -> Option<Duration> {
+    let value = response
+        .headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (retry_at.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Exponential backoff with equal jitter: half of the delay is fixed, the other half random
+fn get_backoff(attempt: u32) -> Duration {
+    let backoff = BASE_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt - 1))
+        .min(MAX_BACKOFF);
+    let half = backoff / 2;
+
+    let mut random = [0u8; 4];
+    let jitter_ratio = match SystemRandom::new().fill(&mut random) {
+        Ok(()) => u32::from_le_bytes(random) as f64 / u32::MAX as f64,
+        Err(_) => 0.5,
+    };
+
+    half + half.mul_f64(jitter_ratio)
+}
+
+fn describe_outcome(outcome: &Result<HttpResponse, ProviderHttpError>) -> String {
+    match outcome {
+        Ok(response) => format!("HTTP {}", response.status_code),
+        Err(ProviderHttpError::Reqwest(e)) => e.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
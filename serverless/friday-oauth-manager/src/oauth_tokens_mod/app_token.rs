@@ -0,0 +1,45 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde_derive::Serialize;
+use utoipa::ToSchema;
+
+use crate::oauth_provider::OAuthProvider;
+
+/// App-only access token obtained with the client-credentials grant
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AppToken {
+    /// OAuth provider that issued the token
+    pub provider: OAuthProvider,
+    /// OAuth access token issued to the application identity
+    #[schema(example = "eyJ0eXAiOiJKV1QiLCJub25jZSI6...")]
+    pub access_token: String,
+    /// Token expiry date and time in UTC
+    pub expiry_date: DateTime<Utc>,
+    /// Scopes requested for the token
+    #[schema(example = json!(["https://graph.microsoft.com/.default"]))]
+    pub scopes: Vec<String>,
+}
+
+/// Provider and requested scopes
+type AppTokenKey = (OAuthProvider, Vec<String>);
+
+/// Tokens de aplicação em cache, indexados pelo provedor e pelos escopos solicitados
+static APP_TOKENS: Lazy<Mutex<HashMap<AppTokenKey, AppToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn get_cached_app_token(provider: &OAuthProvider, scopes: &[String]) -> Option<AppToken> {
+    APP_TOKENS
+        .lock()
+        .unwrap()
+        .get(&(provider.clone(), scopes.to_vec()))
+        .cloned()
+}
+
+pub fn cache_app_token(app_token: AppToken) {
+    APP_TOKENS.lock().unwrap().insert(
+        (app_token.provider.clone(), app_token.scopes.clone()),
+        app_token,
+    );
+}
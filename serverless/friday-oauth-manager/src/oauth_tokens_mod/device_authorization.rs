@@ -0,0 +1,82 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+use crate::{oauth_provider::OAuthProvider, oauth_tokens_mod::oauth_tokens::OAuthTokens};
+
+/// How long a finished device authorization is kept for the client to read its result
+const DEVICE_AUTHORIZATION_RESULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone)]
+pub enum DeviceAuthorizationStatus {
+    /// The user has not completed the authorization on the verification URI yet
+    Pending,
+    Completed(Box<OAuthTokens>),
+    Failed(String),
+}
+
+/// Device flow started through `exchange_device_code`, polled in the background
+#[derive(Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub provider: OAuthProvider,
+    pub status: DeviceAuthorizationStatus,
+    expires_at: Instant,
+}
+
+impl DeviceAuthorization {
+    pub fn new(provider: OAuthProvider, expires_in: Duration) -> Self {
+        Self {
+            provider,
+            status: DeviceAuthorizationStatus::Pending,
+            expires_at: Instant::now() + expires_in + DEVICE_AUTHORIZATION_RESULT_TTL,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+}
+
+/// Autorizações de dispositivo em andamento, indexadas pelo id devolvido ao cliente
+static DEVICE_AUTHORIZATIONS: Lazy<Mutex<HashMap<Uuid, DeviceAuthorization>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn remember_device_authorization(id_device_authorization: Uuid, device: DeviceAuthorization) {
+    let mut device_authorizations = DEVICE_AUTHORIZATIONS.lock().unwrap();
+    device_authorizations.retain(|_, device| !device.is_expired());
+    device_authorizations.insert(id_device_authorization, device);
+}
+
+pub fn update_device_authorization_status(
+    id_device_authorization: &Uuid,
+    status: DeviceAuthorizationStatus,
+) {
+    if let Some(device) = DEVICE_AUTHORIZATIONS
+        .lock()
+        .unwrap()
+        .get_mut(id_device_authorization)
+    {
+        device.status = status;
+    }
+}
+
+/// Current state of the device flow; finished flows are removed once read
+pub fn check_device_authorization(id_device_authorization: &Uuid) -> Option<DeviceAuthorization> {
+    let mut device_authorizations = DEVICE_AUTHORIZATIONS.lock().unwrap();
+
+    let device = device_authorizations
+        .get(id_device_authorization)
+        .filter(|device| !device.is_expired())
+        .cloned()?;
+
+    if !matches!(device.status, DeviceAuthorizationStatus::Pending) {
+        device_authorizations.remove(id_device_authorization);
+    }
+
+    Some(device)
+}
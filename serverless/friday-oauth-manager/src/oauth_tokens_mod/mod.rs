@@ -1,4 +1,9 @@
 pub mod oauth_tokens_logic;
 pub mod oauth_tokens_controller;
 pub mod oauth_tokens;
-mod oauth_tokens_data;
\ No newline at end of file
+pub mod oauth_tokens_status;
+pub mod app_token;
+mod device_authorization;
+mod oauth_tokens_data;
+mod pending_authorization;
+mod refresh_rate_limit;
\ No newline at end of file
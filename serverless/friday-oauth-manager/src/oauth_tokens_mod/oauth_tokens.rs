@@ -6,18 +6,8 @@ use uuid::Uuid;
 
 use crate::oauth_provider::OAuthProvider;
 
-impl From<i32> for OAuthProvider {
-    fn from(id: i32) -> Self {
-        match id {
-            1 => OAuthProvider::Microsoft,
-            2 => OAuthProvider::Google,
-            _ => panic!("Unknown OAuthProvider id: {}", id),
-        }
-    }
-}
-
 /// OAuth tokens with expiry information
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct OAuthTokens {
     /// Unique identifier for the token record
     pub id_oauth_tokens: Option<Uuid>,
@@ -31,37 +21,43 @@ pub struct OAuthTokens {
     pub refresh_token: String,
     /// Token expiry date and time in UTC
     pub expiry_date: DateTime<Utc>,
+    /// Scopes granted by the provider for this credential
+    #[schema(example = json!(["offline_access", "https://graph.microsoft.com/Tasks.ReadWrite"]))]
+    pub granted_scopes: Vec<String>,
+    /// `sub` claim of the verified ID token (OpenID Connect providers only)
+    pub subject: Option<String>,
+    /// Account e-mail from the verified ID token (OpenID Connect providers only)
+    #[schema(example = "user@example.com")]
+    pub email: Option<String>,
 }
 
 impl OAuthTokens {
     pub fn from_row(row: &PgRow) -> Result<Option<OAuthTokens>, Box<dyn std::error::Error>> {
-        let id_oauth_tokens: Uuid = row
-            .try_get("id_oauth_tokens")
-            .expect("Failed to parse id_oauth_tokens");
+        let id_oauth_tokens: Uuid = row.try_get("id_oauth_tokens")?;
+
+        let access_token: String = row.try_get("access_token")?;
+
+        let refresh_token: String = row.try_get("refresh_token")?;
+
+        let expiry_date: DateTime<Utc> = row.try_get("expiry_date")?;
 
-        let access_token: String = row
-            .try_get("access_token")
-            .expect("Failed to parse access_token");
+        let granted_scopes: Vec<String> = row.try_get("granted_scopes")?;
 
-        let refresh_token: String = row
-            .try_get("refresh_token")
-            .expect("Failed to parse refresh_token");
+        let subject: Option<String> = row.try_get("account_subject")?;
 
-        let expiry_date: DateTime<Utc> = row
-            .try_get("expiry_date")
-            .expect("Failed to parse expiry_date");
+        let email: Option<String> = row.try_get("account_email")?;
 
-        let id_provider = row
-            .try_get::<i32, _>("id_provider")
-            .map(OAuthProvider::from)
-            .expect("Failed to parse id_provider");
+        let id_provider = OAuthProvider::from_id(row.try_get("id_provider")?)?;
 
         let oauth_token = OAuthTokens {
             id_oauth_tokens: Some(id_oauth_tokens),
             access_token,
             refresh_token,
             expiry_date,
+            granted_scopes,
             id_provider,
+            subject,
+            email,
         };
 
         Ok(Some(oauth_token))
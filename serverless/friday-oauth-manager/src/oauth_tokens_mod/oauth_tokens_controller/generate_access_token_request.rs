@@ -0,0 +1,18 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use super::generate_oauth_url_request::split_comma_separated;
+
+/// Query parameters for access token generation
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+pub struct GenerateAccessTokenRequest {
+    /// Comma-separated scopes the stored credential must have been granted
+    #[param(example = "https://graph.microsoft.com/Tasks.ReadWrite")]
+    pub scopes: Option<String>,
+}
+
+impl GenerateAccessTokenRequest {
+    pub fn scopes(&self) -> Vec<String> {
+        split_comma_separated(self.scopes.as_deref())
+    }
+}
@@ -0,0 +1,18 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use super::generate_oauth_url_request::split_comma_separated;
+
+/// Query parameters for app-only token generation
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+pub struct GenerateAppTokenRequest {
+    /// Comma-separated scopes to request. The provider app scopes are used when omitted.
+    #[param(example = "https://graph.microsoft.com/.default")]
+    pub scopes: Option<String>,
+}
+
+impl GenerateAppTokenRequest {
+    pub fn scopes(&self) -> Vec<String> {
+        split_comma_separated(self.scopes.as_deref())
+    }
+}
@@ -0,0 +1,26 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+/// Query parameters for OAuth authorization URL generation
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+pub struct GenerateOAuthUrlRequest {
+    /// Comma-separated scope profiles to request (e.g. "todo,calendar").
+    /// The provider default profiles are used when omitted.
+    #[param(example = "todo,calendar")]
+    pub profiles: Option<String>,
+}
+
+impl GenerateOAuthUrlRequest {
+    pub fn profiles(&self) -> Vec<String> {
+        split_comma_separated(self.profiles.as_deref())
+    }
+}
+
+pub fn split_comma_separated(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
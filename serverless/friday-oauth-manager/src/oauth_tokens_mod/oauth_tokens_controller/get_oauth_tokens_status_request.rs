@@ -0,0 +1,10 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+/// Query parameters for the stored credentials status
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+pub struct GetOAuthTokensStatusRequest {
+    /// Registered provider name; all providers are listed when omitted
+    #[param(example = "microsoft")]
+    pub provider: Option<String>,
+}
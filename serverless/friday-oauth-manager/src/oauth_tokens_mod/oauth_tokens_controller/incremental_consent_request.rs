@@ -0,0 +1,18 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Request to add scopes to an existing credential without redoing the full consent
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct IncrementalConsentRequest {
+    /// Stored credential receiving the new scopes
+    pub id_oauth_tokens: Uuid,
+    /// Scope profiles to add (e.g. "calendar")
+    #[serde(default)]
+    #[schema(example = json!(["calendar"]))]
+    pub profiles: Vec<String>,
+    /// Raw scopes to add, in addition to the profiles
+    #[serde(default)]
+    #[schema(example = json!(["https://graph.microsoft.com/Calendars.ReadWrite"]))]
+    pub scopes: Vec<String>,
+}
@@ -1,6 +1,14 @@
+pub mod generate_access_token_request;
+pub mod generate_app_token_request;
+pub mod generate_oauth_url_request;
 pub mod get_oauth_tokens_request;
+pub mod get_oauth_tokens_status_request;
+pub mod incremental_consent_request;
+#[allow(clippy::module_inception)]
 pub mod oauth_tokens_controller;
 pub mod refresh_access_token_request;
+pub mod revoke_oauth_tokens_request;
+pub mod start_device_authorization_request;
 
 // Re-export the controller functions for easier access
 pub use oauth_tokens_controller::*;
@@ -1,15 +1,23 @@
-use actix_web::{get, post, Responder};
+use actix_web::{get, post, HttpResponse, Responder, ResponseError};
 use serde_json::json;
 use tracing::{error, info, warn};
 use utoipa;
+use uuid::Uuid;
 
 use crate::{
-    business_response::BusinessResponse,
+    database_mod::DatabasePool,
     oauth_provider::OAuthProvider,
     oauth_tokens_mod::{
         oauth_tokens_controller::{
+            generate_access_token_request::GenerateAccessTokenRequest,
+            generate_app_token_request::GenerateAppTokenRequest,
+            generate_oauth_url_request::GenerateOAuthUrlRequest,
             get_oauth_tokens_request::GetOAuthTokensRequest,
+            get_oauth_tokens_status_request::GetOAuthTokensStatusRequest,
+            incremental_consent_request::IncrementalConsentRequest,
             refresh_access_token_request::RefreshAccessTokenRequest,
+            revoke_oauth_tokens_request::RevokeOAuthTokensRequest,
+            start_device_authorization_request::StartDeviceAuthorizationRequest,
         },
         oauth_tokens_logic,
     },
@@ -24,31 +32,71 @@ extern crate dotenv;
 #[utoipa::path(
     get,
     path = "/api/friday-oauth-manager/oauth/generate-access-token",
+    params(GenerateAccessTokenRequest),
     responses(
         (status = 200, description = "Access token generated successfully", body = BusinessResponse),
+        (status = 401, description = "Refresh token revoked by the provider; data carries the reconsent_url", body = BusinessResponse),
+        (status = 403, description = "Stored credential lacks required scopes; data carries the missing_scopes", body = BusinessResponse),
+        (status = 404, description = "No stored credential available", body = BusinessResponse),
+        (status = 429, description = "Refresh rate limit of the credential reached", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth"
 )]
 #[get("/api/friday-oauth-manager/oauth/generate-access-token")]
-pub async fn generate_access_token() -> impl Responder {
+pub async fn generate_access_token(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    query: actix_web::web::Query<GenerateAccessTokenRequest>,
+) -> impl Responder {
     info!("Solicitação recebida para gerar access token");
 
-    match oauth_tokens_logic::generate_access_token().await {
+    match oauth_tokens_logic::generate_access_token(&database_pool.get(), query.scopes()).await {
         Ok(response) => {
-            if response.success {
-                info!("Access token gerado com sucesso");
-            } else {
-                warn!("Falha ao gerar access token: {:?}", response.errors);
-            }
-            actix_web::web::Json(response)
+            info!("Access token gerado com sucesso");
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            error!("Erro ao gerar access token: {}", e);
+            e.error_response()
         }
+    }
+}
+
+/// Generate an app-only access token
+///
+/// This endpoint obtains an access token for the application identity with the
+/// client-credentials grant, for service-to-service calls that must not run with a
+/// user's delegated token. Tokens are cached until they are about to expire.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/oauth/app-token/{provider}",
+    params(
+        ("provider" = String, Path, description = "Registered provider name"),
+        GenerateAppTokenRequest
+    ),
+    responses(
+        (status = 200, description = "App token generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "OAuth"
+)]
+#[get("/api/friday-oauth-manager/oauth/app-token/{provider}")]
+pub async fn generate_app_token(
+    provider: actix_web::web::Path<String>,
+    query: actix_web::web::Query<GenerateAppTokenRequest>,
+) -> impl Responder {
+    let provider = OAuthProvider::from(provider.into_inner());
+    info!(provider = %provider, "Solicitação recebida para gerar token de aplicação");
+
+    match oauth_tokens_logic::generate_app_token(provider, query.scopes()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
-            error!("Erro crítico ao gerar access token: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            error!("Erro ao gerar token de aplicação: {}", e);
+            e.error_response()
         }
     }
 }
@@ -63,24 +111,60 @@ pub async fn generate_access_token() -> impl Responder {
     request_body = RefreshAccessTokenRequest,
     responses(
         (status = 200, description = "Access token refreshed successfully", body = BusinessResponse),
+        (status = 401, description = "Refresh token revoked by the provider; data carries the reconsent_url", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 429, description = "Refresh rate limit of the credential reached", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth"
 )]
 #[post("/api/friday-oauth-manager/oauth/refresh-access-token")]
 pub async fn refresh_access_token(
+    database_pool: actix_web::web::Data<DatabasePool>,
     request: actix_web::web::Json<RefreshAccessTokenRequest>,
 ) -> impl Responder {
     info!("Fazendo refresh do access token");
 
-    match oauth_tokens_logic::refresh_access_token(request.into_inner()).await {
-        Ok(response) => actix_web::web::Json(response),
+    match oauth_tokens_logic::refresh_access_token(&database_pool.get(), request.into_inner()).await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Erro ao fazer refresh do access token: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            e.error_response()
+        }
+    }
+}
+
+/// Revoke OAuth tokens
+///
+/// This endpoint revokes the refresh token at the provider (when it exposes a
+/// revocation endpoint) and deletes the stored credential.
+#[utoipa::path(
+    post,
+    path = "/api/friday-oauth-manager/oauth/revoke",
+    request_body = RevokeOAuthTokensRequest,
+    responses(
+        (status = 200, description = "Tokens revoked successfully", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "OAuth"
+)]
+#[post("/api/friday-oauth-manager/oauth/revoke")]
+pub async fn revoke_oauth_tokens(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    request: actix_web::web::Json<RevokeOAuthTokensRequest>,
+) -> impl Responder {
+    let request = request.into_inner();
+    info!(provider = %request.provider, "Revogando tokens OAuth");
+
+    match oauth_tokens_logic::revoke_oauth_tokens(&database_pool.get(), request).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao revogar tokens OAuth: {}", e);
+            e.error_response()
         }
     }
 }
@@ -92,24 +176,25 @@ pub async fn refresh_access_token(
 #[utoipa::path(
     get,
     path = "/api/friday-oauth-manager/oauth/url",
+    params(GenerateOAuthUrlRequest),
     responses(
         (status = 200, description = "OAuth URL generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth URLs"
 )]
 #[get("/api/friday-oauth-manager/oauth/url")]
-pub async fn generate_oauth_url_endpoint() -> impl Responder {
+pub async fn generate_oauth_url_endpoint(
+    query: actix_web::web::Query<GenerateOAuthUrlRequest>,
+) -> impl Responder {
     info!("Gerando URL OAuth padrão (Microsoft)");
 
-    match oauth_tokens_logic::generate_oauth_url().await {
-        Ok(response) => actix_web::web::Json(response),
+    match oauth_tokens_logic::generate_oauth_url(query.profiles()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Erro ao gerar URL OAuth: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            e.error_response()
         }
     }
 }
@@ -121,24 +206,30 @@ pub async fn generate_oauth_url_endpoint() -> impl Responder {
 #[utoipa::path(
     get,
     path = "/api/friday-oauth-manager/oauth/url/google",
+    params(GenerateOAuthUrlRequest),
     responses(
         (status = 200, description = "Google OAuth URL generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth URLs"
 )]
 #[get("/api/friday-oauth-manager/oauth/url/google")]
-pub async fn generate_google_oauth_url() -> impl Responder {
+pub async fn generate_google_oauth_url(
+    query: actix_web::web::Query<GenerateOAuthUrlRequest>,
+) -> impl Responder {
     info!("Gerando URL OAuth do Google");
 
-    match oauth_tokens_logic::generate_oauth_url_for_provider(OAuthProvider::Google).await {
-        Ok(response) => actix_web::web::Json(response),
+    match oauth_tokens_logic::generate_oauth_url_for_provider(
+        OAuthProvider::from("google"),
+        query.profiles(),
+    )
+    .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Erro ao gerar URL OAuth do Google: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            e.error_response()
         }
     }
 }
@@ -150,24 +241,200 @@ pub async fn generate_google_oauth_url() -> impl Responder {
 #[utoipa::path(
     get,
     path = "/api/friday-oauth-manager/oauth/url/microsoft",
+    params(GenerateOAuthUrlRequest),
     responses(
         (status = 200, description = "Microsoft OAuth URL generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth URLs"
 )]
 #[get("/api/friday-oauth-manager/oauth/url/microsoft")]
-pub async fn generate_microsoft_oauth_url() -> impl Responder {
+pub async fn generate_microsoft_oauth_url(
+    query: actix_web::web::Query<GenerateOAuthUrlRequest>,
+) -> impl Responder {
     info!("Gerando URL OAuth da Microsoft");
 
-    match oauth_tokens_logic::generate_oauth_url_for_provider(OAuthProvider::Microsoft).await {
-        Ok(response) => actix_web::web::Json(response),
+    match oauth_tokens_logic::generate_oauth_url_for_provider(
+        OAuthProvider::from("microsoft"),
+        query.profiles(),
+    )
+    .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Erro ao gerar URL OAuth da Microsoft: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            e.error_response()
+        }
+    }
+}
+
+/// Generate OAuth authorization URL for any registered provider
+///
+/// This endpoint generates an OAuth authorization URL for a provider registered
+/// in `tb_oauth_providers` (e.g. github, spotify) or one of the built-in providers.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/oauth/url/{provider}",
+    params(
+        ("provider" = String, Path, description = "Registered provider name"),
+        GenerateOAuthUrlRequest
+    ),
+    responses(
+        (status = 200, description = "OAuth URL generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "OAuth URLs"
+)]
+#[get("/api/friday-oauth-manager/oauth/url/{provider}")]
+pub async fn generate_provider_oauth_url(
+    provider: actix_web::web::Path<String>,
+    query: actix_web::web::Query<GenerateOAuthUrlRequest>,
+) -> impl Responder {
+    let provider = OAuthProvider::from(provider.into_inner());
+    info!(provider = %provider, "Gerando URL OAuth");
+
+    match oauth_tokens_logic::generate_oauth_url_for_provider(provider, query.profiles()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao gerar URL OAuth: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// Generate an OAuth URL asking an existing credential for additional scopes
+///
+/// The generated URL requests only what is missing for the stored credential
+/// (`include_granted_scopes` for Google, the merged scopes for other providers).
+/// On callback the new grant is merged into the stored credential.
+#[utoipa::path(
+    post,
+    path = "/api/friday-oauth-manager/oauth/incremental-consent",
+    request_body = IncrementalConsentRequest,
+    responses(
+        (status = 200, description = "OAuth URL generated successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
+        (status = 404, description = "Credential not found", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "OAuth URLs"
+)]
+#[post("/api/friday-oauth-manager/oauth/incremental-consent")]
+pub async fn generate_incremental_consent_url(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    request: actix_web::web::Json<IncrementalConsentRequest>,
+) -> impl Responder {
+    info!(id_oauth_tokens = %request.id_oauth_tokens, "Gerando URL de consentimento incremental");
+
+    match oauth_tokens_logic::generate_incremental_consent_url(
+        &database_pool.get(),
+        request.into_inner(),
+    )
+    .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao gerar URL de consentimento incremental: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// Start the device authorization flow for headless clients
+///
+/// This endpoint starts the OAuth 2.0 device authorization grant (RFC 8628) and returns
+/// the user code and verification URI to show to the user. Tokens are requested in the
+/// background; poll the device authorization status until it is completed.
+#[utoipa::path(
+    post,
+    path = "/api/friday-oauth-manager/oauth/device/authorize",
+    request_body = StartDeviceAuthorizationRequest,
+    responses(
+        (status = 200, description = "Device authorization started successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid request", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "Device Authorization"
+)]
+#[post("/api/friday-oauth-manager/oauth/device/authorize")]
+pub async fn start_device_authorization(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    request: actix_web::web::Json<StartDeviceAuthorizationRequest>,
+) -> impl Responder {
+    info!(provider = %request.provider, "Iniciando autorização de dispositivo");
+
+    match oauth_tokens_logic::start_device_authorization(&database_pool.get(), request.into_inner())
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao iniciar autorização de dispositivo: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// Check a device authorization
+///
+/// Returns `pending` while the user has not completed the authorization, and the stored
+/// OAuth tokens once it is `completed`. Finished authorizations can be read only once.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/oauth/device/{id_device_authorization}",
+    params(
+        ("id_device_authorization" = Uuid, Path, description = "Id returned when the device authorization was started")
+    ),
+    responses(
+        (status = 200, description = "Device authorization status", body = BusinessResponse),
+        (status = 404, description = "Device authorization not found or expired", body = BusinessResponse)
+    ),
+    tag = "Device Authorization"
+)]
+#[get("/api/friday-oauth-manager/oauth/device/{id_device_authorization}")]
+pub async fn check_device_authorization(
+    id_device_authorization: actix_web::web::Path<Uuid>,
+) -> impl Responder {
+    match oauth_tokens_logic::check_device_authorization(id_device_authorization.into_inner()) {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            warn!("Autorização de dispositivo não encontrada: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// Status of the stored credentials
+///
+/// Lists the stored credentials per provider and account with expiry, granted scopes,
+/// last refresh result and revocation status. Token secrets are never returned.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/oauth/tokens/status",
+    params(GetOAuthTokensStatusRequest),
+    responses(
+        (status = 200, description = "Credentials status listed successfully", body = BusinessResponse),
+        (status = 404, description = "Unknown provider", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "OAuth"
+)]
+#[get("/api/friday-oauth-manager/oauth/tokens/status")]
+pub async fn get_oauth_tokens_status(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    query: actix_web::web::Query<GetOAuthTokensStatusRequest>,
+) -> impl Responder {
+    let provider = query.into_inner().provider.map(OAuthProvider::from);
+
+    match oauth_tokens_logic::get_oauth_tokens_status(&database_pool.get(), provider).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao listar status das credenciais: {}", e);
+            e.error_response()
         }
     }
 }
@@ -182,25 +449,26 @@ pub async fn generate_microsoft_oauth_url() -> impl Responder {
     request_body = GetOAuthTokensRequest,
     responses(
         (status = 200, description = "Tokens exchanged successfully", body = BusinessResponse),
+        (status = 400, description = "Invalid callback URL or authorization denied by the provider", body = BusinessResponse),
+        (status = 404, description = "Unknown provider or credential", body = BusinessResponse),
+        (status = 502, description = "OAuth provider error", body = BusinessResponse),
         (status = 500, description = "Internal server error", body = BusinessResponse)
     ),
     tag = "OAuth"
 )]
 #[post("/api/friday-oauth-manager/oauth/tokens")]
 pub async fn get_oauth_tokens(
+    database_pool: actix_web::web::Data<DatabasePool>,
     request: actix_web::web::Json<GetOAuthTokensRequest>,
 ) -> impl Responder {
     let request = request.into_inner();
     info!(provider = %request.provider, "Fazendo exchange de tokens OAuth");
 
-    match oauth_tokens_logic::get_oauth_tokens(request).await {
-        Ok(response) => actix_web::web::Json(response),
+    match oauth_tokens_logic::get_oauth_tokens(&database_pool.get(), request).await {
+        Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Erro ao fazer exchange de tokens OAuth: {}", e);
-            actix_web::web::Json(BusinessResponse::<serde_json::Value>::error(&format!(
-                "Erro interno: {}",
-                e
-            )))
+            e.error_response()
         }
     }
 }
@@ -0,0 +1,14 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::oauth_provider::OAuthProvider;
+
+/// Request to revoke OAuth tokens at the provider and delete the stored record
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RevokeOAuthTokensRequest {
+    /// The refresh token identifying the stored credential
+    #[schema(example = "1//0GWthWtnM1YzzCgYIARAAGAwSNwF-L9IrqcH...")]
+    pub refresh_token: String,
+    /// OAuth provider that issued the refresh token
+    pub provider: OAuthProvider,
+}
@@ -0,0 +1,15 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::oauth_provider::OAuthProvider;
+
+/// Request to start the device authorization flow (RFC 8628) for headless clients
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct StartDeviceAuthorizationRequest {
+    /// OAuth provider to authorize; must have a device authorization endpoint
+    pub provider: OAuthProvider,
+    /// Scope profiles to request; the provider default profiles are used when empty
+    #[serde(default)]
+    #[schema(example = json!(["todo"]))]
+    pub profiles: Vec<String>,
+}
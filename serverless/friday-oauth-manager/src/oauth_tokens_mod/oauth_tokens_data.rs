@@ -1,26 +1,22 @@
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::PgPool;
 use tracing::{debug, error};
+use uuid::Uuid;
 
-use crate::{oauth_tokens_mod::oauth_tokens::OAuthTokens, secret_manager_mod};
-
-async fn create_database_pool() -> Result<PgPool, Box<dyn std::error::Error>> {
-    let database_url = secret_manager_mod::get_database_url().await?;
-    let pool = PgPoolOptions::new().connect(&database_url).await?;
-
-    Ok(pool)
-}
+use crate::oauth_tokens_mod::{oauth_tokens::OAuthTokens, oauth_tokens_status::OAuthTokensStatus};
 
 pub async fn insert_oauth_token(
+    pool: &PgPool,
     oauth_tokens: &OAuthTokens,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let pool = create_database_pool().await?;
-
-    let result = sqlx::query("CALL pr_ins_oauth_tokens($1, $2, $3, $4)")
+    let result = sqlx::query("CALL pr_ins_oauth_tokens($1, $2, $3, $4, $5, $6, $7)")
         .bind(&oauth_tokens.access_token)
         .bind(&oauth_tokens.refresh_token)
         .bind(oauth_tokens.expiry_date)
-        .bind(oauth_tokens.id_provider.clone() as i32)
-        .execute(&pool)
+        .bind(oauth_tokens.id_provider.id()?)
+        .bind(&oauth_tokens.subject)
+        .bind(&oauth_tokens.email)
+        .bind(&oauth_tokens.granted_scopes)
+        .execute(pool)
         .await;
 
     if let Err(e) = result {
@@ -33,16 +29,45 @@ pub async fn insert_oauth_token(
     Ok(())
 }
 
+/// Atualiza o registro identificado por `previous_refresh_token`, gravando o refresh_token
+/// rotacionado pelo provedor (ou o mesmo, quando não houve rotação)
 pub async fn update_oauth_token_by_refresh_token(
+    pool: &PgPool,
+    previous_refresh_token: &str,
     oauth_tokens: &OAuthTokens,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let pool = create_database_pool().await?;
+    sqlx::query("CALL pr_upd_oauth_tokens_by_refresh_token($1, $2, $3, $4, $5)")
+        .bind(&oauth_tokens.access_token)
+        .bind(previous_refresh_token)
+        .bind(&oauth_tokens.refresh_token)
+        .bind(oauth_tokens.expiry_date)
+        .bind(&oauth_tokens.granted_scopes)
+        .execute(pool)
+        .await?;
+
+    debug!("Registro atualizado com sucesso");
 
-    sqlx::query("CALL pr_upd_oauth_tokens_by_refresh_token($1, $2, $3)")
+    Ok(())
+}
+
+/// Substitui os tokens de um registro existente (consentimento incremental)
+pub async fn update_oauth_token_by_id(
+    pool: &PgPool,
+    oauth_tokens: &OAuthTokens,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let id_oauth_tokens = oauth_tokens
+        .id_oauth_tokens
+        .ok_or("id_oauth_tokens is required to update oauth_tokens by id")?;
+
+    sqlx::query("CALL pr_upd_oauth_tokens_by_id($1, $2, $3, $4, $5, $6, $7)")
+        .bind(id_oauth_tokens)
         .bind(&oauth_tokens.access_token)
         .bind(&oauth_tokens.refresh_token)
         .bind(oauth_tokens.expiry_date)
-        .execute(&pool)
+        .bind(&oauth_tokens.granted_scopes)
+        .bind(&oauth_tokens.subject)
+        .bind(&oauth_tokens.email)
+        .execute(pool)
         .await?;
 
     debug!("Registro atualizado com sucesso");
@@ -50,15 +75,59 @@ pub async fn update_oauth_token_by_refresh_token(
     Ok(())
 }
 
+pub async fn update_oauth_token_revoked_by_refresh_token(
+    pool: &PgPool,
+    refresh_token: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_upd_oauth_tokens_revoked_by_refresh_token($1)")
+        .bind(refresh_token)
+        .execute(pool)
+        .await?;
+
+    debug!("Registro marcado como revogado");
+
+    Ok(())
+}
+
+/// Registra a falha da última tentativa de refresh
+pub async fn update_oauth_token_refresh_error_by_refresh_token(
+    pool: &PgPool,
+    refresh_token: &str,
+    refresh_error: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_upd_oauth_tokens_refresh_error_by_refresh_token($1, $2)")
+        .bind(refresh_token)
+        .bind(refresh_error)
+        .execute(pool)
+        .await?;
+
+    debug!("Falha de refresh registrada");
+
+    Ok(())
+}
+
+pub async fn delete_oauth_token_by_refresh_token(
+    pool: &PgPool,
+    refresh_token: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_del_oauth_tokens_by_refresh_token($1)")
+        .bind(refresh_token)
+        .execute(pool)
+        .await?;
+
+    debug!("Registro removido com sucesso");
+
+    Ok(())
+}
+
 pub async fn fn_get_first_oauth_tokens_by_last_expiry_date(
+    pool: &PgPool,
 ) -> Result<Option<OAuthTokens>, Box<dyn std::error::Error>> {
-    let pool = create_database_pool().await?;
-
     let query = "SELECT * FROM fn_get_first_oauth_tokens_by_last_expiry_date()";
 
     debug!("Executando query para buscar tokens: {}", query);
 
-    let row = sqlx::query(query).fetch_optional(&pool).await?;
+    let row = sqlx::query(query).fetch_optional(pool).await?;
 
     match row {
         Some(row) => {
@@ -78,3 +147,75 @@ pub async fn fn_get_first_oauth_tokens_by_last_expiry_date(
         }
     }
 }
+
+pub async fn get_oauth_tokens_by_refresh_token(
+    pool: &PgPool,
+    refresh_token: &str,
+) -> Result<Option<OAuthTokens>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_oauth_tokens_by_refresh_token($1)")
+        .bind(refresh_token)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(row) => OAuthTokens::from_row(&row),
+        None => {
+            debug!("Nenhum token encontrado para o refresh_token informado");
+            Ok(None)
+        }
+    }
+}
+
+/// Latest non-revoked credential of the provider
+pub async fn get_first_oauth_tokens_by_provider(
+    pool: &PgPool,
+    id_provider: i32,
+) -> Result<Option<OAuthTokens>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_first_oauth_tokens_by_provider($1)")
+        .bind(id_provider)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(row) => OAuthTokens::from_row(&row),
+        None => {
+            debug!("Nenhum token encontrado para id_provider={}", id_provider);
+            Ok(None)
+        }
+    }
+}
+
+pub async fn get_oauth_tokens_by_id(
+    pool: &PgPool,
+    id_oauth_tokens: Uuid,
+) -> Result<Option<OAuthTokens>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_oauth_tokens_by_id($1)")
+        .bind(id_oauth_tokens)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(row) => OAuthTokens::from_row(&row),
+        None => {
+            debug!(
+                "Nenhum token encontrado para id_oauth_tokens={}",
+                id_oauth_tokens
+            );
+            Ok(None)
+        }
+    }
+}
+
+pub async fn get_oauth_tokens_status(
+    pool: &PgPool,
+    id_provider: Option<i32>,
+) -> Result<Vec<OAuthTokensStatus>, Box<dyn std::error::Error>> {
+    let rows = sqlx::query("SELECT * FROM fn_get_oauth_tokens_status($1)")
+        .bind(id_provider)
+        .fetch_all(pool)
+        .await?;
+
+    debug!("{} credenciais encontradas", rows.len());
+
+    rows.iter().map(OAuthTokensStatus::from_row).collect()
+}
@@ -1,40 +1,75 @@
 use std::time::Duration;
 
-use chrono::Utc;
-use oauth2::{AuthorizationCode, CsrfToken, RefreshToken, RequestTokenError, TokenResponse};
+use chrono::{DateTime, Utc};
+use oauth2::{
+    basic::{BasicErrorResponse, BasicErrorResponseType},
+    AuthorizationCode, ConfigurationError, CsrfToken, ErrorResponseType, RefreshToken,
+    RequestTokenError, Scope, StandardDeviceAuthorizationResponse, StandardErrorResponse,
+    StandardRevocableToken, TokenResponse,
+};
 
 use serde_json::json;
+use sqlx::PgPool;
 use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 use crate::{
     business_response::BusinessResponse,
-    get_oauth_client,
-    oauth_provider::{OAuthProvider, OAuthProviderFactory},
+    credential_events_mod::{publish_credential_event, CredentialEvent, CredentialEventType},
+    get_oauth_app_client, get_oauth_client,
+    oauth_manager_error::OAuthManagerError,
+    oauth_provider::{
+        merge_scopes, oauth_provider_registry,
+        oidc_logic::{self, ExpectedNonce},
+        provider_http_client, FridayOAuthClient, FridayTokenResponse, OAuthProvider,
+        OAuthProviderConfig, OAuthProviderFactory, OidcUserClaims,
+    },
     oauth_tokens_mod::{
+        app_token::{self, AppToken},
+        device_authorization::{self, DeviceAuthorization, DeviceAuthorizationStatus},
         oauth_tokens::OAuthTokens,
         oauth_tokens_controller::{
             get_oauth_tokens_request::GetOAuthTokensRequest,
+            incremental_consent_request::IncrementalConsentRequest,
             refresh_access_token_request::RefreshAccessTokenRequest,
+            revoke_oauth_tokens_request::RevokeOAuthTokensRequest,
+            start_device_authorization_request::StartDeviceAuthorizationRequest,
         },
         oauth_tokens_data,
+        pending_authorization::{self, PendingAuthorization},
+        refresh_rate_limit,
     },
 };
 
+const EXPIRY_BUFFER_SECONDS: i64 = 30;
+/// Lifetime assumed when the provider omits `expires_in` (RFC 6749 only recommends it)
+const DEFAULT_EXPIRES_IN_SECONDS: u64 = 60 * 60;
+
 /// Business logic for obtaining OAuth tokens from authorization code
 pub async fn get_oauth_tokens(
+    pool: &PgPool,
     request: GetOAuthTokensRequest,
-) -> Result<BusinessResponse<serde_json::Value>, Box<dyn std::error::Error>> {
-    let client = get_oauth_client(request.provider.clone()).await?;
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    get_provider_config(&request.provider)?;
 
     let code = AuthorizationCode::new(extract_code_from_url(&request.url)?);
 
+    // Sem autorização pendente para o state não há como conferir CSRF nem nonce
+    let Some(pending) = extract_state_from_url(&request.url)?
+        .and_then(|state| pending_authorization::take_pending_authorization(&state))
+    else {
+        warn!(provider = %request.provider, "Autorização pendente não encontrada para o state do callback");
+        return Err(OAuthManagerError::BadRequest(
+            "state do callback desconhecido ou expirado; gere uma nova URL de autorização"
+                .to_string(),
+        ));
+    };
+
+    let client = get_oauth_client(request.provider.clone()).await?;
+
     // Create the provider to get additional params
-    let oauth_provider = OAuthProviderFactory::create_provider(
-        &request.provider,
-        String::new(),
-        String::new(),
-        String::new(),
-    );
+    let oauth_provider =
+        OAuthProviderFactory::create_provider(&request.provider, String::new(), String::new())?;
 
     let mut token_request = client.exchange_code(code);
 
@@ -44,73 +79,191 @@ pub async fn get_oauth_tokens(
     }
 
     let tokens_response = match token_request
-        .request_async(oauth2::reqwest::async_http_client)
+        .request_async(|http_request| provider_http_client(&request.provider, http_request))
         .await
     {
         Ok(resp) => resp,
         Err(e) => {
-            let detailed_msg = match &e {
-                RequestTokenError::ServerResponse(err) => {
-                    let desc = err
-                        .error_description()
-                        .map(|d| format!(", description: {}", d))
-                        .unwrap_or_default();
-                    format!(
-                        "OAuth server rejected the authorization code: error={}{}",
-                        err.error(),
-                        desc
-                    )
-                }
-                RequestTokenError::Request(req_err) => {
-                    format!("Network error during token exchange: {}", req_err)
-                }
-                RequestTokenError::Parse(parse_err, raw) => {
-                    format!(
-                        "Failed to parse token response: {} | raw body: {}",
-                        parse_err,
-                        String::from_utf8_lossy(raw)
-                    )
+            let detailed_msg = describe_token_error(&e);
+            error!(provider = %request.provider, "{}", detailed_msg);
+            return Err(OAuthManagerError::Provider(detailed_msg));
+        }
+    };
+
+    // Consentimento incremental: o novo grant é mesclado na credencial já armazenada
+    let existing_tokens = match pending.id_oauth_tokens {
+        Some(id_oauth_tokens) => {
+            match oauth_tokens_data::get_oauth_tokens_by_id(pool, id_oauth_tokens).await? {
+                Some(existing) if existing.id_provider == request.provider => Some(existing),
+                _ => {
+                    warn!(provider = %request.provider, %id_oauth_tokens, "Credencial do consentimento incremental não encontrada");
+                    return Err(OAuthManagerError::NotFound(
+                        "Credencial do consentimento incremental não encontrada para o provedor"
+                            .to_string(),
+                    ));
                 }
-                RequestTokenError::Other(msg) => {
-                    format!("Token exchange error: {}", msg)
+            }
+        }
+        None => None,
+    };
+
+    let refresh_token = match (handle_get_refresh_token(&tokens_response), &existing_tokens) {
+        (Some(refresh_token), _) => refresh_token,
+        (None, Some(existing)) => existing.refresh_token.clone(),
+        (None, None) => {
+            warn!(provider = %request.provider, "refresh_token não estava presente na resposta");
+            return Err(OAuthManagerError::BadRequest(
+                "O provedor não retornou refresh_token; refaça o consentimento com acesso offline"
+                    .to_string(),
+            ));
+        }
+    };
+
+    let user_claims = validate_user_claims(
+        &request.provider,
+        ExpectedNonce::Authorization(pending.nonce.as_deref()),
+        client.client_id().as_str(),
+        &tokens_response,
+    )
+    .await
+    .map_err(|e| OAuthManagerError::Provider(e.to_string()))?;
+
+    let mut oauth_tokens = extract_oauth_tokens(
+        tokens_response,
+        request.provider,
+        refresh_token,
+        pending.requested_scopes,
+    );
+    if let Some(claims) = &user_claims {
+        oauth_tokens.subject = Some(claims.sub.clone());
+        oauth_tokens.email = claims.account_email();
+    }
+
+    match existing_tokens {
+        Some(existing) => {
+            if let (Some(existing_subject), Some(subject)) =
+                (&existing.subject, &oauth_tokens.subject)
+            {
+                if existing_subject != subject {
+                    warn!(provider = %oauth_tokens.id_provider, "Consentimento incremental concedido por outra conta");
+                    return Err(OAuthManagerError::BadRequest(
+                        "O consentimento incremental foi concedido por uma conta diferente da credencial armazenada"
+                            .to_string(),
+                    ));
                 }
-            };
-            error!(provider = %request.provider, "{}", detailed_msg);
-            return Err(detailed_msg.into());
+            }
+
+            let mut granted_scopes = existing.granted_scopes;
+            merge_scopes(&mut granted_scopes, &oauth_tokens.granted_scopes);
+
+            oauth_tokens.id_oauth_tokens = existing.id_oauth_tokens;
+            oauth_tokens.granted_scopes = granted_scopes;
+            oauth_tokens.subject = oauth_tokens.subject.or(existing.subject);
+            oauth_tokens.email = oauth_tokens.email.or(existing.email);
+
+            oauth_tokens_data::update_oauth_token_by_id(pool, &oauth_tokens).await?;
+
+            info!(provider = %oauth_tokens.id_provider, "Escopos adicionais mesclados na credencial existente");
         }
+        None => oauth_tokens_data::insert_oauth_token(pool, &oauth_tokens).await?,
+    }
+
+    publish_credential_event(CredentialEvent::new(
+        CredentialEventType::TokenAcquired,
+        oauth_tokens.id_provider.clone(),
+        Some(&oauth_tokens),
+    ));
+
+    Ok(BusinessResponse::success(json!({
+        "oauth_tokens": oauth_tokens,
+        "user_claims": user_claims
+    })))
+}
+
+/// Validates the ID token of OpenID Connect providers, returning its verified claims
+async fn validate_user_claims(
+    provider: &OAuthProvider,
+    nonce: ExpectedNonce<'_>,
+    client_id: &str,
+    tokens_response: &FridayTokenResponse,
+) -> Result<Option<OidcUserClaims>, Box<dyn std::error::Error>> {
+    let config = oauth_provider_registry().get(provider)?;
+
+    let Some(issuer) = &config.issuer else {
+        return Ok(None);
     };
 
-    let oauth_tokens = extract_oauth_tokens(tokens_response, request.provider);
-    oauth_tokens_data::insert_oauth_token(&oauth_tokens).await?;
+    let Some(id_token) = &tokens_response.extra_fields().id_token else {
+        warn!(provider = %provider, "Provedor OIDC não retornou id_token");
+        return Ok(None);
+    };
 
-    Ok(BusinessResponse::success(
-        json!({ "oauth_tokens": oauth_tokens }),
-    ))
+    let claims = oidc_logic::validate_id_token(issuer, client_id, id_token, nonce)
+        .await
+        .map_err(|e| {
+            error!(provider = %provider, "ID token inválido: {}", e);
+            e
+        })?;
+
+    info!(provider = %provider, sub = %claims.sub, "ID token validado com sucesso");
+
+    Ok(Some(claims))
 }
 
-fn handle_get_refresh_token(
-    tokens_response: &oauth2::StandardTokenResponse<
-        oauth2::EmptyExtraTokenFields,
-        oauth2::basic::BasicTokenType,
-    >,
-) -> String {
-    let refresh_token = match tokens_response.refresh_token() {
-        Some(token) => token.secret().to_string(),
-        None => {
-            warn!("refresh_token não estava presente na resposta");
-            String::new()
+fn describe_token_error<RE, T>(e: &RequestTokenError<RE, StandardErrorResponse<T>>) -> String
+where
+    RE: std::error::Error + 'static,
+    T: ErrorResponseType + std::fmt::Display + 'static,
+{
+    match e {
+        RequestTokenError::ServerResponse(err) => {
+            let desc = err
+                .error_description()
+                .map(|d| format!(", description: {}", d))
+                .unwrap_or_default();
+            format!(
+                "OAuth server rejected the request: error={}{}",
+                err.error(),
+                desc
+            )
         }
-    };
+        RequestTokenError::Request(req_err) => {
+            format!("Network error during token request: {}", req_err)
+        }
+        RequestTokenError::Parse(parse_err, raw) => {
+            format!(
+                "Failed to parse token response: {} | raw body: {}",
+                parse_err,
+                String::from_utf8_lossy(raw)
+            )
+        }
+        RequestTokenError::Other(msg) => {
+            format!("Token request error: {}", msg)
+        }
+    }
+}
+
+fn is_invalid_grant<RE: std::error::Error + 'static>(
+    e: &RequestTokenError<RE, BasicErrorResponse>,
+) -> bool {
+    matches!(
+        e,
+        RequestTokenError::ServerResponse(err)
+            if *err.error() == BasicErrorResponseType::InvalidGrant
+    )
+}
 
-    refresh_token
+fn handle_get_refresh_token(tokens_response: &FridayTokenResponse) -> Option<String> {
+    tokens_response
+        .refresh_token()
+        .map(|token| token.secret().to_string())
 }
 
 fn extract_oauth_tokens(
-    tokens_response: oauth2::StandardTokenResponse<
-        oauth2::EmptyExtraTokenFields,
-        oauth2::basic::BasicTokenType,
-    >,
+    tokens_response: FridayTokenResponse,
     provider: OAuthProvider,
+    refresh_token: String,
+    requested_scopes: Vec<String>,
 ) -> OAuthTokens {
     let now = Utc::now();
     let access_token = tokens_response.access_token().secret().to_string();
@@ -119,59 +272,131 @@ fn extract_oauth_tokens(
         &access_token[..20.min(access_token.len())]
     ); // Log apenas o início do token
 
-    let refresh_token = handle_get_refresh_token(&tokens_response);
-
-    let expires_in = tokens_response.expires_in().unwrap().as_millis();
-    let expires_in_seconds = expires_in / 1000;
-    let expiry_date = now + Duration::from_millis(expires_in.try_into().unwrap());
+    let expires_in = get_expires_in(&tokens_response);
+    let expiry_date = now + expires_in;
 
     info!(
-        "Token criado: expires_in={}ms ({}s), current_time={}, expiry_date={}",
-        expires_in, expires_in_seconds, now, expiry_date
+        "Token criado: expires_in={}s, current_time={}, expiry_date={}",
+        expires_in.as_secs(),
+        now,
+        expiry_date
     );
 
-    let oauth_tokens = OAuthTokens {
+    // Sem `scope` na resposta, o provedor concedeu exatamente o que foi pedido (RFC 6749 §5.1)
+    let granted_scopes = match tokens_response.scopes() {
+        Some(scopes) => scopes.iter().map(|scope| scope.to_string()).collect(),
+        None => requested_scopes,
+    };
+
+    OAuthTokens {
         id_oauth_tokens: None,
         access_token,
         refresh_token,
         expiry_date,
+        granted_scopes,
         id_provider: provider,
-    };
+        subject: None,
+        email: None,
+    }
+}
+
+/// Lifetime of the access token, falling back to the default when `expires_in` is missing
+fn get_expires_in(tokens_response: &FridayTokenResponse) -> Duration {
+    tokens_response.expires_in().unwrap_or_else(|| {
+        warn!(
+            "Provedor não informou expires_in, assumindo {}s",
+            DEFAULT_EXPIRES_IN_SECONDS
+        );
+        Duration::from_secs(DEFAULT_EXPIRES_IN_SECONDS)
+    })
+}
+
+/// Authorization code of the callback URL. Callbacks carrying `error` are rejected with
+/// the provider's `error`/`error_description`.
+fn extract_code_from_url(url: &str) -> Result<String, OAuthManagerError> {
+    if let Some(error) = get_query_param(url, "error")? {
+        let error_description = get_query_param(url, "error_description")?;
+        warn!(
+            "Provedor retornou erro no callback: {} {:?}",
+            error, error_description
+        );
+        return Err(OAuthManagerError::AuthorizationDenied {
+            error,
+            error_description,
+        });
+    }
 
-    oauth_tokens
+    get_query_param(url, "code")?.ok_or_else(|| {
+        OAuthManagerError::BadRequest(
+            "Parâmetro code não encontrado na URL de callback".to_string(),
+        )
+    })
 }
 
-fn extract_code_from_url(url: &str) -> Result<String, Box<dyn std::error::Error>> {
-    let url = reqwest::Url::parse(url)?;
-    let code = url
+fn extract_state_from_url(url: &str) -> Result<Option<String>, OAuthManagerError> {
+    get_query_param(url, "state")
+}
+
+fn get_query_param(url: &str, name: &str) -> Result<Option<String>, OAuthManagerError> {
+    let url = reqwest::Url::parse(url)
+        .map_err(|e| OAuthManagerError::BadRequest(format!("URL de callback inválida: {}", e)))?;
+
+    Ok(url
         .query_pairs()
-        .find(|(key, _)| key == "code")
-        .map(|(_, value)| value.into_owned())
-        .ok_or_else(|| panic!("Code not found in URL"));
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned()))
+}
 
-    Ok(code.unwrap())
+/// Configuration of a registered provider; unknown providers are reported as not found
+fn get_provider_config(
+    provider: &OAuthProvider,
+) -> Result<&'static OAuthProviderConfig, OAuthManagerError> {
+    oauth_provider_registry()
+        .get(provider)
+        .map_err(OAuthManagerError::NotFound)
 }
 
 /// Business logic for refreshing an OAuth access token
 pub async fn refresh_access_token(
+    pool: &PgPool,
     request: RefreshAccessTokenRequest,
-) -> Result<BusinessResponse<serde_json::Value>, Box<dyn std::error::Error>> {
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    get_provider_config(&request.provider)?;
+
     let client = get_oauth_client(request.provider.clone()).await?;
 
     // Create the provider to get scopes and params
-    let oauth_provider = OAuthProviderFactory::create_provider(
-        &request.provider,
-        String::new(),
-        String::new(),
-        String::new(),
-    );
+    let oauth_provider =
+        OAuthProviderFactory::create_provider(&request.provider, String::new(), String::new())?;
+
+    let stored_tokens =
+        oauth_tokens_data::get_oauth_tokens_by_refresh_token(pool, &request.refresh_token).await?;
+    let requested_scopes = get_refresh_scopes(&request.provider, stored_tokens.as_ref())?;
+
+    // Limite por conta: um chamador com defeito não pode fazer o provedor limitar o app inteiro
+    let account_key = match stored_tokens
+        .as_ref()
+        .and_then(|tokens| tokens.id_oauth_tokens)
+    {
+        Some(id_oauth_tokens) => id_oauth_tokens.to_string(),
+        None => request.refresh_token.clone(),
+    };
+    if let Err(retry_after) = refresh_rate_limit::check_refresh_rate_limit(&account_key) {
+        warn!(provider = %request.provider, "Limite de refresh por conta atingido");
+        return Err(OAuthManagerError::TooManyRequests {
+            message: format!(
+                "Limite de refresh atingido para a credencial; tente novamente em {}s",
+                retry_after.as_secs() + 1
+            ),
+            retry_after,
+        });
+    }
 
     let refresh_token = RefreshToken::new(request.refresh_token.to_owned());
     let mut refresh_request = client.exchange_refresh_token(&refresh_token);
 
-    // Add provider-specific scopes
-    for scope in oauth_provider.get_auth_scopes() {
-        refresh_request = refresh_request.add_scope(scope);
+    for scope in &requested_scopes {
+        refresh_request = refresh_request.add_scope(Scope::new(scope.clone()));
     }
 
     // Add provider-specific parameters
@@ -180,122 +405,540 @@ pub async fn refresh_access_token(
     }
 
     match refresh_request
-        .request_async(oauth2::reqwest::async_http_client)
+        .request_async(|http_request| provider_http_client(&request.provider, http_request))
         .await
     {
         Ok(tokens_response) => {
-            let mut oauth_tokens = extract_oauth_tokens(tokens_response, request.provider.clone());
-            oauth_tokens.refresh_token = request.refresh_token;
+            let refresh_token = match handle_get_refresh_token(&tokens_response) {
+                Some(rotated) if rotated != request.refresh_token => {
+                    info!("Provedor rotacionou o refresh_token");
+                    rotated
+                }
+                _ => request.refresh_token.clone(),
+            };
 
-            if let Some(on_update_exception) =
-                oauth_tokens_data::update_oauth_token_by_refresh_token(&oauth_tokens)
-                    .await
-                    .err()
+            let mut oauth_tokens = extract_oauth_tokens(
+                tokens_response,
+                request.provider.clone(),
+                refresh_token,
+                requested_scopes,
+            );
+            if let Some(stored_tokens) = &stored_tokens {
+                oauth_tokens.id_oauth_tokens = stored_tokens.id_oauth_tokens;
+                oauth_tokens.subject = stored_tokens.subject.clone();
+                oauth_tokens.email = stored_tokens.email.clone();
+            }
+
+            // O provedor já invalidou o refresh_token anterior: sem salvar o novo, a
+            // credencial seria perdida sem aviso
+            if let Err(e) = oauth_tokens_data::update_oauth_token_by_refresh_token(
+                pool,
+                &request.refresh_token,
+                &oauth_tokens,
+            )
+            .await
             {
-                error!(
-                    "Erro ao tentar atualizar oauth tokens: {}",
-                    on_update_exception
-                );
+                error!("Erro ao tentar atualizar oauth tokens: {}", e);
+                return Err(OAuthManagerError::Internal(e));
             }
 
             info!("Access Token gerado com sucesso");
 
+            publish_credential_event(CredentialEvent::new(
+                CredentialEventType::TokenRefreshed,
+                request.provider.clone(),
+                Some(&oauth_tokens),
+            ));
+
             Ok(BusinessResponse::success(
                 json!({ "oauth_tokens": oauth_tokens }),
             ))
         }
+        Err(e) if is_invalid_grant(&e) => {
+            let detailed_msg = describe_token_error(&e);
+            warn!(provider = %request.provider, "Refresh token revogado: {}", detailed_msg);
+
+            oauth_tokens_data::update_oauth_token_revoked_by_refresh_token(
+                pool,
+                &request.refresh_token,
+            )
+            .await?;
+            record_refresh_error(pool, &request.refresh_token, &detailed_msg).await;
+
+            let reconsent_url = build_oauth_url(&request.provider, requested_scopes, None).await?;
+
+            publish_credential_event(CredentialEvent::refresh_failed(
+                request.provider.clone(),
+                stored_tokens.as_ref(),
+                detailed_msg.clone(),
+                Some(reconsent_url.clone()),
+            ));
+
+            Err(OAuthManagerError::Revoked {
+                message: detailed_msg,
+                provider: request.provider.to_string(),
+                reconsent_url,
+            })
+        }
         Err(e) => {
-            error!("Failed to refresh access token: {}", e);
-            Err(Box::new(e))
+            let detailed_msg = describe_token_error(&e);
+            error!(provider = %request.provider, "Failed to refresh access token: {}", detailed_msg);
+            record_refresh_error(pool, &request.refresh_token, &detailed_msg).await;
+
+            publish_credential_event(CredentialEvent::refresh_failed(
+                request.provider.clone(),
+                stored_tokens.as_ref(),
+                detailed_msg.clone(),
+                None,
+            ));
+
+            Err(OAuthManagerError::Provider(detailed_msg))
         }
     }
 }
 
-/// Business logic for generating access token using stored refresh tokens
+/// Keeps the last refresh failure for the status endpoint; failing to record it does not
+/// hide the original error
+async fn record_refresh_error(pool: &PgPool, refresh_token: &str, refresh_error: &str) {
+    if let Err(e) = oauth_tokens_data::update_oauth_token_refresh_error_by_refresh_token(
+        pool,
+        refresh_token,
+        refresh_error,
+    )
+    .await
+    {
+        error!("Erro ao registrar falha de refresh: {}", e);
+    }
+}
+
+/// Scopes to ask for on refresh: the provider base scopes plus the ones granted to the
+/// stored credential, or the default profiles when the credential predates scope tracking
+fn get_refresh_scopes(
+    provider: &OAuthProvider,
+    stored_tokens: Option<&OAuthTokens>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let config = oauth_provider_registry().get(provider)?;
+
+    match stored_tokens {
+        Some(tokens) if !tokens.granted_scopes.is_empty() => {
+            let mut scopes = config.scopes.clone();
+            merge_scopes(&mut scopes, &tokens.granted_scopes);
+            Ok(scopes)
+        }
+        _ => Ok(config.resolve_scopes(&[])?),
+    }
+}
+
+/// Business logic for revoking OAuth tokens at the provider and deleting the stored record
+pub async fn revoke_oauth_tokens(
+    pool: &PgPool,
+    request: RevokeOAuthTokensRequest,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    get_provider_config(&request.provider)?;
+
+    let client = get_oauth_client(request.provider.clone()).await?;
+
+    let stored_tokens =
+        oauth_tokens_data::get_oauth_tokens_by_refresh_token(pool, &request.refresh_token).await?;
+
+    let revocable_token =
+        StandardRevocableToken::RefreshToken(RefreshToken::new(request.refresh_token.clone()));
+
+    let provider_revoked = match client.revoke_token(revocable_token) {
+        Ok(revocation_request) => {
+            revocation_request
+                .request_async(|http_request| provider_http_client(&request.provider, http_request))
+                .await
+                .map_err(|e| {
+                    error!(provider = %request.provider, "Falha ao revogar token no provedor: {}", e);
+                    OAuthManagerError::Provider(e.to_string())
+                })?;
+            true
+        }
+        Err(ConfigurationError::MissingUrl(_)) => {
+            info!(
+                provider = %request.provider,
+                "Provedor não possui endpoint de revogação, removendo apenas o registro local"
+            );
+            false
+        }
+        Err(e) => return Err(OAuthManagerError::Internal(Box::new(e))),
+    };
+
+    oauth_tokens_data::delete_oauth_token_by_refresh_token(pool, &request.refresh_token).await?;
+
+    info!(provider = %request.provider, "OAuth tokens revogados com sucesso");
+
+    publish_credential_event(CredentialEvent::new(
+        CredentialEventType::TokenRevoked,
+        request.provider.clone(),
+        stored_tokens.as_ref(),
+    ));
+
+    Ok(BusinessResponse::success(json!({
+        "provider": request.provider.to_string(),
+        "provider_revoked": provider_revoked
+    })))
+}
+
+/// Business logic for generating access token using stored refresh tokens.
+/// When `required_scopes` is not empty, the stored credential must have been granted all of them.
 pub async fn generate_access_token(
-) -> Result<BusinessResponse<serde_json::Value>, Box<dyn std::error::Error>> {
+    pool: &PgPool,
+    required_scopes: Vec<String>,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
     let response_oauth_tokens =
-        oauth_tokens_data::fn_get_first_oauth_tokens_by_last_expiry_date().await?;
+        oauth_tokens_data::fn_get_first_oauth_tokens_by_last_expiry_date(pool).await?;
 
     match response_oauth_tokens {
         Some(oauth_tokens) => {
-            // Consider token expired if it will expire in the next 30 seconds (reduced buffer)
-            let now = chrono::Utc::now();
-            let expiry_buffer = chrono::Duration::seconds(30);
+            let missing_scopes = get_missing_scopes(&oauth_tokens, &required_scopes);
+            if !missing_scopes.is_empty() {
+                warn!("Escopos não concedidos ao token: {:?}", missing_scopes);
+                return Err(OAuthManagerError::MissingScopes {
+                    provider: oauth_tokens.id_provider.to_string(),
+                    missing_scopes,
+                });
+            }
 
             info!(
-                "Checking token expiry: current_time={}, token_expiry={}, buffer_time={}",
-                now,
-                oauth_tokens.expiry_date,
-                now + expiry_buffer
+                "Checking token expiry: current_time={}, token_expiry={}",
+                Utc::now(),
+                oauth_tokens.expiry_date
             );
 
-            if oauth_tokens.expiry_date > now + expiry_buffer {
+            if !is_token_expiring(oauth_tokens.expiry_date) {
                 // Token is still valid
                 info!("Token is still valid, returning existing access_token");
                 Ok(BusinessResponse::success(json!(oauth_tokens.access_token)))
             } else {
                 // Token expired or about to expire, refresh it
                 info!("Token expired or expiring soon, refreshing token");
-                let refresh_response = refresh_access_token(RefreshAccessTokenRequest {
-                    refresh_token: oauth_tokens.refresh_token,
-                    provider: oauth_tokens.id_provider,
-                })
+                let refresh_response = refresh_access_token(
+                    pool,
+                    RefreshAccessTokenRequest {
+                        refresh_token: oauth_tokens.refresh_token,
+                        provider: oauth_tokens.id_provider,
+                    },
+                )
                 .await?;
 
-                // Extract just the access_token from the refresh response to maintain consistency
-                if refresh_response.success {
-                    if let Some(data) = refresh_response.data {
-                        if let Some(oauth_tokens_obj) = data.get("oauth_tokens") {
-                            if let Some(access_token) = oauth_tokens_obj.get("access_token") {
-                                info!("Token refreshed successfully, returning new access_token");
-                                return Ok(BusinessResponse::success(json!(access_token
-                                    .as_str()
-                                    .unwrap_or(""))));
-                            }
-                        }
-                    }
-                }
+                // Falhas do refresh (credencial revogada, erro do provedor) já chegam como Err
+                let access_token = refresh_response
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("oauth_tokens"))
+                    .and_then(|oauth_tokens| oauth_tokens.get("access_token"))
+                    .and_then(|access_token| access_token.as_str())
+                    .ok_or_else(|| {
+                        OAuthManagerError::Internal("Resposta do refresh sem access_token".into())
+                    })?;
 
-                // If refresh failed, return the error
-                error!("Token refresh failed");
-                Ok(BusinessResponse::new(false, None, refresh_response.errors))
+                info!("Token refreshed successfully, returning new access_token");
+                Ok(BusinessResponse::success(json!(access_token)))
             }
         }
         None => {
             warn!("No refresh tokens found in database");
-            Ok(BusinessResponse::error(
-                "Não foram encontrados refresh_token disponíveis para geração do access_token",
+            Err(OAuthManagerError::NotFound(
+                "Não foram encontrados refresh_token disponíveis para geração do access_token"
+                    .to_string(),
             ))
         }
     }
 }
 
+/// Stored credential used on behalf of callers: the one given by `id_oauth_tokens`, or the
+/// latest credential of the provider
+pub async fn get_provider_credential(
+    pool: &PgPool,
+    provider: &OAuthProvider,
+    id_oauth_tokens: Option<Uuid>,
+) -> Result<OAuthTokens, OAuthManagerError> {
+    let id_provider = get_provider_config(provider)?.id_provider;
+
+    let oauth_tokens = match id_oauth_tokens {
+        Some(id_oauth_tokens) => oauth_tokens_data::get_oauth_tokens_by_id(pool, id_oauth_tokens)
+            .await?
+            .filter(|oauth_tokens| oauth_tokens.id_provider == *provider),
+        None => oauth_tokens_data::get_first_oauth_tokens_by_provider(pool, id_provider).await?,
+    };
+
+    oauth_tokens.ok_or_else(|| {
+        OAuthManagerError::NotFound(format!(
+            "Nenhuma credencial do provedor {} encontrada",
+            provider
+        ))
+    })
+}
+
+/// The credential with a usable access token, refreshed first when it is expiring or when
+/// `force_refresh` is set (e.g. the provider rejected the current access token)
+pub async fn get_fresh_oauth_tokens(
+    pool: &PgPool,
+    oauth_tokens: OAuthTokens,
+    force_refresh: bool,
+) -> Result<OAuthTokens, OAuthManagerError> {
+    if !force_refresh && !is_token_expiring(oauth_tokens.expiry_date) {
+        return Ok(oauth_tokens);
+    }
+
+    let refresh_response = refresh_access_token(
+        pool,
+        RefreshAccessTokenRequest {
+            refresh_token: oauth_tokens.refresh_token.clone(),
+            provider: oauth_tokens.id_provider.clone(),
+        },
+    )
+    .await?;
+
+    refresh_response
+        .data
+        .and_then(|mut data| data.get_mut("oauth_tokens").map(serde_json::Value::take))
+        .map(serde_json::from_value::<OAuthTokens>)
+        .transpose()
+        .map_err(|e| OAuthManagerError::Internal(Box::new(e)))?
+        .ok_or_else(|| OAuthManagerError::Internal("Resposta do refresh sem oauth_tokens".into()))
+}
+
+/// Tokens expiring in the next 30 seconds are treated as expired (reduced buffer)
+fn is_token_expiring(expiry_date: DateTime<Utc>) -> bool {
+    expiry_date <= Utc::now() + chrono::Duration::seconds(EXPIRY_BUFFER_SECONDS)
+}
+
+/// Business logic for generating an app-only access token with the client-credentials grant.
+/// The token is cached per provider and scopes until it is about to expire.
+pub async fn generate_app_token(
+    provider: OAuthProvider,
+    scopes: Vec<String>,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    if get_provider_config(&provider)?.app_token_url.is_none() {
+        return Err(OAuthManagerError::BadRequest(format!(
+            "O provedor {} não suporta tokens de aplicação (client credentials)",
+            provider
+        )));
+    }
+
+    let oauth_provider =
+        OAuthProviderFactory::create_provider(&provider, String::new(), String::new())?;
+
+    let scopes = if scopes.is_empty() {
+        oauth_provider.get_app_scopes()
+    } else {
+        scopes
+    };
+
+    if let Some(app_token) = app_token::get_cached_app_token(&provider, &scopes) {
+        if !is_token_expiring(app_token.expiry_date) {
+            info!(provider = %provider, "Token de aplicação ainda válido, retornando do cache");
+            return Ok(BusinessResponse::success(json!({ "app_token": app_token })));
+        }
+    }
+
+    let client = get_oauth_app_client(provider.clone()).await?;
+
+    let tokens_response = match client
+        .exchange_client_credentials()
+        .add_scopes(scopes.iter().cloned().map(Scope::new))
+        .request_async(|http_request| provider_http_client(&provider, http_request))
+        .await
+    {
+        Ok(resp) => resp,
+        Err(e) => {
+            let detailed_msg = describe_token_error(&e);
+            error!(provider = %provider, "Falha ao gerar token de aplicação: {}", detailed_msg);
+            return Err(OAuthManagerError::Provider(detailed_msg));
+        }
+    };
+
+    let app_token = AppToken {
+        provider,
+        access_token: tokens_response.access_token().secret().to_string(),
+        expiry_date: Utc::now() + get_expires_in(&tokens_response),
+        scopes,
+    };
+
+    app_token::cache_app_token(app_token.clone());
+
+    info!(
+        provider = %app_token.provider,
+        "Token de aplicação gerado: expiry_date={}",
+        app_token.expiry_date
+    );
+
+    Ok(BusinessResponse::success(json!({ "app_token": app_token })))
+}
+
+/// Required scopes not granted to the credential. Credentials stored before scope
+/// tracking have no granted scopes recorded and are not checked.
+fn get_missing_scopes(oauth_tokens: &OAuthTokens, required_scopes: &[String]) -> Vec<String> {
+    if oauth_tokens.granted_scopes.is_empty() {
+        return vec![];
+    }
+
+    required_scopes
+        .iter()
+        .filter(|required| {
+            !oauth_tokens
+                .granted_scopes
+                .iter()
+                .any(|granted| granted.eq_ignore_ascii_case(required))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Business logic for listing the stored credentials with expiry, scopes, last refresh
+/// result and revocation status, without their tokens
+pub async fn get_oauth_tokens_status(
+    pool: &PgPool,
+    provider: Option<OAuthProvider>,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let id_provider = match &provider {
+        Some(provider) => match provider.id() {
+            Ok(id_provider) => Some(id_provider),
+            Err(message) => {
+                warn!(provider = %provider, "{}", message);
+                return Err(OAuthManagerError::NotFound(message));
+            }
+        },
+        None => None,
+    };
+
+    let credentials = oauth_tokens_data::get_oauth_tokens_status(pool, id_provider).await?;
+
+    info!("{} credenciais listadas", credentials.len());
+
+    Ok(BusinessResponse::success(
+        json!({ "credentials": credentials }),
+    ))
+}
+
 /// Business logic for generating OAuth authorization URL for default provider (Microsoft)
 pub async fn generate_oauth_url(
-) -> Result<BusinessResponse<serde_json::Value>, Box<dyn std::error::Error>> {
-    generate_oauth_url_for_provider(OAuthProvider::Microsoft).await
+    profiles: Vec<String>,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    generate_oauth_url_for_provider(OAuthProvider::from("microsoft"), profiles).await
 }
 
 /// Business logic for generating OAuth authorization URL for specific provider
 pub async fn generate_oauth_url_for_provider(
     provider: OAuthProvider,
-) -> Result<BusinessResponse<serde_json::Value>, Box<dyn std::error::Error>> {
+    profiles: Vec<String>,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    get_provider_config(&provider)?;
+
+    let oauth_provider =
+        OAuthProviderFactory::create_provider(&provider, String::new(), String::new())?;
+
+    let scopes = match oauth_provider.get_auth_scopes(&profiles) {
+        Ok(scopes) => scopes,
+        Err(message) => {
+            warn!(provider = %provider, "{}", message);
+            return Err(OAuthManagerError::BadRequest(message));
+        }
+    };
+
+    let auth_url = build_oauth_url(&provider, scopes.clone(), None).await?;
+
+    Ok(BusinessResponse::success(json!({
+        "url": auth_url,
+        "provider": provider.to_string(),
+        "scopes": scopes
+    })))
+}
+
+/// Business logic for generating an authorization URL that asks an existing credential
+/// for additional scopes, merged into the stored grant on callback
+pub async fn generate_incremental_consent_url(
+    pool: &PgPool,
+    request: IncrementalConsentRequest,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let Some(stored_tokens) =
+        oauth_tokens_data::get_oauth_tokens_by_id(pool, request.id_oauth_tokens).await?
+    else {
+        return Err(OAuthManagerError::NotFound(format!(
+            "Credencial {} não encontrada",
+            request.id_oauth_tokens
+        )));
+    };
+
+    let provider = stored_tokens.id_provider.clone();
+    let config = get_provider_config(&provider)?;
+
+    let mut additional_scopes = match config.resolve_profile_scopes(&request.profiles) {
+        Ok(scopes) => scopes,
+        Err(message) => {
+            warn!(provider = %provider, "{}", message);
+            return Err(OAuthManagerError::BadRequest(message));
+        }
+    };
+    merge_scopes(&mut additional_scopes, &request.scopes);
+
+    if additional_scopes.is_empty() {
+        return Err(OAuthManagerError::BadRequest(
+            "Informe ao menos um perfil ou escopo adicional".to_string(),
+        ));
+    }
+
+    let new_scopes = get_missing_scopes(&stored_tokens, &additional_scopes);
+    if new_scopes.is_empty() && !stored_tokens.granted_scopes.is_empty() {
+        return Err(OAuthManagerError::BadRequest(
+            "Todos os escopos solicitados já foram concedidos à credencial".to_string(),
+        ));
+    }
+
+    // Google mantém os escopos já concedidos com include_granted_scopes; os demais
+    // provedores precisam receber novamente todos os escopos da credencial
+    let mut scopes = config.scopes.clone();
+    if !config.include_granted_scopes {
+        merge_scopes(&mut scopes, &stored_tokens.granted_scopes);
+    }
+    merge_scopes(&mut scopes, &additional_scopes);
+
+    let auth_url = build_oauth_url(&provider, scopes.clone(), Some(&stored_tokens)).await?;
+
+    info!(provider = %provider, "URL de consentimento incremental gerada");
+
+    Ok(BusinessResponse::success(json!({
+        "url": auth_url,
+        "provider": provider.to_string(),
+        "scopes": scopes,
+        "new_scopes": new_scopes
+    })))
+}
+
+/// Builds the authorization URL and remembers its state; `existing_tokens` is the stored
+/// credential the grant is merged into on callback (incremental consent)
+async fn build_oauth_url(
+    provider: &OAuthProvider,
+    scopes: Vec<String>,
+    existing_tokens: Option<&OAuthTokens>,
+) -> Result<String, Box<dyn std::error::Error>> {
     let client = get_oauth_client(provider.clone()).await?;
 
-    // Create the provider to get scopes and params
+    // Create the provider to get params
     let oauth_provider = OAuthProviderFactory::create_provider(
-        &provider,
-        String::new(), // We don't need credentials for just getting scopes
-        String::new(),
+        provider,
+        String::new(), // We don't need credentials for just getting params
         String::new(),
-    );
+    )?;
+    let config = oauth_provider_registry().get(provider)?;
 
     let mut auth_url_builder = client.authorize_url(CsrfToken::new_random);
 
-    // Add provider-specific scopes
-    for scope in oauth_provider.get_auth_scopes() {
-        auth_url_builder = auth_url_builder.add_scope(scope);
+    // OpenID Connect providers get a nonce, checked against the ID token on callback
+    let nonce = config
+        .issuer
+        .as_ref()
+        .map(|_| CsrfToken::new_random().secret().clone());
+
+    if let Some(nonce) = &nonce {
+        auth_url_builder = auth_url_builder.add_extra_param("nonce", nonce.clone());
+    }
+
+    for scope in &scopes {
+        auth_url_builder = auth_url_builder.add_scope(Scope::new(scope.clone()));
     }
 
     // Add provider-specific parameters
@@ -303,12 +946,215 @@ pub async fn generate_oauth_url_for_provider(
         auth_url_builder = auth_url_builder.add_extra_param(key, value);
     }
 
-    let (auth_url, _) = auth_url_builder.url();
+    if let Some(existing_tokens) = existing_tokens {
+        if config.include_granted_scopes {
+            auth_url_builder = auth_url_builder.add_extra_param("include_granted_scopes", "true");
+        }
+        if let Some(email) = &existing_tokens.email {
+            auth_url_builder = auth_url_builder.add_extra_param("login_hint", email.clone());
+        }
+    }
 
-    debug!("Generated {} OAuth URL: {}", provider, auth_url.to_string());
+    let (auth_url, csrf_token) = auth_url_builder.url();
 
-    Ok(BusinessResponse::success(json!({
-        "url": auth_url.to_string(),
-        "provider": provider.to_string()
-    })))
+    pending_authorization::remember_pending_authorization(
+        csrf_token.secret(),
+        PendingAuthorization::new(
+            nonce,
+            scopes,
+            existing_tokens.and_then(|tokens| tokens.id_oauth_tokens),
+        ),
+    );
+
+    debug!("Generated {} OAuth URL: {}", provider, auth_url);
+
+    Ok(auth_url.to_string())
+}
+
+/// Business logic for starting the device authorization flow (RFC 8628). Tokens are
+/// requested in the background until the user completes the authorization or the code expires.
+pub async fn start_device_authorization(
+    pool: &PgPool,
+    request: StartDeviceAuthorizationRequest,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let provider = request.provider;
+
+    let scopes = {
+        let config = get_provider_config(&provider)?;
+
+        if config.device_authorization_url.is_none() {
+            return Err(OAuthManagerError::BadRequest(format!(
+                "O provedor {} não suporta o fluxo de autorização de dispositivo",
+                provider
+            )));
+        }
+
+        match config.resolve_scopes(&request.profiles) {
+            Ok(scopes) => scopes,
+            Err(message) => {
+                warn!(provider = %provider, "{}", message);
+                return Err(OAuthManagerError::BadRequest(message));
+            }
+        }
+    };
+
+    let client = get_oauth_client(provider.clone()).await?;
+
+    let details: StandardDeviceAuthorizationResponse = match client
+        .exchange_device_code()
+        .map_err(|e| OAuthManagerError::Internal(Box::new(e)))?
+        .add_scopes(scopes.iter().cloned().map(Scope::new))
+        .request_async(|http_request| provider_http_client(&provider, http_request))
+        .await
+    {
+        Ok(details) => details,
+        Err(e) => {
+            let detailed_msg = describe_token_error(&e);
+            error!(provider = %provider, "Falha ao iniciar autorização de dispositivo: {}", detailed_msg);
+            return Err(OAuthManagerError::Provider(detailed_msg));
+        }
+    };
+
+    let id_device_authorization = Uuid::new_v4();
+    device_authorization::remember_device_authorization(
+        id_device_authorization,
+        DeviceAuthorization::new(provider.clone(), details.expires_in()),
+    );
+
+    let response = json!({
+        "id_device_authorization": id_device_authorization,
+        "provider": provider.to_string(),
+        "user_code": details.user_code().secret(),
+        "verification_uri": details.verification_uri().as_str(),
+        "verification_uri_complete": details
+            .verification_uri_complete()
+            .map(|uri| uri.secret().clone()),
+        "expires_in": details.expires_in().as_secs(),
+        "interval": details.interval().as_secs(),
+        "scopes": scopes
+    });
+
+    info!(provider = %provider, %id_device_authorization, "Autorização de dispositivo iniciada");
+
+    actix_web::rt::spawn(poll_device_authorization(
+        pool.clone(),
+        id_device_authorization,
+        client,
+        details,
+        provider,
+        scopes,
+    ));
+
+    Ok(BusinessResponse::success(response))
+}
+
+async fn poll_device_authorization(
+    pool: PgPool,
+    id_device_authorization: Uuid,
+    client: FridayOAuthClient,
+    details: StandardDeviceAuthorizationResponse,
+    provider: OAuthProvider,
+    requested_scopes: Vec<String>,
+) {
+    let status = match exchange_device_access_token(
+        &pool,
+        &client,
+        &details,
+        provider.clone(),
+        requested_scopes,
+    )
+    .await
+    {
+        Ok(oauth_tokens) => {
+            info!(provider = %provider, %id_device_authorization, "Autorização de dispositivo concluída");
+            publish_credential_event(CredentialEvent::new(
+                CredentialEventType::TokenAcquired,
+                provider.clone(),
+                Some(&oauth_tokens),
+            ));
+            DeviceAuthorizationStatus::Completed(Box::new(oauth_tokens))
+        }
+        Err(e) => {
+            warn!(provider = %provider, %id_device_authorization, "Autorização de dispositivo falhou: {}", e);
+            DeviceAuthorizationStatus::Failed(e.to_string())
+        }
+    };
+
+    device_authorization::update_device_authorization_status(&id_device_authorization, status);
+}
+
+/// Polls the token endpoint at the interval given by the provider and stores the tokens
+async fn exchange_device_access_token(
+    pool: &PgPool,
+    client: &FridayOAuthClient,
+    details: &StandardDeviceAuthorizationResponse,
+    provider: OAuthProvider,
+    requested_scopes: Vec<String>,
+) -> Result<OAuthTokens, Box<dyn std::error::Error>> {
+    let tokens_response = client
+        .exchange_device_access_token(details)
+        .request_async(
+            |http_request| provider_http_client(&provider, http_request),
+            tokio::time::sleep,
+            Some(details.expires_in()),
+        )
+        .await
+        .map_err(|e| describe_token_error(&e))?;
+
+    let refresh_token = handle_get_refresh_token(&tokens_response)
+        .ok_or("O provedor não retornou refresh_token; solicite acesso offline")?;
+
+    let user_claims = validate_user_claims(
+        &provider,
+        ExpectedNonce::DeviceFlow,
+        client.client_id().as_str(),
+        &tokens_response,
+    )
+    .await?;
+
+    let mut oauth_tokens =
+        extract_oauth_tokens(tokens_response, provider, refresh_token, requested_scopes);
+    if let Some(claims) = &user_claims {
+        oauth_tokens.subject = Some(claims.sub.clone());
+        oauth_tokens.email = claims.account_email();
+    }
+
+    oauth_tokens_data::insert_oauth_token(pool, &oauth_tokens).await?;
+
+    Ok(oauth_tokens)
+}
+
+/// Business logic for checking a device authorization; tokens are returned once completed
+pub fn check_device_authorization(
+    id_device_authorization: Uuid,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let Some(device) = device_authorization::check_device_authorization(&id_device_authorization)
+    else {
+        return Err(OAuthManagerError::NotFound(format!(
+            "Autorização de dispositivo {} não encontrada ou expirada",
+            id_device_authorization
+        )));
+    };
+
+    let provider = device.provider.to_string();
+
+    Ok(match device.status {
+        DeviceAuthorizationStatus::Pending => BusinessResponse::success(json!({
+            "status": "pending",
+            "provider": provider
+        })),
+        DeviceAuthorizationStatus::Completed(oauth_tokens) => BusinessResponse::success(json!({
+            "status": "completed",
+            "provider": provider,
+            "oauth_tokens": oauth_tokens
+        })),
+        DeviceAuthorizationStatus::Failed(message) => BusinessResponse::new(
+            false,
+            Some(json!({
+                "status": "failed",
+                "provider": provider
+            })),
+            vec![message],
+        ),
+    })
 }
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Row};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::oauth_provider::OAuthProvider;
+
+/// Health of a stored credential. Never carries the access or refresh token.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct OAuthTokensStatus {
+    pub id_oauth_tokens: Uuid,
+    pub id_provider: OAuthProvider,
+    /// `sub` claim of the verified ID token (OpenID Connect providers only)
+    pub subject: Option<String>,
+    #[schema(example = "user@example.com")]
+    pub email: Option<String>,
+    #[schema(example = json!(["offline_access", "https://graph.microsoft.com/Tasks.ReadWrite"]))]
+    pub granted_scopes: Vec<String>,
+    /// Expiry of the current access token
+    pub expiry_date: DateTime<Utc>,
+    /// Whether the current access token has already expired
+    pub expired: bool,
+    /// Last refresh attempt, successful or not
+    pub last_refresh_at: Option<DateTime<Utc>>,
+    /// Error of the last refresh attempt; empty when it succeeded
+    pub last_refresh_error: Option<String>,
+    /// When the provider rejected the refresh token; the account needs a new consent
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl OAuthTokensStatus {
+    pub fn from_row(row: &PgRow) -> Result<Self, Box<dyn std::error::Error>> {
+        let expiry_date: DateTime<Utc> = row.try_get("expiry_date")?;
+
+        Ok(OAuthTokensStatus {
+            id_oauth_tokens: row.try_get("id_oauth_tokens")?,
+            id_provider: OAuthProvider::from_id(row.try_get("id_provider")?)?,
+            subject: row.try_get("account_subject")?,
+            email: row.try_get("account_email")?,
+            granted_scopes: row.try_get("granted_scopes")?,
+            expiry_date,
+            expired: expiry_date <= Utc::now(),
+            last_refresh_at: row.try_get("last_refresh_at")?,
+            last_refresh_error: row.try_get("last_refresh_error")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+const PENDING_AUTHORIZATION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// What was requested in an authorization URL, kept until the callback with the same `state`
+#[derive(Debug, Clone)]
+pub struct PendingAuthorization {
+    /// Nonce sent to OpenID Connect providers, checked against the ID token
+    pub nonce: Option<String>,
+    pub requested_scopes: Vec<String>,
+    /// Stored credential the grant is merged into (incremental consent)
+    pub id_oauth_tokens: Option<Uuid>,
+    created_at: Instant,
+}
+
+impl PendingAuthorization {
+    pub fn new(
+        nonce: Option<String>,
+        requested_scopes: Vec<String>,
+        id_oauth_tokens: Option<Uuid>,
+    ) -> Self {
+        Self {
+            nonce,
+            requested_scopes,
+            id_oauth_tokens,
+            created_at: Instant::now(),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.created_at.elapsed() >= PENDING_AUTHORIZATION_TTL
+    }
+}
+
+/// Autorizações pendentes, indexadas pelo `state` da URL de autorização
+static PENDING_AUTHORIZATIONS: Lazy<Mutex<HashMap<String, PendingAuthorization>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn remember_pending_authorization(state: &str, pending: PendingAuthorization) {
+    let mut pending_authorizations = PENDING_AUTHORIZATIONS.lock().unwrap();
+    pending_authorizations.retain(|_, pending| !pending.is_expired());
+    pending_authorizations.insert(state.to_string(), pending);
+}
+
+pub fn take_pending_authorization(state: &str) -> Option<PendingAuthorization> {
+    PENDING_AUTHORIZATIONS
+        .lock()
+        .unwrap()
+        .remove(state)
+        .filter(|pending| !pending.is_expired())
+}
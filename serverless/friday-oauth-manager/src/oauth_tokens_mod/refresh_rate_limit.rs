@@ -0,0 +1,42 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+/// Refreshes allowed per account in `REFRESH_WINDOW`; access tokens last about an hour, so
+/// legitimate callers stay far below it
+const MAX_REFRESHES_PER_WINDOW: usize = 6;
+const REFRESH_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Instantes dos últimos refreshes de cada conta
+static REFRESH_ATTEMPTS: Lazy<Mutex<HashMap<String, VecDeque<Instant>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Records a refresh of the account, or returns how long to wait when the limit was reached
+pub fn check_refresh_rate_limit(account_key: &str) -> Result<(), Duration> {
+    let mut refresh_attempts = REFRESH_ATTEMPTS.lock().unwrap();
+    let now = Instant::now();
+
+    refresh_attempts.retain(|_, attempts| {
+        while attempts
+            .front()
+            .is_some_and(|attempt| now - *attempt >= REFRESH_WINDOW)
+        {
+            attempts.pop_front();
+        }
+        !attempts.is_empty()
+    });
+
+    let attempts = refresh_attempts.entry(account_key.to_string()).or_default();
+
+    if attempts.len() >= MAX_REFRESHES_PER_WINDOW {
+        let oldest = attempts[0];
+        return Err(REFRESH_WINDOW - (now - oldest));
+    }
+
+    attempts.push_back(now);
+    Ok(())
+}
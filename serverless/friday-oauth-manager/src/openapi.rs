@@ -3,13 +3,25 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     business_response::BusinessResponse,
-    oauth_provider::OAuthProvider,
+    oauth_provider::{OAuthProvider, OidcUserClaims},
     oauth_tokens_mod::{
+        app_token::AppToken,
         oauth_tokens::OAuthTokens,
         oauth_tokens_controller::{
             get_oauth_tokens_request::GetOAuthTokensRequest,
+            incremental_consent_request::IncrementalConsentRequest,
             refresh_access_token_request::RefreshAccessTokenRequest,
+            revoke_oauth_tokens_request::RevokeOAuthTokensRequest,
+            start_device_authorization_request::StartDeviceAuthorizationRequest,
         },
+        oauth_tokens_status::OAuthTokensStatus,
+    },
+    service_auth_mod::{
+        service_auth_controller::{
+            generate_service_token_request::GenerateServiceTokenRequest,
+            register_service_client_request::RegisterServiceClientRequest,
+        },
+        service_client::ServiceClient,
     },
 };
 
@@ -17,28 +29,51 @@ use crate::{
 #[openapi(
     info(
         title = "Friday OAuth API",
-        description = "OAuth 2.0 authentication service supporting Google and Microsoft providers",
+        description = "OAuth 2.0 authentication service with a configurable provider registry (Google and Microsoft built in) and internal token issuer for service-to-service authentication",
         version = "1.0.0",
     ),
     paths(
         crate::oauth_tokens_mod::oauth_tokens_controller::generate_access_token,
+        crate::oauth_tokens_mod::oauth_tokens_controller::generate_app_token,
         crate::oauth_tokens_mod::oauth_tokens_controller::refresh_access_token,
+        crate::oauth_tokens_mod::oauth_tokens_controller::revoke_oauth_tokens,
         crate::oauth_tokens_mod::oauth_tokens_controller::generate_oauth_url_endpoint,
         crate::oauth_tokens_mod::oauth_tokens_controller::generate_google_oauth_url,
         crate::oauth_tokens_mod::oauth_tokens_controller::generate_microsoft_oauth_url,
+        crate::oauth_tokens_mod::oauth_tokens_controller::generate_provider_oauth_url,
+        crate::oauth_tokens_mod::oauth_tokens_controller::generate_incremental_consent_url,
+        crate::oauth_tokens_mod::oauth_tokens_controller::start_device_authorization,
+        crate::oauth_tokens_mod::oauth_tokens_controller::check_device_authorization,
         crate::oauth_tokens_mod::oauth_tokens_controller::get_oauth_tokens,
+        crate::oauth_tokens_mod::oauth_tokens_controller::get_oauth_tokens_status,
         crate::oauth_tokens_mod::oauth_tokens_controller::health_check,
+        crate::service_auth_mod::service_auth_controller::register_service_client,
+        crate::service_auth_mod::service_auth_controller::generate_service_token,
+        crate::service_auth_mod::service_auth_controller::get_jwks,
+        crate::provider_proxy_mod::provider_proxy_controller::proxy_provider_request,
     ),
     components(schemas(
         BusinessResponse<serde_json::Value>,
         OAuthProvider,
         GetOAuthTokensRequest,
+        IncrementalConsentRequest,
         RefreshAccessTokenRequest,
+        RevokeOAuthTokensRequest,
+        StartDeviceAuthorizationRequest,
         OAuthTokens,
+        OAuthTokensStatus,
+        AppToken,
+        OidcUserClaims,
+        RegisterServiceClientRequest,
+        GenerateServiceTokenRequest,
+        ServiceClient,
     )),
     tags(
         (name = "OAuth", description = "OAuth token management endpoints"),
         (name = "OAuth URLs", description = "OAuth authorization URL generation endpoints"),
+        (name = "Device Authorization", description = "Device authorization grant endpoints for headless clients"),
+        (name = "Service Auth", description = "Internal service client registration, tokens and JWKS"),
+        (name = "Provider Proxy", description = "Provider API calls on behalf of authenticated services"),
         (name = "Health", description = "Service health check endpoints")
     )
 )]
@@ -0,0 +1,3 @@
+pub mod provider_proxy_controller;
+pub mod provider_proxy_logic;
+pub mod provider_proxy_request;
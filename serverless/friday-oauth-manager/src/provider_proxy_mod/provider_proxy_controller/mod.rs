@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod provider_proxy_controller;
+
+pub use provider_proxy_controller::*;
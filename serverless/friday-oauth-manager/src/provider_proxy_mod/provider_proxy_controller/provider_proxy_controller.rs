@@ -0,0 +1,125 @@
+use actix_web::{
+    http::{header::AUTHORIZATION, StatusCode},
+    route, web, HttpRequest, HttpResponse, ResponseError,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    database_mod::DatabasePool,
+    oauth_manager_error::OAuthManagerError,
+    oauth_provider::OAuthProvider,
+    provider_proxy_mod::{
+        provider_proxy_logic::{self, CREDENTIAL_HEADER},
+        provider_proxy_request::ProviderProxyRequest,
+    },
+};
+
+/// Call a provider API on behalf of the caller
+///
+/// Forwards the request to `{api_base_url}/{path}` of the provider (e.g. Microsoft Graph)
+/// with the access token of the stored credential, refreshing it when needed, and streams
+/// the provider response back unchanged. Requires an internal service token with the
+/// `oauth.proxy` scope. `X-Friday-Credential` selects the credential; the latest credential
+/// of the provider is used when it is omitted. GET, POST, PUT, PATCH and DELETE are proxied.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/proxy/{provider}/{path}",
+    params(
+        ("provider" = OAuthProvider, Path, description = "Registered OAuth provider"),
+        ("path" = String, Path, description = "Path below the provider API base URL", example = "v1.0/me/todo/lists"),
+        ("Authorization" = String, Header, description = "Bearer internal service token with the oauth.proxy scope"),
+        ("X-Friday-Credential" = Option<Uuid>, Header, description = "id_oauth_tokens of the credential to use")
+    ),
+    responses(
+        (status = 200, description = "Provider response, streamed back with its status and headers"),
+        (status = 400, description = "Provider without API or path outside of it", body = BusinessResponse),
+        (status = 401, description = "Missing or invalid service token, or credential revoked by the provider", body = BusinessResponse),
+        (status = 403, description = "Service token without the oauth.proxy scope", body = BusinessResponse),
+        (status = 404, description = "Unknown provider or credential", body = BusinessResponse),
+        (status = 502, description = "Provider unreachable or credential could not be refreshed", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "Provider Proxy"
+)]
+#[route(
+    "/api/friday-oauth-manager/proxy/{provider}/{path:.*}",
+    method = "GET",
+    method = "POST",
+    method = "PUT",
+    method = "PATCH",
+    method = "DELETE"
+)]
+pub async fn proxy_provider_request(
+    database_pool: web::Data<DatabasePool>,
+    http_request: HttpRequest,
+    path: web::Path<(OAuthProvider, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let (provider, path) = path.into_inner();
+
+    let request = match build_proxy_request(&http_request, path, body) {
+        Ok(request) => request,
+        Err(e) => return e.error_response(),
+    };
+
+    match provider_proxy_logic::proxy_provider_request(&database_pool.get(), provider, request)
+        .await
+    {
+        Ok(response) => {
+            let status =
+                StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+            let mut proxy_response = HttpResponse::build(status);
+
+            for (name, value) in response.headers() {
+                if provider_proxy_logic::is_forwarded_header(name.as_str()) {
+                    proxy_response.append_header((name.as_str(), value.as_bytes()));
+                }
+            }
+
+            proxy_response.streaming(response.bytes_stream())
+        }
+        Err(e) => {
+            error!("Erro no proxy para o provedor: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+fn build_proxy_request(
+    http_request: &HttpRequest,
+    path: String,
+    body: web::Bytes,
+) -> Result<ProviderProxyRequest, OAuthManagerError> {
+    let header = |name| {
+        http_request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    let id_oauth_tokens = header(CREDENTIAL_HEADER)
+        .map(|value| {
+            Uuid::parse_str(value.trim()).map_err(|_| {
+                OAuthManagerError::BadRequest(format!("{} inválido: {}", CREDENTIAL_HEADER, value))
+            })
+        })
+        .transpose()?;
+
+    let method = reqwest::Method::from_bytes(http_request.method().as_str().as_bytes())
+        .map_err(|e| OAuthManagerError::BadRequest(e.to_string()))?;
+
+    Ok(ProviderProxyRequest {
+        method,
+        path,
+        query: http_request.query_string().to_string(),
+        authorization: header(AUTHORIZATION.as_str()).map(str::to_string),
+        id_oauth_tokens,
+        headers: http_request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str().to_string(), value.as_bytes().to_vec()))
+            .collect(),
+        body: body.to_vec(),
+    })
+}
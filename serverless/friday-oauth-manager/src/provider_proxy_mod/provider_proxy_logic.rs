@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use reqwest::{StatusCode, Url};
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+
+use crate::{
+    oauth_manager_error::OAuthManagerError,
+    oauth_provider::{oauth_provider_registry, OAuthProvider},
+    oauth_tokens_mod::oauth_tokens_logic,
+    provider_proxy_mod::provider_proxy_request::ProviderProxyRequest,
+    service_auth_mod::service_auth_logic,
+};
+
+/// Scope of the service token required to call provider APIs through the proxy
+pub const PROVIDER_PROXY_SCOPE: &str = "oauth.proxy";
+/// Header selecting the credential (`id_oauth_tokens`) used by the proxy
+pub const CREDENTIAL_HEADER: &str = "X-Friday-Credential";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Cabeçalhos de conexão (hop-by-hop) e de autenticação que não atravessam o proxy
+const NOT_FORWARDED_HEADERS: [&str; 14] = [
+    "authorization",
+    "connection",
+    "content-length",
+    "cookie",
+    "host",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "set-cookie",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "x-friday-credential",
+];
+
+/// No total timeout: the response body is streamed back to the caller
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .expect("Failed to build the proxy HTTP client")
+});
+
+/// Whether a request or response header is passed through the proxy
+pub fn is_forwarded_header(name: &str) -> bool {
+    !NOT_FORWARDED_HEADERS
+        .iter()
+        .any(|not_forwarded| name.eq_ignore_ascii_case(not_forwarded))
+}
+
+/// Business logic for calling a provider API on behalf of an authenticated service. The
+/// credential's access token is injected (refreshed when expiring) and, when the provider
+/// rejects it with 401, the token is refreshed and the request sent once more.
+pub async fn proxy_provider_request(
+    pool: &PgPool,
+    provider: OAuthProvider,
+    request: ProviderProxyRequest,
+) -> Result<reqwest::Response, OAuthManagerError> {
+    let claims = service_auth_logic::verify_service_token(
+        request.authorization.as_deref(),
+        PROVIDER_PROXY_SCOPE,
+    )
+    .await?;
+
+    let api_base_url = oauth_provider_registry()
+        .get(&provider)
+        .map_err(OAuthManagerError::NotFound)?
+        .api_base_url
+        .clone()
+        .ok_or_else(|| {
+            OAuthManagerError::BadRequest(format!(
+                "O provedor {} não possui API configurada para o proxy",
+                provider
+            ))
+        })?;
+
+    let url = build_provider_url(&api_base_url, &request.path, &request.query)?;
+
+    let oauth_tokens =
+        oauth_tokens_logic::get_provider_credential(pool, &provider, request.id_oauth_tokens)
+            .await?;
+
+    info!(
+        provider = %provider,
+        service = %claims.sub,
+        "Proxy {} {}",
+        request.method,
+        url.path()
+    );
+
+    let oauth_tokens =
+        oauth_tokens_logic::get_fresh_oauth_tokens(pool, oauth_tokens, false).await?;
+    let response = send_provider_request(&request, &url, &oauth_tokens.access_token).await?;
+
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+
+    warn!(provider = %provider, "Provedor recusou o access token, renovando e repetindo a requisição");
+
+    let oauth_tokens = oauth_tokens_logic::get_fresh_oauth_tokens(pool, oauth_tokens, true).await?;
+    send_provider_request(&request, &url, &oauth_tokens.access_token).await
+}
+
+/// URL of `path` below the provider's API base URL. Paths escaping the base URL (`..`,
+/// absolute URLs) are rejected, so the access token is only ever sent to the provider API.
+fn build_provider_url(
+    api_base_url: &str,
+    path: &str,
+    query: &str,
+) -> Result<Url, OAuthManagerError> {
+    let base_url = Url::parse(api_base_url)
+        .map_err(|e| OAuthManagerError::Internal(format!("api_base_url inválida: {}", e).into()))?;
+
+    let mut url = format!(
+        "{}/{}",
+        api_base_url.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    let url = Url::parse(&url)
+        .map_err(|e| OAuthManagerError::BadRequest(format!("Caminho inválido: {}", e)))?;
+
+    let base_path = base_url.path().trim_end_matches('/');
+    if url.origin() != base_url.origin()
+        || !(url.path() == base_path || url.path().starts_with(&format!("{}/", base_path)))
+    {
+        return Err(OAuthManagerError::BadRequest(format!(
+            "Caminho fora da API do provedor: {}",
+            path
+        )));
+    }
+
+    Ok(url)
+}
+
+async fn send_provider_request(
+    request: &ProviderProxyRequest,
+    url: &Url,
+    access_token: &str,
+) -> Result<reqwest::Response, OAuthManagerError> {
+    let mut request_builder = HTTP_CLIENT
+        .request(request.method.clone(), url.clone())
+        .bearer_auth(access_token)
+        .body(request.body.clone());
+
+    for (name, value) in &request.headers {
+        if is_forwarded_header(name) {
+            request_builder = request_builder.header(name.as_str(), value.as_slice());
+        }
+    }
+
+    request_builder.send().await.map_err(|e| {
+        error!("Erro ao chamar a API do provedor: {}", e);
+        OAuthManagerError::Provider(format!("Erro ao chamar a API do provedor: {}", e))
+    })
+}
@@ -0,0 +1,17 @@
+use uuid::Uuid;
+
+/// Request of a service to a provider API, forwarded with the credential's access token
+#[derive(Debug, Clone)]
+pub struct ProviderProxyRequest {
+    pub method: reqwest::Method,
+    /// Path below the provider's `api_base_url` (e.g. "v1.0/me/todo/lists")
+    pub path: String,
+    pub query: String,
+    /// `Authorization` header with the internal service token of the caller
+    pub authorization: Option<String>,
+    /// Credential to use; the latest credential of the provider when not given
+    pub id_oauth_tokens: Option<Uuid>,
+    /// Headers forwarded to the provider
+    pub headers: Vec<(String, Vec<u8>)>,
+    pub body: Vec<u8>,
+}
@@ -4,4 +4,8 @@ pub mod secret_manager_logic;
 pub mod secret_response;
 
 // Re-export for easier access
-pub use secret_manager_logic::{get_database_url, get_oauth_credentials};
+pub use secret_manager_logic::{
+    get_credential_webhook_secret, get_credential_webhook_urls, get_database_url,
+    get_oauth_credentials, get_rabbit_credentials, get_service_auth_admin_key,
+    get_service_auth_signing_key,
+};
@@ -3,6 +3,7 @@ use utoipa::ToSchema;
 
 /// Secret data structure for Friday Secret Manager
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[allow(dead_code)]
 pub struct Secret {
     pub key: String,
     pub value: String,
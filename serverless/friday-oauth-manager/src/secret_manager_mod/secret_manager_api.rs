@@ -9,6 +9,12 @@ pub struct SecretManagerApi {
     base_url: String,
 }
 
+impl Default for SecretManagerApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SecretManagerApi {
     pub fn new() -> Self {
         Self {
@@ -1,42 +1,136 @@
+use std::{collections::HashMap, sync::Mutex};
+
 use super::secret_manager_api::SecretManagerApi;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
+use tracing::warn;
+
+/// (client_id, client_secret)
+type OAuthCredentials = (String, String);
+
+/// Credenciais em cache, indexadas pelo par de chaves do secret manager
+static OAUTH_CREDENTIALS: Lazy<Mutex<HashMap<(String, String), OAuthCredentials>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static SERVICE_AUTH_ADMIN_KEY: OnceCell<String> = OnceCell::new();
 
-static OAUTH_CREDENTIALS: OnceCell<(String, String)> = OnceCell::new();
-static DATABASE_URL: OnceCell<String> = OnceCell::new();
+/// Chaves compartilhadas, usadas quando o provedor não possui credenciais próprias
+const SHARED_CLIENT_ID_KEY: &str = "OAUTH_CLIENT_ID";
+const SHARED_SECRET_VALUE_KEY: &str = "OAUTH_SECRET_VALUE";
 
-pub async fn get_oauth_credentials() -> Result<(String, String), Box<dyn std::error::Error>> {
-    if let Some(credentials) = OAUTH_CREDENTIALS.get() {
+pub async fn get_oauth_credentials(
+    client_id_key: &str,
+    secret_value_key: &str,
+) -> Result<OAuthCredentials, Box<dyn std::error::Error>> {
+    let cache_key = (client_id_key.to_string(), secret_value_key.to_string());
+
+    if let Some(credentials) = OAUTH_CREDENTIALS.lock().unwrap().get(&cache_key) {
         return Ok(credentials.clone());
     }
 
     let client = SecretManagerApi::new();
 
-    let client_id = client
-        .get_secret_value("OAUTH_CLIENT_ID")
-        .await?
-        .ok_or("OAUTH_CLIENT_ID not found in secret manager")?;
+    let credentials =
+        match fetch_oauth_credentials(&client, client_id_key, secret_value_key).await? {
+            Some(credentials) => credentials,
+            None => {
+                warn!(
+                    "{}/{} not found in secret manager, falling back to shared OAuth credentials",
+                    client_id_key, secret_value_key
+                );
+                fetch_oauth_credentials(&client, SHARED_CLIENT_ID_KEY, SHARED_SECRET_VALUE_KEY)
+                    .await?
+                    .ok_or_else(|| format!("{} not found in secret manager", client_id_key))?
+            }
+        };
 
-    let secret_value = client
-        .get_secret_value("OAUTH_SECRET_VALUE")
-        .await?
-        .ok_or("OAUTH_SECRET_VALUE not found in secret manager")?;
+    OAUTH_CREDENTIALS
+        .lock()
+        .unwrap()
+        .insert(cache_key, credentials.clone());
+    Ok(credentials)
+}
+
+async fn fetch_oauth_credentials(
+    client: &SecretManagerApi,
+    client_id_key: &str,
+    secret_value_key: &str,
+) -> Result<Option<OAuthCredentials>, Box<dyn std::error::Error>> {
+    let Some(client_id) = client.get_secret_value(client_id_key).await? else {
+        return Ok(None);
+    };
+
+    let Some(secret_value) = client.get_secret_value(secret_value_key).await? else {
+        return Ok(None);
+    };
 
-    OAUTH_CREDENTIALS.set((client_id.clone(), secret_value.clone())).ok();
-    Ok((client_id, secret_value))
+    Ok(Some((client_id, secret_value)))
 }
 
+/// Not cached: the shared pool reads it again to detect a rotated connection string
 pub async fn get_database_url() -> Result<String, Box<dyn std::error::Error>> {
-    if let Some(url) = DATABASE_URL.get() {
-        return Ok(url.clone());
+    let database_url = SecretManagerApi::new()
+        .get_secret_value("ConnectionStrings:Postgres")
+        .await?
+        .ok_or("ConnectionStrings:Postgres not found in secret manager")?;
+
+    Ok(database_url)
+}
+
+/// Ed25519 private key (PKCS#8 DER, base64) used to sign internal service tokens
+pub async fn get_service_auth_signing_key() -> Result<Option<String>, Box<dyn std::error::Error>> {
+    SecretManagerApi::new()
+        .get_secret_value("ServiceAuth:SigningKey")
+        .await
+}
+
+/// Key required to register service clients
+pub async fn get_service_auth_admin_key() -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(admin_key) = SERVICE_AUTH_ADMIN_KEY.get() {
+        return Ok(admin_key.clone());
     }
 
     let client = SecretManagerApi::new();
 
-    let database_url = client
-        .get_secret_value("ConnectionStrings:Postgres")
+    let admin_key = client
+        .get_secret_value("ServiceAuth:AdminKey")
         .await?
-        .ok_or("ConnectionStrings:Postgres not found in secret manager")?;
+        .ok_or("ServiceAuth:AdminKey not found in secret manager")?;
 
-    DATABASE_URL.set(database_url.clone()).ok();
-    Ok(database_url)
+    SERVICE_AUTH_ADMIN_KEY.set(admin_key.clone()).ok();
+    Ok(admin_key)
+}
+
+/// Comma-separated URLs notified of credential events
+pub async fn get_credential_webhook_urls() -> Result<Option<String>, Box<dyn std::error::Error>> {
+    SecretManagerApi::new()
+        .get_secret_value("CredentialEvents:WebhookUrls")
+        .await
+}
+
+/// Key used to sign the credential event webhooks (HMAC-SHA256)
+pub async fn get_credential_webhook_secret() -> Result<Option<String>, Box<dyn std::error::Error>> {
+    SecretManagerApi::new()
+        .get_secret_value("CredentialEvents:WebhookSecret")
+        .await
+}
+
+/// (host, user, password) of the RabbitMQ broker, when configured
+pub async fn get_rabbit_credentials(
+) -> Result<Option<(String, String, String)>, Box<dyn std::error::Error>> {
+    let client = SecretManagerApi::new();
+
+    let Some(host) = client.get_secret_value("RabbitMq:Host").await? else {
+        return Ok(None);
+    };
+
+    let user = client
+        .get_secret_value("RabbitMq:User")
+        .await?
+        .ok_or("RabbitMq:User not found in secret manager")?;
+
+    let password = client
+        .get_secret_value("RabbitMq:Password")
+        .await?
+        .ok_or("RabbitMq:Password not found in secret manager")?;
+
+    Ok(Some((host, user, password)))
 }
@@ -0,0 +1,5 @@
+pub mod service_auth_controller;
+mod service_auth_data;
+pub mod service_auth_logic;
+pub mod service_client;
+mod service_signing_key;
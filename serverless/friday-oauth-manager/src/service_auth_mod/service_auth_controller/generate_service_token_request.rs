@@ -0,0 +1,16 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Client-credentials request for an internal service token
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct GenerateServiceTokenRequest {
+    /// Id returned when the service client was registered
+    pub client_id: Uuid,
+    /// Secret returned when the service client was registered
+    pub client_secret: String,
+    /// Scopes to include in the token; all allowed scopes are granted when empty
+    #[serde(default)]
+    #[schema(example = json!(["todo.read"]))]
+    pub scopes: Vec<String>,
+}
@@ -0,0 +1,6 @@
+pub mod generate_service_token_request;
+pub mod register_service_client_request;
+#[allow(clippy::module_inception)]
+pub mod service_auth_controller;
+
+pub use service_auth_controller::*;
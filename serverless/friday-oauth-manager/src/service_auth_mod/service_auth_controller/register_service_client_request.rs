@@ -0,0 +1,14 @@
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Request to register a service allowed to obtain internal tokens
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RegisterServiceClientRequest {
+    /// Unique name of the service, used as the `sub` of its tokens
+    #[schema(example = "friday-todo-manager")]
+    pub client_name: String,
+    /// Scopes the service may request
+    #[serde(default)]
+    #[schema(example = json!(["todo.read", "todo.write"]))]
+    pub allowed_scopes: Vec<String>,
+}
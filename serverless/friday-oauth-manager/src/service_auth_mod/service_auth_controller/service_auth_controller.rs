@@ -0,0 +1,126 @@
+use actix_web::{get, post, HttpRequest, HttpResponse, Responder, ResponseError};
+use tracing::{error, info};
+
+use crate::{
+    business_response::BusinessResponse,
+    database_mod::DatabasePool,
+    service_auth_mod::{
+        service_auth_controller::{
+            generate_service_token_request::GenerateServiceTokenRequest,
+            register_service_client_request::RegisterServiceClientRequest,
+        },
+        service_auth_logic,
+    },
+};
+
+/// Header carrying the key required to register service clients
+const ADMIN_KEY_HEADER: &str = "X-Friday-Admin-Key";
+
+/// Register a service client
+///
+/// Registers a service allowed to obtain internal tokens with the client-credentials
+/// grant. Requires the `X-Friday-Admin-Key` header. The client secret is returned only once.
+#[utoipa::path(
+    post,
+    path = "/api/friday-oauth-manager/service-auth/clients",
+    request_body = RegisterServiceClientRequest,
+    params(
+        ("X-Friday-Admin-Key" = String, Header, description = "Key allowed to register service clients")
+    ),
+    responses(
+        (status = 200, description = "Service client registered successfully", body = BusinessResponse),
+        (status = 400, description = "Missing client_name", body = BusinessResponse),
+        (status = 403, description = "Invalid admin key", body = BusinessResponse),
+        (status = 409, description = "A service client with this name already exists", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "Service Auth"
+)]
+#[post("/api/friday-oauth-manager/service-auth/clients")]
+pub async fn register_service_client(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    http_request: HttpRequest,
+    request: actix_web::web::Json<RegisterServiceClientRequest>,
+) -> impl Responder {
+    info!(client_name = %request.client_name, "Registrando service client");
+
+    let admin_key = http_request
+        .headers()
+        .get(ADMIN_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    match service_auth_logic::register_service_client(
+        &database_pool.get(),
+        admin_key,
+        request.into_inner(),
+    )
+    .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao registrar service client: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// Issue an internal service token
+///
+/// Exchanges the credentials of a registered service client for a short-lived JWT
+/// signed with EdDSA, verifiable with the keys published at `/.well-known/jwks.json`.
+#[utoipa::path(
+    post,
+    path = "/api/friday-oauth-manager/service-auth/token",
+    request_body = GenerateServiceTokenRequest,
+    responses(
+        (status = 200, description = "Service token issued successfully", body = BusinessResponse),
+        (status = 401, description = "Invalid client credentials", body = BusinessResponse),
+        (status = 403, description = "Requested scopes not allowed to the client", body = BusinessResponse),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "Service Auth"
+)]
+#[post("/api/friday-oauth-manager/service-auth/token")]
+pub async fn generate_service_token(
+    database_pool: actix_web::web::Data<DatabasePool>,
+    request: actix_web::web::Json<GenerateServiceTokenRequest>,
+) -> impl Responder {
+    info!(client_id = %request.client_id, "Solicitação de token de serviço");
+
+    match service_auth_logic::generate_service_token(&database_pool.get(), request.into_inner())
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Erro ao emitir token de serviço: {}", e);
+            e.error_response()
+        }
+    }
+}
+
+/// JSON Web Key Set of the internal service tokens
+///
+/// Returns the standard JWKS document (not wrapped in `BusinessResponse`) so that
+/// verifiers can fetch it directly.
+#[utoipa::path(
+    get,
+    path = "/api/friday-oauth-manager/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "JSON Web Key Set"),
+        (status = 500, description = "Internal server error", body = BusinessResponse)
+    ),
+    tag = "Service Auth"
+)]
+#[get("/api/friday-oauth-manager/.well-known/jwks.json")]
+pub async fn get_jwks() -> impl Responder {
+    match service_auth_logic::get_jwks().await {
+        Ok(jwks) => HttpResponse::Ok().json(jwks),
+        Err(e) => {
+            error!("Erro ao carregar JWKS: {}", e);
+            HttpResponse::InternalServerError().json(BusinessResponse::<serde_json::Value>::error(
+                &format!("Erro interno: {}", e),
+            ))
+        }
+    }
+}
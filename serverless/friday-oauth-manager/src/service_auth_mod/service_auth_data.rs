@@ -0,0 +1,48 @@
+use sqlx::PgPool;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+use super::service_client::ServiceClient;
+
+pub async fn insert_service_client(
+    pool: &PgPool,
+    service_client: &ServiceClient,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let result = sqlx::query("CALL pr_ins_service_client($1, $2, $3, $4)")
+        .bind(service_client.id_service_client)
+        .bind(&service_client.client_name)
+        .bind(&service_client.client_secret_hash)
+        .bind(&service_client.allowed_scopes)
+        .execute(pool)
+        .await;
+
+    if let Err(e) = result {
+        error!("Erro ao inserir service client: {:?}", e);
+        return Err(Box::new(e));
+    }
+
+    debug!("Service client inserido com sucesso");
+
+    Ok(())
+}
+
+pub async fn get_service_client_by_id(
+    pool: &PgPool,
+    id_service_client: Uuid,
+) -> Result<Option<ServiceClient>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_service_client_by_id($1)")
+        .bind(id_service_client)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(row) => ServiceClient::from_row(&row),
+        None => {
+            debug!(
+                "Nenhum service client ativo encontrado para id_service_client={}",
+                id_service_client
+            );
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use friday_service_auth::{
+    ServiceClaims, ServiceTokenVerifier, SERVICE_TOKEN_AUDIENCE, SERVICE_TOKEN_ISSUER,
+};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, Header};
+use once_cell::sync::OnceCell;
+use ring::{
+    digest,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    business_response::BusinessResponse,
+    oauth_manager_error::OAuthManagerError,
+    secret_manager_mod,
+    service_auth_mod::{
+        service_auth_controller::{
+            generate_service_token_request::GenerateServiceTokenRequest,
+            register_service_client_request::RegisterServiceClientRequest,
+        },
+        service_auth_data,
+        service_client::ServiceClient,
+        service_signing_key,
+    },
+};
+
+/// Internal tokens are short-lived; services request a new one when it expires
+const SERVICE_TOKEN_TTL_SECONDS: i64 = 5 * 60;
+
+/// Business logic for registering a service client. The generated secret is returned
+/// only once; just its hash is stored.
+pub async fn register_service_client(
+    pool: &PgPool,
+    admin_key: Option<String>,
+    request: RegisterServiceClientRequest,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let expected_admin_key = secret_manager_mod::get_service_auth_admin_key().await?;
+    if admin_key.map(|key| hash_secret(&key)) != Some(hash_secret(&expected_admin_key)) {
+        warn!("Tentativa de registrar service client sem chave de administração válida");
+        return Err(OAuthManagerError::Forbidden(
+            "Chave de administração inválida para registrar service clients".to_string(),
+        ));
+    }
+
+    let client_name = request.client_name.trim().to_string();
+    if client_name.is_empty() {
+        return Err(OAuthManagerError::BadRequest(
+            "client_name é obrigatório".to_string(),
+        ));
+    }
+
+    let allowed_scopes: Vec<String> = request
+        .allowed_scopes
+        .iter()
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| !scope.is_empty())
+        .collect();
+
+    let client_secret = generate_client_secret()?;
+    let service_client = ServiceClient {
+        id_service_client: Uuid::new_v4(),
+        client_name,
+        client_secret_hash: hash_secret(&client_secret),
+        allowed_scopes,
+    };
+
+    if let Err(e) = service_auth_data::insert_service_client(pool, &service_client).await {
+        let is_unique_violation = e
+            .downcast_ref::<sqlx::Error>()
+            .and_then(|e| e.as_database_error())
+            .is_some_and(|e| e.is_unique_violation());
+
+        if is_unique_violation {
+            warn!(client_name = %service_client.client_name, "Service client já registrado");
+            return Err(OAuthManagerError::Conflict(format!(
+                "Service client '{}' já registrado",
+                service_client.client_name
+            )));
+        }
+
+        return Err(OAuthManagerError::Internal(e));
+    }
+
+    info!(client_name = %service_client.client_name, "Service client registrado com sucesso");
+
+    Ok(BusinessResponse::success(json!({
+        "service_client": service_client,
+        "client_secret": client_secret
+    })))
+}
+
+/// Business logic for issuing an internal service token with the client-credentials grant
+pub async fn generate_service_token(
+    pool: &PgPool,
+    request: GenerateServiceTokenRequest,
+) -> Result<BusinessResponse<serde_json::Value>, OAuthManagerError> {
+    let service_client = match service_auth_data::get_service_client_by_id(pool, request.client_id)
+        .await?
+    {
+        Some(client) if client.client_secret_hash == hash_secret(&request.client_secret) => client,
+        _ => {
+            warn!(client_id = %request.client_id, "Credenciais de service client inválidas");
+            return Err(OAuthManagerError::Unauthorized(
+                "Credenciais de service client inválidas".to_string(),
+            ));
+        }
+    };
+
+    let scopes = if request.scopes.is_empty() {
+        service_client.allowed_scopes.clone()
+    } else {
+        request.scopes
+    };
+
+    let not_allowed: Vec<&String> = scopes
+        .iter()
+        .filter(|scope| !service_client.allowed_scopes.contains(scope))
+        .collect();
+    if !not_allowed.is_empty() {
+        warn!(client_name = %service_client.client_name, "Escopos não permitidos: {:?}", not_allowed);
+        return Err(OAuthManagerError::Forbidden(format!(
+            "Escopos não permitidos ao service client: {}",
+            not_allowed
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        )));
+    }
+
+    let now = Utc::now().timestamp();
+    let claims = ServiceClaims {
+        iss: SERVICE_TOKEN_ISSUER.to_string(),
+        sub: service_client.client_name.clone(),
+        aud: SERVICE_TOKEN_AUDIENCE.to_string(),
+        exp: now + SERVICE_TOKEN_TTL_SECONDS,
+        iat: now,
+        jti: Uuid::new_v4(),
+        client_id: service_client.id_service_client,
+        scope: scopes.join(" "),
+    };
+
+    let signing_key = service_signing_key::get_signing_key().await?;
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(signing_key.kid.clone());
+
+    let access_token = jsonwebtoken::encode(&header, &claims, signing_key.encoding_key())
+        .map_err(|e| OAuthManagerError::Internal(e.into()))?;
+
+    info!(client_name = %service_client.client_name, "Token de serviço emitido");
+
+    Ok(BusinessResponse::success(json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": SERVICE_TOKEN_TTL_SECONDS,
+        "scope": claims.scope
+    })))
+}
+
+/// Public keys for verifying internal service tokens
+pub async fn get_jwks() -> Result<JwkSet, Box<dyn std::error::Error>> {
+    let signing_key = service_signing_key::get_signing_key().await?;
+
+    Ok(JwkSet {
+        keys: vec![signing_key.jwk()],
+    })
+}
+
+static SERVICE_TOKEN_VERIFIER: OnceCell<ServiceTokenVerifier> = OnceCell::new();
+
+/// Verifies an internal service token sent to this service (`Authorization: Bearer`) with
+/// the local signing key and checks it was granted `required_scope`
+pub async fn verify_service_token(
+    authorization_header: Option<&str>,
+    required_scope: &str,
+) -> Result<ServiceClaims, OAuthManagerError> {
+    let verifier = match SERVICE_TOKEN_VERIFIER.get() {
+        Some(verifier) => verifier,
+        None => {
+            let jwks = get_jwks().await?;
+            SERVICE_TOKEN_VERIFIER.get_or_init(|| ServiceTokenVerifier::with_jwks(&jwks))
+        }
+    };
+
+    verifier
+        .authorize(authorization_header, &[required_scope.to_string()])
+        .await
+        .map_err(|e| {
+            warn!("Token de serviço recusado: {}", e);
+            OAuthManagerError::from(e)
+        })
+}
+
+fn generate_client_secret() -> Result<String, Box<dyn std::error::Error>> {
+    let mut secret = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut secret)
+        .map_err(|_| "Failed to generate client secret")?;
+
+    Ok(URL_SAFE_NO_PAD.encode(secret))
+}
+
+fn hash_secret(secret: &str) -> String {
+    URL_SAFE_NO_PAD.encode(digest::digest(&digest::SHA256, secret.as_bytes()))
+}
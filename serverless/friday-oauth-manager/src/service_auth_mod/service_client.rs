@@ -0,0 +1,30 @@
+use serde_derive::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Row};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Service registered to obtain internal tokens with the client-credentials grant
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ServiceClient {
+    /// Client id used on the token request
+    pub id_service_client: Uuid,
+    #[schema(example = "friday-todo-manager")]
+    pub client_name: String,
+    /// SHA-256 of the client secret; the secret itself is only returned on registration
+    #[serde(skip_serializing)]
+    pub client_secret_hash: String,
+    /// Scopes the client may request
+    #[schema(example = json!(["todo.read", "todo.write"]))]
+    pub allowed_scopes: Vec<String>,
+}
+
+impl ServiceClient {
+    pub fn from_row(row: &PgRow) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        Ok(Some(ServiceClient {
+            id_service_client: row.try_get("id_service_client")?,
+            client_name: row.try_get("client_name")?,
+            client_secret_hash: row.try_get("client_secret_hash")?,
+            allowed_scopes: row.try_get("allowed_scopes")?,
+        }))
+    }
+}
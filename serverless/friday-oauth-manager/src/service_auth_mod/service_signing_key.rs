@@ -0,0 +1,91 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use jsonwebtoken::{
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, KeyAlgorithm,
+        OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse,
+    },
+    EncodingKey,
+};
+use once_cell::sync::OnceCell;
+use ring::{
+    digest,
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use tracing::{info, warn};
+
+use crate::{secret_manager_mod, ENV_CONFIG};
+
+/// Ed25519 key signing the internal service tokens, published in the JWKS
+pub struct ServiceSigningKey {
+    pub kid: String,
+    encoding_key: EncodingKey,
+    public_key: Vec<u8>,
+}
+
+impl ServiceSigningKey {
+    fn from_pkcs8(pkcs8: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(pkcs8)
+            .map_err(|e| format!("Invalid Ed25519 signing key: {}", e))?;
+        let public_key = key_pair.public_key().as_ref().to_vec();
+
+        // kid estável derivado da chave pública, para os verificadores escolherem a chave certa
+        let fingerprint = digest::digest(&digest::SHA256, &public_key);
+
+        Ok(Self {
+            kid: URL_SAFE_NO_PAD.encode(&fingerprint.as_ref()[..12]),
+            encoding_key: EncodingKey::from_ed_der(pkcs8),
+            public_key,
+        })
+    }
+
+    pub fn encoding_key(&self) -> &EncodingKey {
+        &self.encoding_key
+    }
+
+    pub fn jwk(&self) -> Jwk {
+        Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(KeyAlgorithm::EdDSA),
+                key_id: Some(self.kid.clone()),
+                ..Default::default()
+            },
+            algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(&self.public_key),
+            }),
+        }
+    }
+}
+
+static SIGNING_KEY: OnceCell<ServiceSigningKey> = OnceCell::new();
+
+/// Loads the signing key from the secret manager. Outside production a temporary key is
+/// generated when none is configured; tokens signed with it stop validating on restart.
+pub async fn get_signing_key() -> Result<&'static ServiceSigningKey, Box<dyn std::error::Error>> {
+    if let Some(signing_key) = SIGNING_KEY.get() {
+        return Ok(signing_key);
+    }
+
+    let signing_key = match secret_manager_mod::get_service_auth_signing_key().await? {
+        Some(encoded) => ServiceSigningKey::from_pkcs8(&STANDARD.decode(encoded.trim())?)?,
+        None if ENV_CONFIG.is_prod => {
+            return Err("ServiceAuth:SigningKey not found in secret manager".into());
+        }
+        None => {
+            warn!("ServiceAuth:SigningKey não encontrada, gerando chave de assinatura temporária");
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+                .map_err(|_| "Failed to generate Ed25519 signing key")?;
+            ServiceSigningKey::from_pkcs8(pkcs8.as_ref())?
+        }
+    };
+
+    info!(kid = %signing_key.kid, "Chave de assinatura de serviços carregada");
+
+    Ok(SIGNING_KEY.get_or_init(|| signing_key))
+}
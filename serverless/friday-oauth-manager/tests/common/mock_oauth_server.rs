@@ -0,0 +1,391 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    sync::{Arc, Mutex},
+};
+
+use actix_web::{
+    http::{header, StatusCode},
+    web, HttpRequest, HttpResponse,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use friday_oauth_manager::oauth_provider::{
+    ConfiguredOAuthProvider, FridayOAuthClient, OAuthClientProvider, OAuthProviderConfig,
+};
+use serde_json::json;
+
+use super::spawn_server;
+
+pub const MOCK_PROVIDER: &str = "mock";
+pub const MOCK_ID_PROVIDER: i32 = 99;
+pub const MOCK_CLIENT_ID: &str = "mock-client-id";
+pub const MOCK_CLIENT_SECRET: &str = "mock-client-secret";
+pub const MOCK_CLIENT_ID_SECRET_KEY: &str = "MOCK_OAUTH_CLIENT_ID";
+pub const MOCK_CLIENT_SECRET_SECRET_KEY: &str = "MOCK_OAUTH_SECRET_VALUE";
+pub const MOCK_REDIRECT_URL: &str = "http://localhost:5000/callback";
+
+/// Lifetime of the issued access tokens unless the test changes it
+const DEFAULT_EXPIRES_IN: u64 = 3600;
+
+struct MockOAuthState {
+    /// `expires_in` of the token responses; `None` omits the field
+    expires_in: Option<u64>,
+    /// `error`/`error_description` redirected to the callback by the authorize endpoint
+    authorization_error: Option<(String, String)>,
+    /// `error`/`error_description` returned by the token endpoint to every request
+    token_error: Option<(String, String)>,
+    /// Status and `Retry-After` of the next token requests, returned before any other check
+    transient_failures: VecDeque<(StatusCode, Option<u64>)>,
+    /// Authorization codes not exchanged yet, with the scopes they were issued for
+    codes: HashMap<String, Vec<String>>,
+    refresh_tokens: HashSet<String>,
+    /// Access tokens accepted by the mock API
+    access_tokens: HashSet<String>,
+    /// `grant_type` of every request received by the token endpoint
+    grant_types: Vec<String>,
+    issued: u64,
+}
+
+impl Default for MockOAuthState {
+    fn default() -> Self {
+        Self {
+            expires_in: Some(DEFAULT_EXPIRES_IN),
+            authorization_error: None,
+            token_error: None,
+            transient_failures: VecDeque::new(),
+            codes: HashMap::new(),
+            refresh_tokens: HashSet::new(),
+            access_tokens: HashSet::new(),
+            grant_types: vec![],
+            issued: 0,
+        }
+    }
+}
+
+impl MockOAuthState {
+    fn next_id(&mut self) -> u64 {
+        self.issued += 1;
+        self.issued
+    }
+}
+
+/// Local authorization server with authorize and token endpoints, plus an API under `/api`
+/// accepting the issued access tokens. Expiry and error responses are configured by the tests.
+#[derive(Clone)]
+pub struct MockOAuthServer {
+    pub base_url: String,
+    state: Arc<Mutex<MockOAuthState>>,
+}
+
+impl MockOAuthServer {
+    pub fn start() -> Self {
+        let state = Arc::new(Mutex::new(MockOAuthState::default()));
+        let data = web::Data::from(state.clone());
+
+        let base_url = spawn_server(move |cfg| {
+            cfg.app_data(data.clone())
+                .route("/authorize", web::get().to(authorize))
+                .route("/token", web::post().to(token))
+                .route("/api/{path:.*}", web::route().to(api));
+        });
+
+        Self { base_url, state }
+    }
+
+    /// Registry entry pointing at this server
+    pub fn provider_config(&self) -> OAuthProviderConfig {
+        OAuthProviderConfig {
+            id_provider: MOCK_ID_PROVIDER,
+            provider_name: MOCK_PROVIDER.to_string(),
+            auth_url: format!("{}/authorize", self.base_url),
+            token_url: format!("{}/token", self.base_url),
+            // oauth2 só revoga tokens em endpoints HTTPS; sem revocation_url o registro local
+            // é removido sem chamar o provedor
+            revocation_url: None,
+            device_authorization_url: None,
+            issuer: None,
+            redirect_url: MOCK_REDIRECT_URL.to_string(),
+            scopes: vec!["offline_access".to_string()],
+            scope_profiles: HashMap::from([
+                (
+                    "todo".to_string(),
+                    vec!["tasks.read".to_string(), "tasks.write".to_string()],
+                ),
+                ("mail".to_string(), vec!["mail.read".to_string()]),
+            ]),
+            default_scope_profiles: vec!["todo".to_string()],
+            include_granted_scopes: false,
+            app_token_url: Some(format!("{}/token", self.base_url)),
+            app_scopes: vec!["app.default".to_string()],
+            api_base_url: Some(format!("{}/api", self.base_url)),
+            auth_params: vec![],
+            token_params: vec![],
+            client_id_secret_key: MOCK_CLIENT_ID_SECRET_KEY.to_string(),
+            client_secret_secret_key: MOCK_CLIENT_SECRET_SECRET_KEY.to_string(),
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = MockOAuthState::default();
+    }
+
+    pub fn set_expires_in(&self, expires_in: Option<u64>) {
+        self.state.lock().unwrap().expires_in = expires_in;
+    }
+
+    pub fn deny_authorization(&self, error: &str, error_description: &str) {
+        self.state.lock().unwrap().authorization_error =
+            Some((error.to_string(), error_description.to_string()));
+    }
+
+    pub fn fail_token_requests(&self, error: &str, error_description: &str) {
+        self.state.lock().unwrap().token_error =
+            Some((error.to_string(), error_description.to_string()));
+    }
+
+    /// Fails the next `count` token requests with `status`, as an overloaded provider would
+    pub fn fail_next_token_requests(
+        &self,
+        count: usize,
+        status: StatusCode,
+        retry_after: Option<u64>,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .transient_failures
+            .extend(std::iter::repeat_n((status, retry_after), count));
+    }
+
+    /// Makes the API reject every access token issued so far, as after a provider-side revocation
+    pub fn reject_access_tokens(&self) {
+        self.state.lock().unwrap().access_tokens.clear();
+    }
+
+    pub fn token_requests(&self, grant_type: &str) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .grant_types
+            .iter()
+            .filter(|requested| requested.as_str() == grant_type)
+            .count()
+    }
+
+    fn configured_provider(&self) -> ConfiguredOAuthProvider {
+        ConfiguredOAuthProvider {
+            config: self.provider_config(),
+            client_id: MOCK_CLIENT_ID.to_string(),
+            client_secret: MOCK_CLIENT_SECRET.to_string(),
+        }
+    }
+}
+
+impl OAuthClientProvider for MockOAuthServer {
+    fn create_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>> {
+        self.configured_provider().create_client()
+    }
+
+    fn create_app_client(&self) -> Result<FridayOAuthClient, Box<dyn Error>> {
+        self.configured_provider().create_app_client()
+    }
+
+    fn get_auth_scopes(&self, profiles: &[String]) -> Result<Vec<String>, String> {
+        self.configured_provider().get_auth_scopes(profiles)
+    }
+
+    fn get_app_scopes(&self) -> Vec<String> {
+        self.configured_provider().get_app_scopes()
+    }
+
+    fn get_additional_auth_params(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn get_additional_token_params(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn provider_name(&self) -> &str {
+        MOCK_PROVIDER
+    }
+}
+
+/// Approves the authorization right away, redirecting to the callback with a new code
+async fn authorize(
+    state: web::Data<Mutex<MockOAuthState>>,
+    query: web::Query<HashMap<String, String>>,
+) -> HttpResponse {
+    let Some(redirect_uri) = query.get("redirect_uri") else {
+        return HttpResponse::BadRequest().body("redirect_uri is required");
+    };
+    let Ok(mut callback_url) = reqwest::Url::parse(redirect_uri) else {
+        return HttpResponse::BadRequest().body("invalid redirect_uri");
+    };
+
+    let mut state = state.lock().unwrap();
+
+    {
+        let mut callback_query = callback_url.query_pairs_mut();
+
+        match &state.authorization_error {
+            Some((error, error_description)) => {
+                callback_query
+                    .append_pair("error", error)
+                    .append_pair("error_description", error_description);
+            }
+            None => {
+                let code = format!("code-{}", state.next_id());
+                let scopes = query
+                    .get("scope")
+                    .map(|scope| scope.split(' ').map(str::to_string).collect())
+                    .unwrap_or_default();
+                state.codes.insert(code.clone(), scopes);
+                callback_query.append_pair("code", &code);
+            }
+        }
+
+        if let Some(csrf_state) = query.get("state") {
+            callback_query.append_pair("state", csrf_state);
+        }
+    }
+
+    HttpResponse::Found()
+        .insert_header((header::LOCATION, callback_url.to_string()))
+        .finish()
+}
+
+async fn token(
+    state: web::Data<Mutex<MockOAuthState>>,
+    request: HttpRequest,
+    form: web::Form<HashMap<String, String>>,
+) -> HttpResponse {
+    if !has_client_credentials(&request, &form) {
+        return token_error_response("invalid_client", "Client authentication failed");
+    }
+
+    let mut state = state.lock().unwrap();
+    let grant_type = form.get("grant_type").cloned().unwrap_or_default();
+    state.grant_types.push(grant_type.clone());
+
+    if let Some((status, retry_after)) = state.transient_failures.pop_front() {
+        let mut response = HttpResponse::build(status);
+        if let Some(retry_after) = retry_after {
+            response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+        return response.finish();
+    }
+
+    if let Some((error, error_description)) = state.token_error.clone() {
+        return token_error_response(&error, &error_description);
+    }
+
+    let (scopes, refresh_token) = match grant_type.as_str() {
+        "authorization_code" => {
+            let code = form.get("code").cloned().unwrap_or_default();
+            let Some(scopes) = state.codes.remove(&code) else {
+                return token_error_response("invalid_grant", "Unknown authorization code");
+            };
+            let refresh_token = format!("refresh-{}", state.next_id());
+            state.refresh_tokens.insert(refresh_token.clone());
+            (scopes, Some(refresh_token))
+        }
+        "refresh_token" => {
+            let refresh_token = form.get("refresh_token").cloned().unwrap_or_default();
+            if !state.refresh_tokens.contains(&refresh_token) {
+                return token_error_response("invalid_grant", "Refresh token is not valid");
+            }
+            let scopes = form
+                .get("scope")
+                .map(|scope| scope.split(' ').map(str::to_string).collect())
+                .unwrap_or_default();
+            (scopes, None)
+        }
+        "client_credentials" => {
+            let scopes = form
+                .get("scope")
+                .map(|scope| scope.split(' ').map(str::to_string).collect())
+                .unwrap_or_default();
+            (scopes, None)
+        }
+        _ => return token_error_response("unsupported_grant_type", &grant_type),
+    };
+
+    let access_token = format!("access-{}", state.next_id());
+    state.access_tokens.insert(access_token.clone());
+
+    let mut response = json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "scope": scopes.join(" ")
+    });
+    if let Some(expires_in) = state.expires_in {
+        response["expires_in"] = json!(expires_in);
+    }
+    if let Some(refresh_token) = refresh_token {
+        response["refresh_token"] = json!(refresh_token);
+    }
+
+    HttpResponse::Ok().json(response)
+}
+
+/// Echoes the request when the bearer token was issued by the token endpoint
+async fn api(
+    state: web::Data<Mutex<MockOAuthState>>,
+    request: HttpRequest,
+    path: web::Path<String>,
+    body: String,
+) -> HttpResponse {
+    let access_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    if !state.lock().unwrap().access_tokens.contains(access_token) {
+        return HttpResponse::Unauthorized().json(json!({
+            "error": { "code": "InvalidAuthenticationToken" }
+        }));
+    }
+
+    HttpResponse::Ok()
+        .insert_header(("X-Mock-Api", "true"))
+        .json(json!({
+            "method": request.method().as_str(),
+            "path": path.into_inner(),
+            "query": request.query_string(),
+            "access_token": access_token,
+            "test_header": request
+                .headers()
+                .get("X-Test")
+                .and_then(|value| value.to_str().ok()),
+            "credential_header_forwarded": request.headers().contains_key("X-Friday-Credential"),
+            "body": body
+        }))
+}
+
+/// Client authentication with HTTP Basic (oauth2 default) or form params
+fn has_client_credentials(request: &HttpRequest, form: &HashMap<String, String>) -> bool {
+    let expected = format!("{}:{}", MOCK_CLIENT_ID, MOCK_CLIENT_SECRET);
+
+    let basic = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .is_some_and(|decoded| decoded == expected.as_bytes());
+
+    let form_params = form.get("client_id").map(String::as_str) == Some(MOCK_CLIENT_ID)
+        && form.get("client_secret").map(String::as_str) == Some(MOCK_CLIENT_SECRET);
+
+    basic || form_params
+}
+
+fn token_error_response(error: &str, error_description: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "error": error,
+        "error_description": error_description
+    }))
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+
+use super::spawn_server;
+
+/// Friday secret manager stand-in serving a fixed set of secrets
+pub fn start(secrets: HashMap<String, String>) -> String {
+    let secrets = web::Data::new(secrets);
+
+    spawn_server(move |cfg| {
+        cfg.app_data(secrets.clone()).route(
+            "/secrets/get_secret_value/{key}",
+            web::get().to(get_secret_value),
+        );
+    })
+}
+
+async fn get_secret_value(
+    secrets: web::Data<HashMap<String, String>>,
+    key: web::Path<String>,
+) -> HttpResponse {
+    match secrets.get(key.as_str()) {
+        Some(value) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": value,
+            "errors": []
+        })),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::Value;
+
+use super::spawn_server;
+
+pub const WEBHOOK_SECRET: &str = "mock-webhook-secret";
+
+/// Credential event received by the mock webhook
+#[derive(Debug, Clone)]
+pub struct ReceivedEvent {
+    pub event_type: Option<String>,
+    pub signature: Option<String>,
+    pub body: String,
+    pub payload: Value,
+}
+
+/// Endpoint recording the credential events posted by the service
+#[derive(Clone)]
+pub struct MockWebhook {
+    pub url: String,
+    events: Arc<Mutex<Vec<ReceivedEvent>>>,
+}
+
+impl MockWebhook {
+    pub fn start() -> Self {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let data = web::Data::from(events.clone());
+
+        let base_url = spawn_server(move |cfg| {
+            cfg.app_data(data.clone())
+                .route("/credential-events", web::post().to(receive_event));
+        });
+
+        Self {
+            url: format!("{}/credential-events", base_url),
+            events,
+        }
+    }
+
+    pub fn reset(&self) {
+        self.events.lock().unwrap().clear();
+    }
+
+    /// Waits for an event of the given type; events are delivered in the background
+    pub async fn wait_for_event(&self, event_type: &str) -> Option<ReceivedEvent> {
+        for _ in 0..50 {
+            let event = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|event| event.payload["event_type"] == event_type)
+                .cloned();
+
+            if event.is_some() {
+                return event;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        None
+    }
+}
+
+async fn receive_event(
+    events: web::Data<Mutex<Vec<ReceivedEvent>>>,
+    request: HttpRequest,
+    body: String,
+) -> HttpResponse {
+    let header = |name: &str| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+
+    let Ok(payload) = serde_json::from_str(&body) else {
+        return HttpResponse::BadRequest().finish();
+    };
+
+    events.lock().unwrap().push(ReceivedEvent {
+        event_type: header("X-Friday-Event"),
+        signature: header("X-Friday-Signature"),
+        body,
+        payload,
+    });
+
+    HttpResponse::NoContent().finish()
+}
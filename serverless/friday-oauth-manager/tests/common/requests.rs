@@ -0,0 +1,109 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    http::{header, StatusCode},
+    test,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::{
+    mock_oauth_server::{MOCK_PROVIDER, MOCK_REDIRECT_URL},
+    SERVICE_AUTH_ADMIN_KEY,
+};
+
+pub const BASE_PATH: &str = "/api/friday-oauth-manager";
+
+/// Sends the request to the service, returning the status and the JSON body
+pub async fn send<S, R, B>(app: &S, request: R) -> (StatusCode, Value)
+where
+    S: Service<R, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let response = app.call(request).await.unwrap();
+    let status = response.status();
+    let body = test::read_body(response).await;
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+/// Generates the authorization URL and follows it on the mock provider, returning the
+/// callback URL the provider redirected to
+pub async fn authorize<S, B>(app: &S) -> String
+where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        test::TestRequest::get()
+            .uri(&format!("{}/oauth/url/{}", BASE_PATH, MOCK_PROVIDER))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let authorization_url = body["data"]["url"].as_str().unwrap();
+
+    let response = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(authorization_url)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FOUND);
+
+    let callback_url = response.headers()[header::LOCATION.as_str()]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(callback_url.starts_with(MOCK_REDIRECT_URL));
+
+    callback_url
+}
+
+pub fn exchange_code_request(callback_url: &str) -> actix_http::Request {
+    test::TestRequest::post()
+        .uri(&format!("{}/oauth/tokens", BASE_PATH))
+        .set_json(json!({ "url": callback_url, "provider": MOCK_PROVIDER }))
+        .to_request()
+}
+
+/// Registers a new service client allowed `scopes` and issues an internal token for it
+pub async fn service_token<S, B>(app: &S, scopes: &[&str]) -> String
+where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        test::TestRequest::post()
+            .uri(&format!("{}/service-auth/clients", BASE_PATH))
+            .insert_header(("X-Friday-Admin-Key", SERVICE_AUTH_ADMIN_KEY))
+            .set_json(json!({
+                "client_name": format!("test-client-{}", Uuid::new_v4().simple()),
+                "allowed_scopes": scopes
+            }))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["success"], true, "{}", body);
+
+    let (status, token) = send(
+        app,
+        test::TestRequest::post()
+            .uri(&format!("{}/service-auth/token", BASE_PATH))
+            .set_json(json!({
+                "client_id": body["data"]["service_client"]["id_service_client"],
+                "client_secret": body["data"]["client_secret"]
+            }))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", token);
+
+    token["data"]["access_token"].as_str().unwrap().to_string()
+}
@@ -0,0 +1,92 @@
+use std::{error::Error, path::Path};
+
+use friday_oauth_manager::oauth_provider::OAuthProviderConfig;
+use sqlx::{Connection, Executor, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+/// Prefix of the throwaway databases; leftovers of previous runs are dropped on setup
+const TEST_DATABASE_PREFIX: &str = "friday_oauth_manager_test_";
+
+/// Creates an empty database next to `admin_url` with the tables and procs of `database/`,
+/// returning its connection string
+pub async fn create_test_database(admin_url: &str) -> Result<String, Box<dyn Error>> {
+    let mut admin = PgConnection::connect(admin_url).await?;
+
+    let leftovers = sqlx::query("SELECT datname FROM pg_database WHERE datname LIKE $1")
+        .bind(format!("{}%", TEST_DATABASE_PREFIX))
+        .fetch_all(&mut admin)
+        .await?;
+    for leftover in leftovers {
+        let datname: String = leftover.try_get("datname")?;
+        // Bancos ainda em uso por outra execução não são removidos
+        let _ = admin
+            .execute(format!("DROP DATABASE IF EXISTS \"{}\"", datname).as_str())
+            .await;
+    }
+
+    let database_name = format!("{}{}", TEST_DATABASE_PREFIX, Uuid::new_v4().simple());
+    admin
+        .execute(format!("CREATE DATABASE \"{}\"", database_name).as_str())
+        .await?;
+    admin.close().await?;
+
+    let mut database_url = reqwest::Url::parse(admin_url)?;
+    database_url.set_path(&database_name);
+    let database_url = database_url.to_string();
+
+    let mut connection = PgConnection::connect(&database_url).await?;
+    let database_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("database");
+    for scripts_dir in ["table", "procs"] {
+        let mut scripts: Vec<_> = std::fs::read_dir(database_dir.join(scripts_dir))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|entry| entry.path())
+            .collect();
+        scripts.sort();
+
+        for script in scripts {
+            let sql = std::fs::read_to_string(&script)?;
+            connection
+                .execute(sql.as_str())
+                .await
+                .map_err(|e| format!("{}: {}", script.display(), e))?;
+        }
+    }
+    connection.close().await?;
+
+    Ok(database_url)
+}
+
+pub async fn insert_provider(
+    pool: &PgPool,
+    config: &OAuthProviderConfig,
+) -> Result<(), Box<dyn Error>> {
+    sqlx::query(
+        "INSERT INTO tb_oauth_providers (id_provider, provider_name, redirect_uri, auth_url, token_url, \
+         revocation_url, device_authorization_url, issuer, scopes, scope_profiles, default_scope_profiles, \
+         include_granted_scopes, app_token_url, app_scopes, api_base_url, client_id_secret_key, \
+         client_secret_secret_key) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)",
+    )
+    .bind(config.id_provider)
+    .bind(&config.provider_name)
+    .bind(&config.redirect_url)
+    .bind(&config.auth_url)
+    .bind(&config.token_url)
+    .bind(&config.revocation_url)
+    .bind(&config.device_authorization_url)
+    .bind(&config.issuer)
+    .bind(&config.scopes)
+    .bind(serde_json::to_value(&config.scope_profiles)?)
+    .bind(&config.default_scope_profiles)
+    .bind(config.include_granted_scopes)
+    .bind(&config.app_token_url)
+    .bind(&config.app_scopes)
+    .bind(&config.api_base_url)
+    .bind(&config.client_id_secret_key)
+    .bind(&config.client_secret_secret_key)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,601 @@
+//! End-to-end tests of the authorization code, refresh and revocation flows and of the
+//! credential events they publish, against the mock OAuth provider and a throwaway Postgres
+//! (see `common`).
+
+mod common;
+
+use actix_web::{
+    dev::Service,
+    http::{header, StatusCode},
+    test, App,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use friday_oauth_manager::{configure_services, oauth_provider::OAuthClientProvider};
+use oauth2::TokenResponse;
+use ring::hmac;
+use serde_json::{json, Value};
+
+use common::{
+    mock_oauth_server::{MOCK_ID_PROVIDER, MOCK_PROVIDER, MOCK_REDIRECT_URL},
+    mock_webhook::WEBHOOK_SECRET,
+    requests::{authorize, exchange_code_request, send, BASE_PATH},
+    test_environment,
+};
+
+fn refresh_access_token_request(refresh_token: &Value) -> actix_http::Request {
+    test::TestRequest::post()
+        .uri(&format!("{}/oauth/refresh-access-token", BASE_PATH))
+        .set_json(json!({ "refresh_token": refresh_token, "provider": MOCK_PROVIDER }))
+        .to_request()
+}
+
+#[actix_web::test]
+async fn authorization_code_flow_stores_the_credential() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["success"], true);
+    let oauth_tokens = &body["data"]["oauth_tokens"];
+    assert_eq!(oauth_tokens["id_provider"], MOCK_PROVIDER.to_uppercase());
+    assert_eq!(
+        oauth_tokens["granted_scopes"],
+        json!(["offline_access", "tasks.read", "tasks.write"])
+    );
+
+    let stored: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tb_oauth_tokens WHERE id_provider = $1")
+            .bind(MOCK_ID_PROVIDER)
+            .fetch_one(&env.pool().await)
+            .await
+            .unwrap();
+    assert_eq!(stored, 1);
+}
+
+#[actix_web::test]
+async fn generate_access_token_refreshes_expiring_tokens() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    // Tokens expiring within the 30s buffer are refreshed on the next request
+    env.oauth_server.set_expires_in(Some(10));
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let first_access_token = body["data"]["oauth_tokens"]["access_token"].clone();
+
+    env.oauth_server.set_expires_in(Some(3600));
+    let generate_access_token = || {
+        test::TestRequest::get()
+            .uri(&format!("{}/oauth/generate-access-token", BASE_PATH))
+            .to_request()
+    };
+
+    let (status, body) = send(&app, generate_access_token()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let refreshed_access_token = body["data"].clone();
+    assert_ne!(refreshed_access_token, first_access_token);
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 1);
+
+    let (status, body) = send(&app, generate_access_token()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"], refreshed_access_token);
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 1);
+}
+
+#[actix_web::test]
+async fn generate_access_token_requires_the_granted_scopes() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    send(&app, exchange_code_request(&callback_url)).await;
+
+    let generate_access_token = |scopes: &str| {
+        test::TestRequest::get()
+            .uri(&format!(
+                "{}/oauth/generate-access-token?scopes={}",
+                BASE_PATH, scopes
+            ))
+            .to_request()
+    };
+
+    let (status, body) = send(&app, generate_access_token("tasks.read")).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = send(&app, generate_access_token("tasks.read,mail.read")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["missing_scopes"], json!(["mail.read"]));
+}
+
+#[actix_web::test]
+async fn generate_access_token_of_a_revoked_credential_is_unauthorized() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    env.oauth_server.set_expires_in(Some(10));
+    let callback_url = authorize(&app).await;
+    send(&app, exchange_code_request(&callback_url)).await;
+
+    env.oauth_server
+        .fail_token_requests("invalid_grant", "Token has been revoked");
+    let (status, body) = send(
+        &app,
+        test::TestRequest::get()
+            .uri(&format!("{}/oauth/generate-access-token", BASE_PATH))
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["revoked"], true);
+    assert!(body["data"]["reconsent_url"].is_string());
+}
+
+#[actix_web::test]
+async fn missing_expires_in_falls_back_to_the_default_lifetime() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    env.oauth_server.set_expires_in(None);
+    let callback_url = authorize(&app).await;
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let expiry_date: DateTime<Utc> =
+        serde_json::from_value(body["data"]["oauth_tokens"]["expiry_date"].clone()).unwrap();
+    assert!(expiry_date > Utc::now() + Duration::minutes(55));
+}
+
+#[actix_web::test]
+async fn authorization_denied_by_the_provider_is_returned_to_the_caller() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    env.oauth_server
+        .deny_authorization("access_denied", "The user declined the consent");
+    let callback_url = authorize(&app).await;
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["error"], "access_denied");
+    assert_eq!(
+        body["data"]["error_description"],
+        "The user declined the consent"
+    );
+}
+
+#[actix_web::test]
+async fn callback_without_code_is_a_bad_request() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = format!("{}?state=unknown", MOCK_REDIRECT_URL);
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+
+    let (status, body) = send(&app, exchange_code_request("not a url")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+}
+
+#[actix_web::test]
+async fn callback_with_an_unknown_state_is_a_bad_request() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let mut forged_url = reqwest::Url::parse(&callback_url).unwrap();
+    let pairs: Vec<(String, String)> = forged_url
+        .query_pairs()
+        .filter(|(key, _)| key != "state")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    forged_url
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("state", "forged");
+
+    let (status, body) = send(&app, exchange_code_request(forged_url.as_str())).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(env.oauth_server.token_requests("authorization_code"), 0);
+
+    // A autorização legítima continua disponível
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[actix_web::test]
+async fn token_endpoint_errors_are_reported_as_bad_gateway() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    env.oauth_server
+        .fail_token_requests("server_error", "Temporarily unavailable");
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY, "{}", body);
+    assert!(body["errors"][0].as_str().unwrap().contains("server_error"));
+}
+
+#[actix_web::test]
+async fn transient_token_endpoint_failures_are_retried() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    env.oauth_server
+        .fail_next_token_requests(2, StatusCode::SERVICE_UNAVAILABLE, None);
+    let (status, body) = send(&app, refresh_access_token_request(&refresh_token)).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["success"], true);
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 3);
+}
+
+#[actix_web::test]
+async fn throttled_token_requests_wait_for_retry_after() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    env.oauth_server
+        .fail_next_token_requests(1, StatusCode::TOO_MANY_REQUESTS, Some(1));
+    let started_at = std::time::Instant::now();
+    let (status, body) = send(&app, refresh_access_token_request(&refresh_token)).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(started_at.elapsed() >= std::time::Duration::from_secs(1));
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 2);
+}
+
+#[actix_web::test]
+async fn refreshes_are_rate_limited_per_account() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    for _ in 0..6 {
+        let (status, body) = send(&app, refresh_access_token_request(&refresh_token)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    let response = app
+        .call(refresh_access_token_request(&refresh_token))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key(header::RETRY_AFTER));
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 6);
+}
+
+#[actix_web::test]
+async fn revoked_refresh_token_is_flagged_with_a_reconsent_url() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    env.oauth_server
+        .fail_token_requests("invalid_grant", "Token has been revoked");
+    let (status, body) = send(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("{}/oauth/refresh-access-token", BASE_PATH))
+            .set_json(json!({ "refresh_token": refresh_token, "provider": MOCK_PROVIDER }))
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["revoked"], true);
+    assert!(body["data"]["reconsent_url"]
+        .as_str()
+        .unwrap()
+        .starts_with(&env.oauth_server.base_url));
+
+    let (revoked, last_refresh_error): (bool, Option<String>) = sqlx::query_as(
+        "SELECT revoked_at IS NOT NULL, last_refresh_error FROM tb_oauth_tokens WHERE refresh_token = $1",
+    )
+    .bind(refresh_token.as_str())
+    .fetch_one(&env.pool().await)
+    .await
+    .unwrap();
+    assert!(revoked);
+    assert!(last_refresh_error.unwrap().contains("invalid_grant"));
+}
+
+#[actix_web::test]
+async fn revoke_deletes_the_credential() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    let (status, body) = send(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("{}/oauth/revoke", BASE_PATH))
+            .set_json(json!({ "refresh_token": refresh_token, "provider": MOCK_PROVIDER }))
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["provider_revoked"], false);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tb_oauth_tokens")
+        .fetch_one(&env.pool().await)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+}
+
+#[actix_web::test]
+async fn app_tokens_are_cached_until_they_expire() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let generate_app_token = || {
+        test::TestRequest::get()
+            .uri(&format!("{}/oauth/app-token/{}", BASE_PATH, MOCK_PROVIDER))
+            .to_request()
+    };
+
+    let (status, first) = send(&app, generate_app_token()).await;
+    assert_eq!(status, StatusCode::OK, "{}", first);
+    let (_, second) = send(&app, generate_app_token()).await;
+
+    assert_eq!(
+        first["data"]["app_token"]["access_token"],
+        second["data"]["app_token"]["access_token"]
+    );
+    assert_eq!(env.oauth_server.token_requests("client_credentials"), 1);
+}
+
+#[actix_web::test]
+async fn mock_server_is_usable_as_an_oauth_client_provider() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+
+    let client = env.oauth_server.create_app_client().unwrap();
+    let tokens_response = client
+        .exchange_client_credentials()
+        .request_async(oauth2::reqwest::async_http_client)
+        .await
+        .unwrap();
+
+    assert!(tokens_response
+        .access_token()
+        .secret()
+        .starts_with("access-"));
+    assert_eq!(env.oauth_server.provider_name(), MOCK_PROVIDER);
+}
+
+#[actix_web::test]
+async fn acquired_credentials_are_notified_to_the_webhook() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (status, body) = send(&app, exchange_code_request(&callback_url)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let event = env
+        .webhook
+        .wait_for_event("token_acquired")
+        .await
+        .expect("token_acquired event not delivered");
+
+    assert_eq!(event.event_type.as_deref(), Some("token_acquired"));
+    assert_eq!(event.payload["provider"], MOCK_PROVIDER.to_uppercase());
+    assert_eq!(event.payload["requires_reconsent"], false);
+    assert!(event.payload.get("access_token").is_none());
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, WEBHOOK_SECRET.as_bytes());
+    let signature = event
+        .signature
+        .unwrap()
+        .strip_prefix("sha256=")
+        .map(|signature| STANDARD.decode(signature).unwrap())
+        .expect("signature without the sha256= prefix");
+    assert!(hmac::verify(&key, event.body.as_bytes(), &signature).is_ok());
+}
+
+#[actix_web::test]
+async fn revoked_refresh_token_is_notified_with_a_reconsent_url() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let refresh_token = body["data"]["oauth_tokens"]["refresh_token"].clone();
+
+    env.oauth_server
+        .fail_token_requests("invalid_grant", "Token has been revoked");
+    send(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("{}/oauth/refresh-access-token", BASE_PATH))
+            .set_json(json!({ "refresh_token": refresh_token, "provider": MOCK_PROVIDER }))
+            .to_request(),
+    )
+    .await;
+
+    let event = env
+        .webhook
+        .wait_for_event("token_refresh_failed")
+        .await
+        .expect("token_refresh_failed event not delivered");
+
+    assert_eq!(event.payload["requires_reconsent"], true);
+    assert!(event.payload["id_oauth_tokens"].is_string());
+    assert!(event.payload["error"]
+        .as_str()
+        .unwrap()
+        .contains("invalid_grant"));
+    assert!(event.payload["reconsent_url"]
+        .as_str()
+        .unwrap()
+        .starts_with(&env.oauth_server.base_url));
+}
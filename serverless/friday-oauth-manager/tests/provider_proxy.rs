@@ -0,0 +1,181 @@
+//! End-to-end tests of the provider API proxy against the mock OAuth provider and a throwaway
+//! Postgres (see `common`).
+
+mod common;
+
+use actix_web::{
+    dev::Service,
+    http::{header, StatusCode},
+    test, App,
+};
+use friday_oauth_manager::configure_services;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::{
+    mock_oauth_server::MOCK_PROVIDER,
+    requests::{authorize, exchange_code_request, send, service_token, BASE_PATH},
+    test_environment,
+};
+
+const PROXY_SCOPE: &str = "oauth.proxy";
+
+fn proxy_request(path: &str, service_token: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri(&format!("{}/proxy/{}/{}", BASE_PATH, MOCK_PROVIDER, path))
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", service_token)))
+}
+
+#[actix_web::test]
+async fn proxy_forwards_the_request_with_the_credential_access_token() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let access_token = body["data"]["oauth_tokens"]["access_token"].clone();
+    let token = service_token(&app, &[PROXY_SCOPE]).await;
+
+    let response = app
+        .call(
+            proxy_request("v1/me/items?top=5", &token)
+                .method(actix_web::http::Method::POST)
+                .insert_header(("X-Test", "forwarded"))
+                .set_json(json!({ "title": "Comprar pão" }))
+                .to_request(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("X-Mock-Api"));
+    let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+    assert_eq!(body["method"], "POST");
+    assert_eq!(body["path"], "v1/me/items");
+    assert_eq!(body["query"], "top=5");
+    assert_eq!(body["access_token"], access_token);
+    assert_eq!(body["test_header"], "forwarded");
+    assert_eq!(
+        serde_json::from_str::<Value>(body["body"].as_str().unwrap()).unwrap(),
+        json!({ "title": "Comprar pão" })
+    );
+}
+
+#[actix_web::test]
+async fn rejected_access_token_is_refreshed_and_the_request_retried() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    let (_, body) = send(&app, exchange_code_request(&callback_url)).await;
+    let first_access_token = body["data"]["oauth_tokens"]["access_token"].clone();
+    let token = service_token(&app, &[PROXY_SCOPE]).await;
+
+    env.oauth_server.reject_access_tokens();
+    let (status, body) = send(&app, proxy_request("v1/me", &token).to_request()).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_ne!(body["access_token"], first_access_token);
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 1);
+
+    let stored_access_token: String =
+        sqlx::query_scalar("SELECT access_token FROM tb_oauth_tokens")
+            .fetch_one(&env.pool().await)
+            .await
+            .unwrap();
+    assert_eq!(Value::from(stored_access_token), body["access_token"]);
+}
+
+#[actix_web::test]
+async fn proxy_requires_a_service_token_with_the_proxy_scope() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let (status, body) = send(
+        &app,
+        test::TestRequest::get()
+            .uri(&format!("{}/proxy/{}/v1/me", BASE_PATH, MOCK_PROVIDER))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+
+    let (status, body) = send(&app, proxy_request("v1/me", "not-a-jwt").to_request()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+
+    let token = service_token(&app, &["todo.read"]).await;
+    let (status, body) = send(&app, proxy_request("v1/me", &token).to_request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+}
+
+#[actix_web::test]
+async fn paths_outside_the_provider_api_are_rejected() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let token = service_token(&app, &[PROXY_SCOPE]).await;
+    let (status, body) = send(&app, proxy_request("%2e%2e/token", &token).to_request()).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert_eq!(env.oauth_server.token_requests("refresh_token"), 0);
+}
+
+#[actix_web::test]
+async fn unknown_credential_is_not_found() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let callback_url = authorize(&app).await;
+    send(&app, exchange_code_request(&callback_url)).await;
+    let token = service_token(&app, &[PROXY_SCOPE]).await;
+
+    let (status, body) = send(
+        &app,
+        proxy_request("v1/me", &token)
+            .insert_header(("X-Friday-Credential", Uuid::new_v4().to_string()))
+            .to_request(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND, "{}", body);
+}
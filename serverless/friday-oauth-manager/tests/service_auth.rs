@@ -0,0 +1,118 @@
+//! End-to-end tests of service client registration and internal token issuance against a
+//! throwaway Postgres (see `common`).
+
+mod common;
+
+use actix_web::{http::StatusCode, test, App};
+use friday_oauth_manager::configure_services;
+use serde_json::json;
+use uuid::Uuid;
+
+use common::{
+    requests::{send, BASE_PATH},
+    test_environment, SERVICE_AUTH_ADMIN_KEY,
+};
+
+fn register_request(admin_key: &str, client_name: &str, scopes: &[&str]) -> actix_http::Request {
+    test::TestRequest::post()
+        .uri(&format!("{}/service-auth/clients", BASE_PATH))
+        .insert_header(("X-Friday-Admin-Key", admin_key))
+        .set_json(json!({ "client_name": client_name, "allowed_scopes": scopes }))
+        .to_request()
+}
+
+fn token_request(
+    client_id: &serde_json::Value,
+    client_secret: &str,
+    scopes: &[&str],
+) -> actix_http::Request {
+    test::TestRequest::post()
+        .uri(&format!("{}/service-auth/token", BASE_PATH))
+        .set_json(json!({
+            "client_id": client_id,
+            "client_secret": client_secret,
+            "scopes": scopes
+        }))
+        .to_request()
+}
+
+#[actix_web::test]
+async fn registration_errors_are_reported_with_their_status() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let client_name = format!("test-client-{}", Uuid::new_v4().simple());
+
+    let (status, body) = send(&app, register_request("wrong-key", &client_name, &[])).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+    assert_eq!(body["success"], false);
+
+    let (status, body) = send(&app, register_request(SERVICE_AUTH_ADMIN_KEY, "  ", &[])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+
+    let (status, body) = send(
+        &app,
+        register_request(SERVICE_AUTH_ADMIN_KEY, &client_name, &[]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = send(
+        &app,
+        register_request(SERVICE_AUTH_ADMIN_KEY, &client_name, &[]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{}", body);
+    assert_eq!(body["success"], false);
+}
+
+#[actix_web::test]
+async fn token_errors_are_reported_with_their_status() {
+    let Some(env) = test_environment() else {
+        return;
+    };
+    let _guard = env.begin().await;
+    let app = test::init_service(
+        App::new()
+            .app_data(env.database_pool().await)
+            .configure(configure_services),
+    )
+    .await;
+
+    let client_name = format!("test-client-{}", Uuid::new_v4().simple());
+    let (_, registered) = send(
+        &app,
+        register_request(SERVICE_AUTH_ADMIN_KEY, &client_name, &["oauth.proxy"]),
+    )
+    .await;
+    let client_id = &registered["data"]["service_client"]["id_service_client"];
+    let client_secret = registered["data"]["client_secret"].as_str().unwrap();
+
+    let (status, body) = send(&app, token_request(client_id, "wrong-secret", &[])).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+    assert_eq!(body["success"], false);
+
+    let (status, body) = send(
+        &app,
+        token_request(client_id, client_secret, &["oauth.admin"]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+    assert_eq!(body["success"], false);
+
+    let (status, body) = send(
+        &app,
+        token_request(client_id, client_secret, &["oauth.proxy"]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["scope"], "oauth.proxy");
+}
@@ -0,0 +1,30 @@
+//! Verification of the internal service tokens issued by friday-oauth-manager.
+//!
+//! Tokens are EdDSA-signed JWTs obtained with the client-credentials grant at
+//! `POST /api/friday-oauth-manager/service-auth/token` and verified against the keys
+//! published at `/api/friday-oauth-manager/.well-known/jwks.json`.
+//!
+//! actix-web services wrap their routes with `ServiceAuth` (feature `actix`, enabled by
+//! default) and axum services with `ServiceAuthLayer` (feature `axum`); both read the
+//! verified [`ServiceClaims`] as an extractor. Other frameworks call
+//! [`ServiceTokenVerifier::authorize`] with the `Authorization` header.
+
+mod service_auth_error;
+#[cfg(feature = "axum")]
+mod service_auth_layer;
+#[cfg(feature = "actix")]
+mod service_auth_middleware;
+mod service_claims;
+mod service_token_verifier;
+#[cfg(all(test, any(feature = "actix", feature = "axum")))]
+mod test_support;
+
+pub use service_auth_error::ServiceAuthError;
+#[cfg(feature = "axum")]
+pub use service_auth_layer::{ServiceAuthLayer, ServiceAuthService};
+#[cfg(feature = "actix")]
+pub use service_auth_middleware::ServiceAuth;
+pub use service_claims::ServiceClaims;
+pub use service_token_verifier::{
+    ServiceTokenVerifier, SERVICE_TOKEN_AUDIENCE, SERVICE_TOKEN_ISSUER,
+};
@@ -0,0 +1,54 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ServiceAuthError {
+    /// No `Authorization: Bearer` header
+    MissingToken,
+    /// Bad signature, expired, wrong issuer/audience or unknown signing key
+    InvalidToken(String),
+    /// The token is valid but was not granted a required scope
+    MissingScope(String),
+    /// The JWKS could not be fetched from friday-oauth-manager
+    KeysUnavailable(String),
+}
+
+impl fmt::Display for ServiceAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceAuthError::MissingToken => write!(f, "Missing service token"),
+            ServiceAuthError::InvalidToken(reason) => {
+                write!(f, "Invalid service token: {}", reason)
+            }
+            ServiceAuthError::MissingScope(scope) => {
+                write!(f, "Service token was not granted scope '{}'", scope)
+            }
+            ServiceAuthError::KeysUnavailable(reason) => {
+                write!(f, "Service token keys unavailable: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ServiceAuthError {}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl ServiceAuthError {
+    /// HTTP status returned by the middlewares: 401 without a valid token, 403 without a
+    /// required scope and 503 when the keys cannot be fetched
+    pub(crate) fn status(&self) -> u16 {
+        match self {
+            ServiceAuthError::MissingToken | ServiceAuthError::InvalidToken(_) => 401,
+            ServiceAuthError::MissingScope(_) => 403,
+            ServiceAuthError::KeysUnavailable(_) => 503,
+        }
+    }
+
+    /// Same shape as the `BusinessResponse` of the Friday services
+    pub(crate) fn response_body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "success": false,
+            "data": null,
+            "errors": [self.to_string()]
+        })
+    }
+}
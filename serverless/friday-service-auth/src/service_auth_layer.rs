@@ -0,0 +1,240 @@
+use std::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::warn;
+
+use crate::{ServiceAuthError, ServiceClaims, ServiceTokenVerifier};
+
+/// axum layer rejecting requests without a valid internal service token.
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use axum::{routing::post, Router};
+/// use friday_service_auth::{ServiceAuthLayer, ServiceClaims, ServiceTokenVerifier};
+///
+/// async fn invoke(claims: ServiceClaims) -> String {
+///     claims.sub
+/// }
+///
+/// let verifier = Arc::new(ServiceTokenVerifier::new("https://k8s.z33p.com/api/friday-oauth-manager"));
+/// let app: Router = Router::new()
+///     .route("/internal/invoke", post(invoke))
+///     .layer(ServiceAuthLayer::new(verifier).require_scope("mcp.invoke"));
+/// ```
+#[derive(Clone)]
+pub struct ServiceAuthLayer {
+    verifier: Arc<ServiceTokenVerifier>,
+    required_scopes: Arc<Vec<String>>,
+}
+
+impl ServiceAuthLayer {
+    pub fn new(verifier: Arc<ServiceTokenVerifier>) -> Self {
+        Self {
+            verifier,
+            required_scopes: Arc::new(vec![]),
+        }
+    }
+
+    pub fn require_scope(mut self, scope: &str) -> Self {
+        Arc::make_mut(&mut self.required_scopes).push(scope.to_string());
+        self
+    }
+}
+
+impl<S> Layer<S> for ServiceAuthLayer {
+    type Service = ServiceAuthService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ServiceAuthService {
+            service,
+            verifier: self.verifier.clone(),
+            required_scopes: self.required_scopes.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ServiceAuthService<S> {
+    service: S,
+    verifier: Arc<ServiceTokenVerifier>,
+    required_scopes: Arc<Vec<String>>,
+}
+
+impl<S> Service<Request> for ServiceAuthService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        // The service polled ready is the one that must handle the request
+        let ready_service = self.service.clone();
+        let mut service = std::mem::replace(&mut self.service, ready_service);
+        let verifier = Arc::clone(&self.verifier);
+        let required_scopes = Arc::clone(&self.required_scopes);
+
+        Box::pin(async move {
+            let authorization_header = request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+
+            match verifier
+                .authorize(authorization_header, &required_scopes)
+                .await
+            {
+                Ok(claims) => {
+                    request.extensions_mut().insert(claims);
+                    service.call(request).await
+                }
+                Err(e) => {
+                    warn!(path = %request.uri().path(), "Requisição de serviço rejeitada: {}", e);
+                    Ok(e.into_response())
+                }
+            }
+        })
+    }
+}
+
+impl IntoResponse for ServiceAuthError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status()).unwrap();
+        (status, Json(self.response_body())).into_response()
+    }
+}
+
+/// Claims verified by [`ServiceAuthLayer`] for the current request
+#[async_trait]
+impl<S> FromRequestParts<S> for ServiceClaims
+where
+    S: Send + Sync,
+{
+    type Rejection = ServiceAuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<ServiceClaims>()
+            .cloned()
+            .ok_or(ServiceAuthError::MissingToken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http,
+        routing::get,
+        Router,
+    };
+    use jsonwebtoken::jwk::JwkSet;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::test_support::{bearer, claims, SigningKey};
+
+    async fn whoami(claims: ServiceClaims) -> String {
+        claims.sub
+    }
+
+    fn app(key: &SigningKey) -> Router {
+        let verifier = Arc::new(ServiceTokenVerifier::with_jwks(&JwkSet {
+            keys: vec![key.jwk.clone()],
+        }));
+
+        Router::new()
+            .route("/internal/whoami", get(whoami))
+            .layer(ServiceAuthLayer::new(verifier).require_scope("mcp.invoke"))
+            .route("/public/whoami", get(whoami))
+    }
+
+    async fn call(app: Router, uri: &str, authorization: Option<String>) -> (StatusCode, Body) {
+        let mut request = http::Request::builder().uri(uri);
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        (response.status(), response.into_body())
+    }
+
+    async fn read_errors(body: Body) -> Vec<Value> {
+        let body: Value =
+            serde_json::from_slice(&to_bytes(body, usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body["success"], false);
+        body["errors"].as_array().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn passes_the_verified_claims_to_the_handler() {
+        let key = SigningKey::generate("key-1");
+        let token = bearer(&key.sign(&claims("mcp.invoke tasks.read")));
+
+        let (status, body) = call(app(&key), "/internal/whoami", Some(token)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let body = to_bytes(body, usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"friday-todo-manager");
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_and_invalid_tokens_with_401() {
+        let key = SigningKey::generate("key-1");
+
+        let (status, body) = call(app(&key), "/internal/whoami", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(read_errors(body).await.len(), 1);
+
+        let forged = SigningKey::generate("key-1").sign(&claims("mcp.invoke"));
+        let (status, _) = call(app(&key), "/internal/whoami", Some(bearer(&forged))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_missing_scope_with_403() {
+        let key = SigningKey::generate("key-1");
+        let token = bearer(&key.sign(&claims("tasks.read")));
+
+        let (status, body) = call(app(&key), "/internal/whoami", Some(token)).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let errors = read_errors(body).await;
+        assert!(errors[0].as_str().unwrap().contains("mcp.invoke"));
+    }
+
+    #[tokio::test]
+    async fn the_extractor_rejects_routes_outside_the_layer() {
+        let key = SigningKey::generate("key-1");
+        let token = bearer(&key.sign(&claims("mcp.invoke")));
+
+        let (status, _) = call(app(&key), "/public/whoami", Some(token)).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
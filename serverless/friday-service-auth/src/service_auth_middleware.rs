@@ -0,0 +1,143 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{header::AUTHORIZATION, StatusCode},
+    Error, FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError,
+};
+use tracing::warn;
+
+use crate::{ServiceAuthError, ServiceClaims, ServiceTokenVerifier};
+
+/// actix-web middleware rejecting requests without a valid internal service token.
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use actix_web::{web, App};
+/// use friday_service_auth::{ServiceAuth, ServiceTokenVerifier};
+///
+/// let verifier = Arc::new(ServiceTokenVerifier::new("https://k8s.z33p.com/api/friday-oauth-manager"));
+/// let app = App::new().service(
+///     web::scope("/internal").wrap(ServiceAuth::new(verifier).require_scope("todo.write")),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ServiceAuth {
+    verifier: Arc<ServiceTokenVerifier>,
+    required_scopes: Rc<Vec<String>>,
+}
+
+impl ServiceAuth {
+    pub fn new(verifier: Arc<ServiceTokenVerifier>) -> Self {
+        Self {
+            verifier,
+            required_scopes: Rc::new(vec![]),
+        }
+    }
+
+    pub fn require_scope(mut self, scope: &str) -> Self {
+        Rc::make_mut(&mut self.required_scopes).push(scope.to_string());
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ServiceAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ServiceAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ServiceAuthMiddleware {
+            service: Rc::new(service),
+            verifier: self.verifier.clone(),
+            required_scopes: self.required_scopes.clone(),
+        }))
+    }
+}
+
+pub struct ServiceAuthMiddleware<S> {
+    service: Rc<S>,
+    verifier: Arc<ServiceTokenVerifier>,
+    required_scopes: Rc<Vec<String>>,
+}
+
+impl<S, B> Service<ServiceRequest> for ServiceAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let verifier = Arc::clone(&self.verifier);
+        let required_scopes = Rc::clone(&self.required_scopes);
+
+        Box::pin(async move {
+            let authorization_header = request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+
+            match verifier
+                .authorize(authorization_header, &required_scopes)
+                .await
+            {
+                Ok(claims) => {
+                    request.extensions_mut().insert(claims);
+                    service
+                        .call(request)
+                        .await
+                        .map(ServiceResponse::map_into_left_body)
+                }
+                Err(e) => {
+                    warn!(path = %request.path(), "Requisição de serviço rejeitada: {}", e);
+                    let response = e.error_response();
+                    Ok(request.into_response(response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+impl ResponseError for ServiceAuthError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self.response_body())
+    }
+}
+
+/// Claims verified by [`ServiceAuth`] for the current request
+impl FromRequest for ServiceClaims {
+    type Error = ServiceAuthError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            request
+                .extensions()
+                .get::<ServiceClaims>()
+                .cloned()
+                .ok_or(ServiceAuthError::MissingToken),
+        )
+    }
+}
@@ -0,0 +1,27 @@
+use serde_derive::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Verified claims of an internal service token
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServiceClaims {
+    pub iss: String,
+    /// Name of the calling service client
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: Uuid,
+    pub client_id: Uuid,
+    /// Space-separated scopes granted to the token
+    pub scope: String,
+}
+
+impl ServiceClaims {
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.scope.split_whitespace()
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes().any(|granted| granted == scope)
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use tracing::{debug, warn};
+
+use crate::{ServiceAuthError, ServiceClaims};
+
+pub const SERVICE_TOKEN_ISSUER: &str = "friday-oauth-manager";
+pub const SERVICE_TOKEN_AUDIENCE: &str = "friday-services";
+
+/// Minimum interval between JWKS fetches triggered by unknown key ids
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Verifies internal service tokens with the JWKS published by friday-oauth-manager.
+/// Keys are cached by `kid` and fetched again when a token uses an unknown key.
+pub struct ServiceTokenVerifier {
+    client: reqwest::Client,
+    /// `None` when the keys are known up front (friday-oauth-manager verifying its own tokens)
+    jwks_url: Option<String>,
+    validation: Validation,
+    keys: RwLock<HashMap<String, DecodingKey>>,
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+impl ServiceTokenVerifier {
+    /// `oauth_manager_url` is the same base URL the services use for friday-oauth-manager
+    /// (e.g. "https://k8s.z33p.com/api/friday-oauth-manager")
+    pub fn new(oauth_manager_url: &str) -> Self {
+        Self::build(
+            Some(format!(
+                "{}/.well-known/jwks.json",
+                oauth_manager_url.trim_end_matches('/')
+            )),
+            HashMap::new(),
+        )
+    }
+
+    /// Verifier with a fixed set of keys that never fetches the JWKS, for the issuer itself
+    pub fn with_jwks(jwks: &JwkSet) -> Self {
+        Self::build(None, get_decoding_keys(jwks))
+    }
+
+    fn build(jwks_url: Option<String>, keys: HashMap<String, DecodingKey>) -> Self {
+        let mut validation = Validation::new(Algorithm::EdDSA);
+        validation.set_issuer(&[SERVICE_TOKEN_ISSUER]);
+        validation.set_audience(&[SERVICE_TOKEN_AUDIENCE]);
+
+        Self {
+            client: reqwest::Client::new(),
+            jwks_url,
+            validation,
+            keys: RwLock::new(keys),
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// Verifies the token of an `Authorization: Bearer` header and checks it was granted
+    /// every required scope
+    pub async fn authorize(
+        &self,
+        authorization_header: Option<&str>,
+        required_scopes: &[String],
+    ) -> Result<ServiceClaims, ServiceAuthError> {
+        let token = authorization_header
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or(ServiceAuthError::MissingToken)?;
+
+        let claims = self.verify(token).await?;
+
+        if let Some(missing) = required_scopes
+            .iter()
+            .find(|scope| !claims.has_scope(scope))
+        {
+            return Err(ServiceAuthError::MissingScope(missing.clone()));
+        }
+
+        Ok(claims)
+    }
+
+    pub async fn verify(&self, token: &str) -> Result<ServiceClaims, ServiceAuthError> {
+        let header =
+            decode_header(token).map_err(|e| ServiceAuthError::InvalidToken(e.to_string()))?;
+        let kid = header
+            .kid
+            .ok_or_else(|| ServiceAuthError::InvalidToken("token has no kid".to_string()))?;
+
+        let decoding_key = self.get_decoding_key(&kid).await?;
+
+        decode::<ServiceClaims>(token, &decoding_key, &self.validation)
+            .map(|data| data.claims)
+            .map_err(|e| ServiceAuthError::InvalidToken(e.to_string()))
+    }
+
+    async fn get_decoding_key(&self, kid: &str) -> Result<DecodingKey, ServiceAuthError> {
+        if let Some(decoding_key) = self.keys.read().unwrap().get(kid) {
+            return Ok(decoding_key.clone());
+        }
+
+        self.refresh_keys().await?;
+
+        self.keys
+            .read()
+            .unwrap()
+            .get(kid)
+            .cloned()
+            .ok_or_else(|| ServiceAuthError::InvalidToken(format!("unknown signing key '{}'", kid)))
+    }
+
+    async fn refresh_keys(&self) -> Result<(), ServiceAuthError> {
+        let Some(jwks_url) = &self.jwks_url else {
+            return Ok(());
+        };
+
+        {
+            let mut last_refresh = self.last_refresh.lock().unwrap();
+            if last_refresh.is_some_and(|at| at.elapsed() < JWKS_REFRESH_INTERVAL) {
+                return Ok(());
+            }
+            *last_refresh = Some(Instant::now());
+        }
+
+        debug!("Buscando JWKS de serviços em {}", jwks_url);
+
+        let jwks: JwkSet = self
+            .client
+            .get(jwks_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| ServiceAuthError::KeysUnavailable(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ServiceAuthError::KeysUnavailable(e.to_string()))?;
+
+        *self.keys.write().unwrap() = get_decoding_keys(&jwks);
+
+        Ok(())
+    }
+}
+
+fn get_decoding_keys(jwks: &JwkSet) -> HashMap<String, DecodingKey> {
+    jwks.keys
+        .iter()
+        .filter_map(|jwk| {
+            let kid = jwk.common.key_id.clone()?;
+            match DecodingKey::from_jwk(jwk) {
+                Ok(decoding_key) => Some((kid, decoding_key)),
+                Err(e) => {
+                    warn!("Chave '{}' do JWKS ignorada: {}", kid, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+// The mock JWKS endpoint runs on actix-web
+#[cfg(all(test, feature = "actix"))]
+mod tests {
+    use std::{
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use jsonwebtoken::jwk::{Jwk, JwkSet};
+
+    use super::*;
+    use crate::test_support::{bearer, claims, SigningKey};
+
+    /// JWKS endpoint counting how many times it was fetched
+    #[derive(Default)]
+    struct MockJwks {
+        keys: Mutex<Vec<Jwk>>,
+        fetches: Mutex<usize>,
+    }
+
+    impl MockJwks {
+        fn publish(&self, key: &SigningKey) {
+            self.keys.lock().unwrap().push(key.jwk.clone());
+        }
+
+        fn fetches(&self) -> usize {
+            *self.fetches.lock().unwrap()
+        }
+    }
+
+    async fn jwks(state: web::Data<MockJwks>) -> HttpResponse {
+        *state.fetches.lock().unwrap() += 1;
+        HttpResponse::Ok().json(JwkSet {
+            keys: state.keys.lock().unwrap().clone(),
+        })
+    }
+
+    /// Starts the mock JWKS endpoint, returning the oauth manager base URL pointing at it
+    fn start_jwks_server() -> (String, Arc<MockJwks>) {
+        let state = Arc::new(MockJwks::default());
+        let data = web::Data::from(state.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/.well-known/jwks.json", web::get().to(jwks))
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap()
+        .run();
+        actix_web::rt::spawn(server);
+
+        (base_url, state)
+    }
+
+    #[actix_web::test]
+    async fn accepts_a_valid_token() {
+        let (base_url, jwks) = start_jwks_server();
+        let key = SigningKey::generate("key-1");
+        jwks.publish(&key);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        let token = key.sign(&claims("oauth.proxy tasks.read"));
+        let verified = verifier
+            .authorize(Some(&bearer(&token)), &["oauth.proxy".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(verified.sub, "friday-todo-manager");
+        assert!(verified.has_scope("tasks.read"));
+    }
+
+    #[actix_web::test]
+    async fn rejects_expired_tokens() {
+        let (base_url, jwks) = start_jwks_server();
+        let key = SigningKey::generate("key-1");
+        jwks.publish(&key);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        let mut expired = claims("oauth.proxy");
+        expired.iat -= 3600;
+        expired.exp = expired.iat + 300;
+
+        let result = verifier.verify(&key.sign(&expired)).await;
+        assert!(
+            matches!(&result, Err(ServiceAuthError::InvalidToken(reason)) if reason.contains("Expired")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[actix_web::test]
+    async fn rejects_tokens_for_another_audience_or_issuer() {
+        let (base_url, jwks) = start_jwks_server();
+        let key = SigningKey::generate("key-1");
+        jwks.publish(&key);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        let mut other_audience = claims("oauth.proxy");
+        other_audience.aud = "another-audience".to_string();
+        assert!(matches!(
+            verifier.verify(&key.sign(&other_audience)).await,
+            Err(ServiceAuthError::InvalidToken(_))
+        ));
+
+        let mut other_issuer = claims("oauth.proxy");
+        other_issuer.iss = "another-issuer".to_string();
+        assert!(matches!(
+            verifier.verify(&key.sign(&other_issuer)).await,
+            Err(ServiceAuthError::InvalidToken(_))
+        ));
+    }
+
+    #[actix_web::test]
+    async fn rejects_tokens_signed_with_an_unpublished_key() {
+        let (base_url, jwks) = start_jwks_server();
+        let published = SigningKey::generate("key-1");
+        jwks.publish(&published);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        // Mesmo kid da chave publicada, assinado com outra chave
+        let forged = SigningKey::generate("key-1").sign(&claims("oauth.proxy"));
+        assert!(matches!(
+            verifier.verify(&forged).await,
+            Err(ServiceAuthError::InvalidToken(_))
+        ));
+    }
+
+    #[actix_web::test]
+    async fn checks_every_required_scope() {
+        let (base_url, jwks) = start_jwks_server();
+        let key = SigningKey::generate("key-1");
+        jwks.publish(&key);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+        let token = bearer(&key.sign(&claims("oauth.proxy")));
+
+        let result = verifier
+            .authorize(
+                Some(&token),
+                &["oauth.proxy".to_string(), "oauth.admin".to_string()],
+            )
+            .await;
+        assert!(
+            matches!(&result, Err(ServiceAuthError::MissingScope(scope)) if scope == "oauth.admin"),
+            "{:?}",
+            result
+        );
+
+        // Escopos são comparados inteiros, não por prefixo
+        let result = verifier
+            .authorize(Some(&token), &["oauth".to_string()])
+            .await;
+        assert!(matches!(result, Err(ServiceAuthError::MissingScope(_))));
+
+        assert!(matches!(
+            verifier.authorize(None, &[]).await,
+            Err(ServiceAuthError::MissingToken)
+        ));
+        assert!(matches!(
+            verifier.authorize(Some("Basic abc"), &[]).await,
+            Err(ServiceAuthError::MissingToken)
+        ));
+    }
+
+    #[actix_web::test]
+    async fn refreshes_the_jwks_on_an_unknown_kid() {
+        let (base_url, jwks) = start_jwks_server();
+        let first = SigningKey::generate("key-1");
+        jwks.publish(&first);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        verifier.verify(&first.sign(&claims(""))).await.unwrap();
+        verifier.verify(&first.sign(&claims(""))).await.unwrap();
+        assert_eq!(jwks.fetches(), 1, "known keys are served from the cache");
+
+        // Rotação: a nova chave é publicada depois que o verificador já buscou o JWKS
+        let rotated = SigningKey::generate("key-2");
+        jwks.publish(&rotated);
+
+        // Dentro do intervalo mínimo o JWKS não é buscado de novo
+        assert!(matches!(
+            verifier.verify(&rotated.sign(&claims(""))).await,
+            Err(ServiceAuthError::InvalidToken(_))
+        ));
+        assert_eq!(jwks.fetches(), 1);
+
+        *verifier.last_refresh.lock().unwrap() =
+            Instant::now().checked_sub(JWKS_REFRESH_INTERVAL + Duration::from_secs(1));
+
+        verifier.verify(&rotated.sign(&claims(""))).await.unwrap();
+        assert_eq!(jwks.fetches(), 2);
+        verifier.verify(&first.sign(&claims(""))).await.unwrap();
+        assert_eq!(jwks.fetches(), 2);
+    }
+
+    #[actix_web::test]
+    async fn verifies_with_fixed_keys_without_fetching() {
+        let key = SigningKey::generate("key-1");
+        let verifier = ServiceTokenVerifier::with_jwks(&JwkSet {
+            keys: vec![key.jwk.clone()],
+        });
+
+        verifier.verify(&key.sign(&claims(""))).await.unwrap();
+
+        let unknown = SigningKey::generate("key-2").sign(&claims(""));
+        assert!(matches!(
+            verifier.verify(&unknown).await,
+            Err(ServiceAuthError::InvalidToken(_))
+        ));
+    }
+
+    #[actix_web::test]
+    async fn reports_an_unreachable_jwks() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let verifier = ServiceTokenVerifier::new(&base_url);
+
+        let token = SigningKey::generate("key-1").sign(&claims(""));
+        assert!(matches!(
+            verifier.verify(&token).await,
+            Err(ServiceAuthError::KeysUnavailable(_))
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! Signing keys and claims shared by the verifier and middleware tests
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    encode,
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, OctetKeyPairParameters,
+        OctetKeyPairType,
+    },
+    Algorithm, EncodingKey, Header,
+};
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use uuid::Uuid;
+
+use crate::{ServiceClaims, SERVICE_TOKEN_AUDIENCE, SERVICE_TOKEN_ISSUER};
+
+pub(crate) struct SigningKey {
+    kid: String,
+    encoding_key: EncodingKey,
+    pub(crate) jwk: Jwk,
+}
+
+impl SigningKey {
+    pub(crate) fn generate(kid: &str) -> Self {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        Self {
+            kid: kid.to_string(),
+            encoding_key: EncodingKey::from_ed_der(pkcs8.as_ref()),
+            jwk: Jwk {
+                common: CommonParameters {
+                    key_id: Some(kid.to_string()),
+                    ..Default::default()
+                },
+                algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+                }),
+            },
+        }
+    }
+
+    pub(crate) fn sign(&self, claims: &ServiceClaims) -> String {
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.kid.clone());
+        encode(&header, claims, &self.encoding_key).unwrap()
+    }
+}
+
+pub(crate) fn claims(scope: &str) -> ServiceClaims {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    ServiceClaims {
+        iss: SERVICE_TOKEN_ISSUER.to_string(),
+        sub: "friday-todo-manager".to_string(),
+        aud: SERVICE_TOKEN_AUDIENCE.to_string(),
+        exp: now + 300,
+        iat: now,
+        jti: Uuid::nil(),
+        client_id: Uuid::nil(),
+        scope: scope.to_string(),
+    }
+}
+
+pub(crate) fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
@@ -0,0 +1,15 @@
+-- DROP FUNCTION fn_get_all_todo_lists;
+CREATE OR REPLACE FUNCTION fn_get_all_todo_lists() RETURNS TABLE (
+    id_todo_list UUID,
+    display_name TEXT,
+    created_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_todo_lists.id_todo_list,
+    tb_todo_lists.display_name,
+    tb_todo_lists.created_at,
+    tb_todo_lists.updated_at
+FROM tb_todo_lists
+ORDER BY tb_todo_lists.created_at;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,21 @@
+-- DROP FUNCTION fn_get_todo_checklist_item_by_id;
+CREATE OR REPLACE FUNCTION fn_get_todo_checklist_item_by_id(
+    p_id_todo_task UUID,
+    p_id_todo_checklist_item UUID
+) RETURNS TABLE (
+    id_todo_checklist_item UUID,
+    display_name TEXT,
+    is_checked BOOLEAN,
+    created_at TIMESTAMP WITH TIME ZONE,
+    checked_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_todo_checklist_items.id_todo_checklist_item,
+    tb_todo_checklist_items.display_name,
+    tb_todo_checklist_items.is_checked,
+    tb_todo_checklist_items.created_at,
+    tb_todo_checklist_items.checked_at
+FROM tb_todo_checklist_items
+WHERE tb_todo_checklist_items.id_todo_task = p_id_todo_task
+    AND tb_todo_checklist_items.id_todo_checklist_item = p_id_todo_checklist_item;
+END;
+$$ LANGUAGE plpgsql;
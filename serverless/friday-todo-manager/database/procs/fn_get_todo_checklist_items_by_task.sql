@@ -0,0 +1,18 @@
+-- DROP FUNCTION fn_get_todo_checklist_items_by_task;
+CREATE OR REPLACE FUNCTION fn_get_todo_checklist_items_by_task(p_id_todo_task UUID) RETURNS TABLE (
+    id_todo_checklist_item UUID,
+    display_name TEXT,
+    is_checked BOOLEAN,
+    created_at TIMESTAMP WITH TIME ZONE,
+    checked_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_todo_checklist_items.id_todo_checklist_item,
+    tb_todo_checklist_items.display_name,
+    tb_todo_checklist_items.is_checked,
+    tb_todo_checklist_items.created_at,
+    tb_todo_checklist_items.checked_at
+FROM tb_todo_checklist_items
+WHERE tb_todo_checklist_items.id_todo_task = p_id_todo_task
+ORDER BY tb_todo_checklist_items.created_at;
+END;
+$$ LANGUAGE plpgsql;
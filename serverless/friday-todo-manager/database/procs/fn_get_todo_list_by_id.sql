@@ -0,0 +1,15 @@
+-- DROP FUNCTION fn_get_todo_list_by_id;
+CREATE OR REPLACE FUNCTION fn_get_todo_list_by_id(p_id_todo_list UUID) RETURNS TABLE (
+    id_todo_list UUID,
+    display_name TEXT,
+    created_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_todo_lists.id_todo_list,
+    tb_todo_lists.display_name,
+    tb_todo_lists.created_at,
+    tb_todo_lists.updated_at
+FROM tb_todo_lists
+WHERE tb_todo_lists.id_todo_list = p_id_todo_list;
+END;
+$$ LANGUAGE plpgsql;
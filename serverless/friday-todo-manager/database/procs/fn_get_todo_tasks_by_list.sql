@@ -0,0 +1,33 @@
+-- The returned columns changed: CREATE OR REPLACE cannot alter them
+DROP FUNCTION IF EXISTS fn_get_todo_tasks_by_list(UUID);
+CREATE OR REPLACE FUNCTION fn_get_todo_tasks_by_list(p_id_todo_list UUID) RETURNS TABLE (
+    id_todo_task UUID,
+    title TEXT,
+    body TEXT,
+    status TEXT,
+    importance TEXT,
+    is_reminder_on BOOLEAN,
+    reminder_date_time TIMESTAMP WITH TIME ZONE,
+    due_date_time TIMESTAMP WITH TIME ZONE,
+    recurrence JSONB,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE
+) AS $$ BEGIN RETURN QUERY
+SELECT tb_todo_tasks.id_todo_task,
+    tb_todo_tasks.title,
+    tb_todo_tasks.body,
+    tb_todo_tasks.status,
+    tb_todo_tasks.importance,
+    tb_todo_tasks.is_reminder_on,
+    tb_todo_tasks.reminder_date_time,
+    tb_todo_tasks.due_date_time,
+    tb_todo_tasks.recurrence,
+    tb_todo_tasks.completed_at,
+    tb_todo_tasks.created_at,
+    tb_todo_tasks.updated_at
+FROM tb_todo_tasks
+WHERE tb_todo_tasks.id_todo_list = p_id_todo_list
+ORDER BY tb_todo_tasks.created_at;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,8 @@
+-- DROP PROCEDURE pr_del_todo_checklist_item
+CREATE OR REPLACE PROCEDURE pr_del_todo_checklist_item(
+        IN p_id_todo_checklist_item UUID
+    ) LANGUAGE plpgsql AS $$ BEGIN
+DELETE FROM tb_todo_checklist_items
+WHERE id_todo_checklist_item = p_id_todo_checklist_item;
+END;
+$$;
@@ -0,0 +1,8 @@
+-- DROP PROCEDURE pr_del_todo_list
+CREATE OR REPLACE PROCEDURE pr_del_todo_list(
+        IN p_id_todo_list UUID
+    ) LANGUAGE plpgsql AS $$ BEGIN
+DELETE FROM tb_todo_lists
+WHERE id_todo_list = p_id_todo_list;
+END;
+$$;
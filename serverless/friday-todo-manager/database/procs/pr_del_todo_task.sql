@@ -0,0 +1,8 @@
+-- DROP PROCEDURE pr_del_todo_task
+CREATE OR REPLACE PROCEDURE pr_del_todo_task(
+        IN p_id_todo_task UUID
+    ) LANGUAGE plpgsql AS $$ BEGIN
+DELETE FROM tb_todo_tasks
+WHERE id_todo_task = p_id_todo_task;
+END;
+$$;
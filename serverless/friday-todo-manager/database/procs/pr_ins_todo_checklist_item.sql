@@ -0,0 +1,23 @@
+-- DROP PROCEDURE pr_ins_todo_checklist_item
+CREATE OR REPLACE PROCEDURE pr_ins_todo_checklist_item(
+        IN p_id_todo_checklist_item UUID,
+        IN p_id_todo_task UUID,
+        IN p_display_name TEXT,
+        IN p_is_checked BOOLEAN
+    ) LANGUAGE plpgsql AS $$ BEGIN
+INSERT INTO tb_todo_checklist_items (
+        id_todo_checklist_item,
+        id_todo_task,
+        display_name,
+        is_checked,
+        checked_at
+    )
+VALUES (
+        p_id_todo_checklist_item,
+        p_id_todo_task,
+        p_display_name,
+        p_is_checked,
+        CASE WHEN p_is_checked THEN NOW() END
+    );
+END;
+$$;
@@ -0,0 +1,9 @@
+-- DROP PROCEDURE pr_ins_todo_list
+CREATE OR REPLACE PROCEDURE pr_ins_todo_list(
+        IN p_id_todo_list UUID,
+        IN p_display_name TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+INSERT INTO tb_todo_lists (id_todo_list, display_name)
+VALUES (p_id_todo_list, p_display_name);
+END;
+$$;
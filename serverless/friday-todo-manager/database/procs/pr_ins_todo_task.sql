@@ -0,0 +1,36 @@
+-- DROP PROCEDURE pr_ins_todo_task
+CREATE OR REPLACE PROCEDURE pr_ins_todo_task(
+        IN p_id_todo_task UUID,
+        IN p_id_todo_list UUID,
+        IN p_title TEXT,
+        IN p_body TEXT,
+        IN p_importance TEXT,
+        IN p_is_reminder_on BOOLEAN,
+        IN p_reminder_date_time TIMESTAMP WITH TIME ZONE,
+        IN p_due_date_time TIMESTAMP WITH TIME ZONE,
+        IN p_recurrence JSONB
+    ) LANGUAGE plpgsql AS $$ BEGIN
+INSERT INTO tb_todo_tasks (
+        id_todo_task,
+        id_todo_list,
+        title,
+        body,
+        importance,
+        is_reminder_on,
+        reminder_date_time,
+        due_date_time,
+        recurrence
+    )
+VALUES (
+        p_id_todo_task,
+        p_id_todo_list,
+        p_title,
+        p_body,
+        p_importance,
+        p_is_reminder_on,
+        p_reminder_date_time,
+        p_due_date_time,
+        p_recurrence
+    );
+END;
+$$;
@@ -0,0 +1,18 @@
+-- DROP PROCEDURE pr_upd_todo_checklist_item
+-- NULL parameters keep the current value; checked_at follows is_checked
+CREATE OR REPLACE PROCEDURE pr_upd_todo_checklist_item(
+        IN p_id_todo_checklist_item UUID,
+        IN p_display_name TEXT,
+        IN p_is_checked BOOLEAN
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_todo_checklist_items
+SET display_name = COALESCE(p_display_name, display_name),
+    is_checked = COALESCE(p_is_checked, is_checked),
+    checked_at = CASE
+        WHEN p_is_checked IS NULL THEN checked_at
+        WHEN p_is_checked THEN COALESCE(checked_at, NOW())
+        ELSE NULL
+    END
+WHERE id_todo_checklist_item = p_id_todo_checklist_item;
+END;
+$$;
@@ -0,0 +1,11 @@
+-- DROP PROCEDURE pr_upd_todo_list
+CREATE OR REPLACE PROCEDURE pr_upd_todo_list(
+        IN p_id_todo_list UUID,
+        IN p_display_name TEXT
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_todo_lists
+SET display_name = p_display_name,
+    updated_at = NOW()
+WHERE id_todo_list = p_id_todo_list;
+END;
+$$;
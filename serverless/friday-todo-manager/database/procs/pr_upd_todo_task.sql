@@ -0,0 +1,32 @@
+-- DROP PROCEDURE pr_upd_todo_task
+-- NULL parameters keep the current value, as fields missing from a PATCH
+CREATE OR REPLACE PROCEDURE pr_upd_todo_task(
+        IN p_id_todo_task UUID,
+        IN p_title TEXT,
+        IN p_body TEXT,
+        IN p_status TEXT,
+        IN p_importance TEXT,
+        IN p_is_reminder_on BOOLEAN,
+        IN p_reminder_date_time TIMESTAMP WITH TIME ZONE,
+        IN p_due_date_time TIMESTAMP WITH TIME ZONE,
+        IN p_recurrence JSONB
+    ) LANGUAGE plpgsql AS $$ BEGIN
+UPDATE tb_todo_tasks
+SET title = COALESCE(p_title, title),
+    body = COALESCE(p_body, body),
+    status = COALESCE(p_status, status),
+    completed_at = CASE
+        WHEN p_status IS NULL THEN completed_at
+        WHEN p_status <> 'completed' THEN NULL
+        WHEN status = 'completed' THEN completed_at
+        ELSE NOW()
+    END,
+    importance = COALESCE(p_importance, importance),
+    is_reminder_on = COALESCE(p_is_reminder_on, is_reminder_on),
+    reminder_date_time = COALESCE(p_reminder_date_time, reminder_date_time),
+    due_date_time = COALESCE(p_due_date_time, due_date_time),
+    recurrence = COALESCE(p_recurrence, recurrence),
+    updated_at = NOW()
+WHERE id_todo_task = p_id_todo_task;
+END;
+$$;
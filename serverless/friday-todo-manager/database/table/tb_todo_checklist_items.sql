@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS tb_todo_checklist_items (
+    id_todo_checklist_item UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    id_todo_task UUID NOT NULL REFERENCES tb_todo_tasks(id_todo_task) ON DELETE CASCADE,
+    display_name TEXT NOT NULL,
+    is_checked BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    checked_at TIMESTAMP WITH TIME ZONE NULL
+);
+
+CREATE INDEX IF NOT EXISTS ix_todo_checklist_items_id_todo_task ON tb_todo_checklist_items(id_todo_task);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS tb_todo_tasks (
+    id_todo_task UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    id_todo_list UUID NOT NULL REFERENCES tb_todo_lists(id_todo_list) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    body TEXT NULL,
+    status TEXT NOT NULL DEFAULT 'notStarted',
+    importance TEXT NOT NULL DEFAULT 'normal',
+    is_reminder_on BOOLEAN NOT NULL DEFAULT FALSE,
+    reminder_date_time TIMESTAMP WITH TIME ZONE NULL,
+    due_date_time TIMESTAMP WITH TIME ZONE NULL,
+    recurrence JSONB NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE tb_todo_tasks ADD COLUMN IF NOT EXISTS completed_at TIMESTAMP WITH TIME ZONE NULL;
+
+CREATE INDEX IF NOT EXISTS ix_todo_tasks_id_todo_list ON tb_todo_tasks(id_todo_list);
@@ -0,0 +1,4 @@
+pub mod redis_client;
+pub mod task_cache;
+pub mod task_cache_data;
+pub mod task_cache_logic;
@@ -0,0 +1,47 @@
+use std::error::Error;
+
+use redis::AsyncCommands;
+
+use crate::ENV_CONFIG;
+
+/// Whether `REDIS_URL` is configured; without it the cache is disabled
+pub fn is_enabled() -> bool {
+    ENV_CONFIG.redis_url.is_some()
+}
+
+/// URL format: `{redis|rediss}://[<username>][:<password>@]<hostname>[:port][/<db>]`
+fn get_redis_client() -> Result<redis::Client, Box<dyn Error>> {
+    let redis_url = ENV_CONFIG
+        .redis_url
+        .as_ref()
+        .ok_or("Redis is not configured")?;
+    let client = redis::Client::open(redis_url.as_str())?;
+    Ok(client)
+}
+
+pub async fn get_value(key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let client = get_redis_client()?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+
+    let result: Option<String> = conn.get(key).await?;
+
+    Ok(result)
+}
+
+pub async fn set_value(key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let client = get_redis_client()?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+
+    conn.set::<_, _, ()>(key, value).await?;
+
+    Ok(())
+}
+
+pub async fn delete_key(key: &str) -> Result<(), Box<dyn Error>> {
+    let client = get_redis_client()?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+
+    conn.del::<_, ()>(key).await?;
+
+    Ok(())
+}
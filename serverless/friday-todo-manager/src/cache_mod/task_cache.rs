@@ -0,0 +1,34 @@
+use crate::todo_mod::task::Task;
+use crate::todo_mod::todo_list::TodoList;
+use chrono::{DateTime, Duration, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+/// Todo lists as of the last delta round
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CachedTodoLists {
+    /// Graph `@odata.deltaLink` the next round resumes from
+    pub delta_link: String,
+    pub synced_at: DateTime<Utc>,
+    pub todo_lists: Vec<TodoList>,
+}
+
+/// Tasks of one list as of the last delta round
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CachedTasks {
+    /// Graph `@odata.deltaLink` the next round resumes from
+    pub delta_link: String,
+    pub synced_at: DateTime<Utc>,
+    pub tasks: Vec<Task>,
+}
+
+impl CachedTodoLists {
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        Utc::now() - self.synced_at <= max_age
+    }
+}
+
+impl CachedTasks {
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        Utc::now() - self.synced_at <= max_age
+    }
+}
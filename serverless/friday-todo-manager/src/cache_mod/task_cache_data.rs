@@ -0,0 +1,61 @@
+use crate::cache_mod::redis_client;
+use crate::cache_mod::task_cache::{CachedTasks, CachedTodoLists};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::warn;
+
+const KEY_PREFIX: &str = "friday-todo-manager";
+
+fn todo_lists_key() -> String {
+    format!("{}:lists", KEY_PREFIX)
+}
+
+fn tasks_key(list_id: &str) -> String {
+    format!("{}:lists:{}:tasks", KEY_PREFIX, list_id)
+}
+
+pub async fn get_todo_lists() -> Result<Option<CachedTodoLists>, Box<dyn std::error::Error>> {
+    get_json(&todo_lists_key()).await
+}
+
+pub async fn set_todo_lists(
+    cached_todo_lists: &CachedTodoLists,
+) -> Result<(), Box<dyn std::error::Error>> {
+    set_json(&todo_lists_key(), cached_todo_lists).await
+}
+
+pub async fn get_tasks(list_id: &str) -> Result<Option<CachedTasks>, Box<dyn std::error::Error>> {
+    get_json(&tasks_key(list_id)).await
+}
+
+pub async fn set_tasks(
+    list_id: &str,
+    cached_tasks: &CachedTasks,
+) -> Result<(), Box<dyn std::error::Error>> {
+    set_json(&tasks_key(list_id), cached_tasks).await
+}
+
+pub async fn delete_tasks(list_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    redis_client::delete_key(&tasks_key(list_id)).await
+}
+
+/// Entries that no longer deserialize, e.g. written by an older version, count as missing
+/// so that they are rebuilt by a full sync
+async fn get_json<T: DeserializeOwned>(key: &str) -> Result<Option<T>, Box<dyn std::error::Error>> {
+    let Some(value) = redis_client::get_value(key).await? else {
+        return Ok(None);
+    };
+
+    match serde_json::from_str(&value) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(e) => {
+            warn!("Data layer: Discarding unreadable entry {}: {}", key, e);
+            Ok(None)
+        }
+    }
+}
+
+async fn set_json<T: Serialize>(key: &str, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let value = serde_json::to_string(value)?;
+    redis_client::set_value(key, &value).await
+}
@@ -0,0 +1,493 @@
+use crate::cache_mod::redis_client;
+use crate::cache_mod::task_cache::{CachedTasks, CachedTodoLists};
+use crate::cache_mod::task_cache_data;
+use crate::microsoft_graph_mod::delta_microsoft_graph_api::{
+    DeltaChange, DeltaChanges, DeltaMicrosoftGraphApi, DELTA_TOKEN_EXPIRED,
+};
+use crate::microsoft_graph_mod::task_response::TaskResponse;
+use crate::microsoft_graph_mod::todo_list_response::TodoListResponse;
+use crate::oauth_mod::oauth_api::OAuthApi;
+use crate::todo_mod::task::Task;
+use crate::todo_mod::todo_list::TodoList;
+use crate::ENV_CONFIG;
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+/// Whether reads may be served from the cache
+pub fn is_enabled() -> bool {
+    redis_client::is_enabled()
+}
+
+/// Oldest sync a read is served from; older entries are brought up to date first
+fn max_age() -> Duration {
+    Duration::seconds(ENV_CONFIG.cache_max_age_seconds as i64)
+}
+
+/// Todo lists from the cache, synced first when older than the freshness bound. `None`
+/// when the cache is disabled or cannot be brought up to date, so that the caller falls
+/// back to Microsoft Graph.
+pub async fn get_todo_lists(access_token: &str) -> Option<Vec<TodoList>> {
+    if !is_enabled() {
+        return None;
+    }
+
+    match task_cache_data::get_todo_lists().await {
+        Ok(Some(cached)) if cached.is_fresh(max_age()) => {
+            info!("Logic layer: Serving todo lists from the cache");
+            return Some(cached.todo_lists);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            warn!(
+                "Logic layer: Failed to read todo lists from the cache: {}",
+                e
+            );
+            return None;
+        }
+    }
+
+    match sync_todo_lists(access_token).await {
+        Ok(cached) => Some(cached.todo_lists),
+        Err(e) => {
+            warn!(
+                "Logic layer: Failed to sync todo lists into the cache: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Tasks of a list from the cache, synced first when older than the freshness bound.
+/// `None` when the cache is disabled or cannot be brought up to date.
+pub async fn get_tasks(list_id: &str, access_token: &str) -> Option<Vec<Task>> {
+    if !is_enabled() {
+        return None;
+    }
+
+    match task_cache_data::get_tasks(list_id).await {
+        Ok(Some(cached)) if cached.is_fresh(max_age()) => {
+            info!(
+                "Logic layer: Serving tasks of list {} from the cache",
+                list_id
+            );
+            return Some(cached.tasks);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            warn!("Logic layer: Failed to read tasks from the cache: {}", e);
+            return None;
+        }
+    }
+
+    match sync_tasks(list_id, access_token).await {
+        Ok(cached) => Some(cached.tasks),
+        Err(e) => {
+            warn!(
+                "Logic layer: Failed to sync tasks of list {} into the cache: {}",
+                list_id, e
+            );
+            None
+        }
+    }
+}
+
+/// Brings the cached todo lists up to date with a delta round. Task caches of lists that
+/// were removed are dropped.
+pub async fn sync_todo_lists(access_token: &str) -> Result<CachedTodoLists, String> {
+    let started_at = Utc::now();
+    let graph_client = DeltaMicrosoftGraphApi::new();
+    let cached = task_cache_data::get_todo_lists()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (mut todo_lists, delta_url) = match cached {
+        Some(cached) => (cached.todo_lists, cached.delta_link),
+        None => (Vec::new(), graph_client.todo_lists_delta_url()),
+    };
+
+    let delta_changes = match get_changes(&graph_client, &delta_url, access_token).await? {
+        Some(delta_changes) => delta_changes,
+        None => {
+            todo_lists.clear();
+            get_changes(
+                &graph_client,
+                &graph_client.todo_lists_delta_url(),
+                access_token,
+            )
+            .await?
+            .ok_or("Delta token expired on a full sync")?
+        }
+    };
+
+    let removed_list_ids = apply_changes(
+        &mut todo_lists,
+        delta_changes.changes,
+        |todo_list| &todo_list.id,
+        |value| {
+            serde_json::from_value::<TodoListResponse>(value)
+                .map_err(|e| format!("Invalid todo list in delta: {}", e))
+                .and_then(TodoList::try_from)
+        },
+    )?;
+
+    for list_id in &removed_list_ids {
+        if let Err(e) = task_cache_data::delete_tasks(list_id).await {
+            warn!(
+                "Logic layer: Failed to drop cached tasks of removed list {}: {}",
+                list_id, e
+            );
+        }
+    }
+
+    let cached = CachedTodoLists {
+        delta_link: delta_changes.delta_link,
+        synced_at: started_at,
+        todo_lists,
+    };
+    task_cache_data::set_todo_lists(&cached)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        "Logic layer: Synced {} todo lists into the cache",
+        cached.todo_lists.len()
+    );
+    Ok(cached)
+}
+
+/// Brings the cached tasks of a list up to date with a delta round
+pub async fn sync_tasks(list_id: &str, access_token: &str) -> Result<CachedTasks, String> {
+    let started_at = Utc::now();
+    let graph_client = DeltaMicrosoftGraphApi::new();
+    let cached = task_cache_data::get_tasks(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (mut tasks, delta_url) = match cached {
+        Some(cached) => (cached.tasks, cached.delta_link),
+        None => (Vec::new(), graph_client.tasks_delta_url(list_id)),
+    };
+
+    let delta_changes = match get_changes(&graph_client, &delta_url, access_token).await? {
+        Some(delta_changes) => delta_changes,
+        None => {
+            tasks.clear();
+            get_changes(
+                &graph_client,
+                &graph_client.tasks_delta_url(list_id),
+                access_token,
+            )
+            .await?
+            .ok_or("Delta token expired on a full sync")?
+        }
+    };
+
+    apply_changes(
+        &mut tasks,
+        delta_changes.changes,
+        |task| &task.id,
+        |value| {
+            serde_json::from_value::<TaskResponse>(value)
+                .map_err(|e| format!("Invalid task in delta: {}", e))
+                .and_then(Task::try_from)
+        },
+    )?;
+
+    let cached = CachedTasks {
+        delta_link: delta_changes.delta_link,
+        synced_at: started_at,
+        tasks,
+    };
+    task_cache_data::set_tasks(list_id, &cached)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        "Logic layer: Synced {} tasks of list {} into the cache",
+        cached.tasks.len(),
+        list_id
+    );
+    Ok(cached)
+}
+
+/// Writes a created or updated task through to the cached list, if it is cached
+pub async fn store_task(list_id: &str, task: &Task) {
+    update_cached_tasks(list_id, |tasks| upsert(tasks, task.clone(), |t| &t.id)).await;
+}
+
+/// Removes a deleted task from the cached list, if it is cached
+pub async fn remove_task(list_id: &str, task_id: &str) {
+    update_cached_tasks(list_id, |tasks| tasks.retain(|task| task.id != task_id)).await;
+}
+
+/// Writes a created or updated todo list through to the cache, if it is cached
+pub async fn store_todo_list(todo_list: &TodoList) {
+    update_cached_todo_lists(|todo_lists| upsert(todo_lists, todo_list.clone(), |l| &l.id)).await;
+}
+
+/// Removes a deleted todo list and its tasks from the cache
+pub async fn remove_todo_list(list_id: &str) {
+    update_cached_todo_lists(|todo_lists| todo_lists.retain(|todo_list| todo_list.id != list_id))
+        .await;
+
+    if is_enabled() {
+        if let Err(e) = task_cache_data::delete_tasks(list_id).await {
+            warn!(
+                "Logic layer: Failed to drop cached tasks of list {}: {}",
+                list_id, e
+            );
+        }
+    }
+}
+
+/// Keeps the cache fresh in the background so that reads rarely wait for a delta round
+pub fn spawn_background_refresh() {
+    if !is_enabled() {
+        info!("Logic layer: REDIS_URL not set, task cache disabled");
+        return;
+    }
+
+    let refresh_interval =
+        std::time::Duration::from_secs(ENV_CONFIG.cache_refresh_interval_seconds.max(1));
+
+    actix_web::rt::spawn(async move {
+        let mut interval = tokio::time::interval(refresh_interval);
+        loop {
+            interval.tick().await;
+            refresh_cache().await;
+        }
+    });
+}
+
+async fn refresh_cache() {
+    let oauth_client = OAuthApi::new();
+    let access_token = match oauth_client.generate_access_token().await {
+        Ok(token) => token,
+        Err(e) => {
+            error!(
+                "Logic layer: Failed to get access token to refresh the cache: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    let cached_todo_lists = match sync_todo_lists(&access_token).await {
+        Ok(cached) => cached,
+        Err(e) => {
+            warn!("Logic layer: Background sync of todo lists failed: {}", e);
+            return;
+        }
+    };
+
+    for todo_list in &cached_todo_lists.todo_lists {
+        if let Err(e) = sync_tasks(&todo_list.id, &access_token).await {
+            warn!(
+                "Logic layer: Background sync of tasks of list {} failed: {}",
+                todo_list.id, e
+            );
+        }
+    }
+}
+
+/// Runs a delta round; `None` when the delta link expired and a full sync is needed
+async fn get_changes(
+    graph_client: &DeltaMicrosoftGraphApi,
+    delta_url: &str,
+    access_token: &str,
+) -> Result<Option<DeltaChanges>, String> {
+    let response = graph_client
+        .get_changes(delta_url, access_token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if response.errors.iter().any(|e| e == DELTA_TOKEN_EXPIRED) {
+        return Ok(None);
+    }
+
+    response
+        .data
+        .map(Some)
+        .ok_or_else(|| response.errors.join(", "))
+}
+
+/// Applies the changes in order and returns the ids of the removed items. Every item is
+/// parsed first, so that an invalid one leaves `items` as it was and aborts the round.
+fn apply_changes<T>(
+    items: &mut Vec<T>,
+    changes: Vec<DeltaChange>,
+    id_of: fn(&T) -> &String,
+    parse: impl Fn(Value) -> Result<T, String>,
+) -> Result<Vec<String>, String> {
+    let changes = changes
+        .into_iter()
+        .map(|change| match change {
+            DeltaChange::Upserted(value) => parse(value).map(ParsedChange::Upserted),
+            DeltaChange::Removed(id) => Ok(ParsedChange::Removed(id)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut removed_ids = Vec::new();
+
+    for change in changes {
+        match change {
+            ParsedChange::Upserted(item) => upsert(items, item, id_of),
+            ParsedChange::Removed(id) => {
+                items.retain(|item| id_of(item) != &id);
+                removed_ids.push(id);
+            }
+        }
+    }
+
+    Ok(removed_ids)
+}
+
+enum ParsedChange<T> {
+    Upserted(T),
+    Removed(String),
+}
+
+fn upsert<T>(items: &mut Vec<T>, item: T, id_of: fn(&T) -> &String) {
+    match items
+        .iter_mut()
+        .find(|existing| id_of(existing) == id_of(&item))
+    {
+        Some(existing) => *existing = item,
+        None => items.push(item),
+    }
+}
+
+async fn update_cached_tasks(list_id: &str, update: impl FnOnce(&mut Vec<Task>)) {
+    if !is_enabled() {
+        return;
+    }
+
+    let result = match task_cache_data::get_tasks(list_id).await {
+        Ok(Some(mut cached)) => {
+            update(&mut cached.tasks);
+            task_cache_data::set_tasks(list_id, &cached).await
+        }
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        // The next delta round brings the cache up to date anyway
+        warn!(
+            "Logic layer: Failed to write task change of list {} to the cache: {}",
+            list_id, e
+        );
+    }
+}
+
+async fn update_cached_todo_lists(update: impl FnOnce(&mut Vec<TodoList>)) {
+    if !is_enabled() {
+        return;
+    }
+
+    let result = match task_cache_data::get_todo_lists().await {
+        Ok(Some(mut cached)) => {
+            update(&mut cached.todo_lists);
+            task_cache_data::set_todo_lists(&cached).await
+        }
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        warn!(
+            "Logic layer: Failed to write todo list change to the cache: {}",
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Item {
+        id: String,
+        title: String,
+    }
+
+    fn item(id: &str, title: &str) -> Item {
+        Item {
+            id: id.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    fn id_of(item: &Item) -> &String {
+        &item.id
+    }
+
+    fn parse(value: Value) -> Result<Item, String> {
+        match (value["id"].as_str(), value["title"].as_str()) {
+            (Some(id), Some(title)) => Ok(item(id, title)),
+            _ => Err(format!("Invalid item in delta: {}", value)),
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_an_existing_item_and_appends_a_new_one() {
+        let mut items = vec![item("1", "Milk"), item("2", "Bread")];
+
+        upsert(&mut items, item("1", "Oat milk"), id_of);
+        upsert(&mut items, item("3", "Eggs"), id_of);
+
+        assert_eq!(
+            items,
+            vec![item("1", "Oat milk"), item("2", "Bread"), item("3", "Eggs")]
+        );
+    }
+
+    #[test]
+    fn removed_items_are_dropped_and_reported() {
+        let mut items = vec![item("1", "Milk"), item("2", "Bread")];
+        let changes = vec![
+            DeltaChange::Upserted(json!({ "id": "3", "title": "Eggs" })),
+            DeltaChange::Removed("1".to_string()),
+            // Removed before it was ever cached
+            DeltaChange::Removed("4".to_string()),
+        ];
+
+        let removed_ids = apply_changes(&mut items, changes, id_of, parse).unwrap();
+
+        assert_eq!(items, vec![item("2", "Bread"), item("3", "Eggs")]);
+        assert_eq!(removed_ids, vec!["1", "4"]);
+    }
+
+    #[test]
+    fn changes_are_applied_in_order() {
+        let mut items = vec![item("1", "Milk")];
+        let changes = vec![
+            DeltaChange::Removed("1".to_string()),
+            DeltaChange::Upserted(json!({ "id": "1", "title": "Milk again" })),
+        ];
+
+        apply_changes(&mut items, changes, id_of, parse).unwrap();
+
+        assert_eq!(items, vec![item("1", "Milk again")]);
+    }
+
+    #[test]
+    fn an_invalid_item_aborts_the_round_without_touching_the_cached_items() {
+        let cached = vec![item("1", "Milk"), item("2", "Bread")];
+        let mut items = cached.clone();
+        let changes = vec![
+            DeltaChange::Removed("1".to_string()),
+            DeltaChange::Upserted(json!({ "id": "2", "title": "Rye bread" })),
+            DeltaChange::Upserted(json!({ "id": "3" })),
+        ];
+
+        let result = apply_changes(&mut items, changes, id_of, parse);
+
+        assert!(result.unwrap_err().starts_with("Invalid item in delta"));
+        assert_eq!(items, cached);
+    }
+}
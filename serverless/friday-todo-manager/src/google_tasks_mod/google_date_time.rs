@@ -0,0 +1,76 @@
+use crate::microsoft_graph_mod::graph_date_time::user_time_zone;
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, TimeZone, Utc};
+
+/// Parses a Google Tasks timestamp such as `updated` or `completed`
+pub fn parse_google_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .map_err(|_| format!("Invalid timestamp: {}", value))
+}
+
+/// Parses a Google Tasks `due`, whose time is always midnight UTC, as the start of that day
+/// in the user's time zone
+pub fn parse_google_due_date(value: &str) -> Result<DateTime<Utc>, String> {
+    let date = value
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Invalid due date: {}", value))?;
+
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| format!("Invalid due date: {}", value))?;
+
+    let time_zone = user_time_zone();
+    match time_zone.from_local_datetime(&midnight) {
+        LocalResult::Single(parsed) => Ok(parsed.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, _) => Ok(earliest.with_timezone(&Utc)),
+        // Midnight skipped when clocks go forward: the day starts an hour later
+        LocalResult::None => time_zone
+            .from_local_datetime(&(midnight + Duration::hours(1)))
+            .earliest()
+            .map(|parsed| parsed.with_timezone(&Utc))
+            .ok_or_else(|| format!("Invalid due date: {}", value)),
+    }
+}
+
+/// Formats a due date as Google Tasks expects it: the user's calendar day at midnight UTC
+pub fn to_google_due_date(date_time: &DateTime<Utc>) -> String {
+    date_time
+        .with_timezone(&user_time_zone())
+        .date_naive()
+        .format("%Y-%m-%dT00:00:00.000Z")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_dates_round_trip_through_the_calendar_day() {
+        let late_evening = user_time_zone()
+            .with_ymd_and_hms(2024, 5, 10, 23, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let due = to_google_due_date(&late_evening);
+        assert_eq!(due, "2024-05-10T00:00:00.000Z");
+
+        let start_of_day = user_time_zone()
+            .with_ymd_and_hms(2024, 5, 10, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_google_due_date(&due), Ok(start_of_day));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(parse_google_due_date("10/05/2024").is_err());
+        assert!(parse_google_due_date("2024-02-30T00:00:00.000Z").is_err());
+        assert!(parse_google_timestamp("2024-05-10").is_err());
+        assert_eq!(
+            parse_google_timestamp("2024-05-10T09:00:00.000Z"),
+            Ok("2024-05-10T09:00:00Z".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoogleTaskListResponse {
+    pub id: String,
+    pub title: String,
+    pub updated: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoogleTaskListsResponse {
+    #[serde(default)]
+    pub items: Vec<GoogleTaskListResponse>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
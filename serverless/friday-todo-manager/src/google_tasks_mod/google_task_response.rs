@@ -0,0 +1,24 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Google Tasks task. Subtasks are tasks with a `parent` and are exposed as checklist items.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoogleTaskResponse {
+    pub id: String,
+    pub title: Option<String>,
+    pub notes: Option<String>,
+    /// `needsAction` or `completed`
+    pub status: String,
+    /// Due date; Google keeps only the date, sent as midnight UTC
+    pub due: Option<String>,
+    pub completed: Option<String>,
+    pub updated: Option<String>,
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoogleTasksResponse {
+    #[serde(default)]
+    pub items: Vec<GoogleTaskResponse>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
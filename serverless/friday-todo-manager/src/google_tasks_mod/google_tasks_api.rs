@@ -0,0 +1,444 @@
+use crate::business_response::BusinessResponse;
+use crate::google_tasks_mod::google_task_list_response::{
+    GoogleTaskListResponse, GoogleTaskListsResponse,
+};
+use crate::google_tasks_mod::google_task_response::{GoogleTaskResponse, GoogleTasksResponse};
+use crate::provider_mod::todo_provider::TodoProviderKind;
+use crate::ENV_CONFIG;
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+/// Upper bound of pages followed when fetching all task lists or tasks
+const MAX_PAGES: usize = 100;
+
+/// Largest page size accepted by Google Tasks
+const PAGE_SIZE: &str = "100";
+
+/// ID of the list exposed by the API: the Google task list ID prefixed with `google:`
+pub fn format_list_id(task_list_id: &str) -> String {
+    format!(
+        "{}{}",
+        TodoProviderKind::Google
+            .list_id_prefix()
+            .unwrap_or_default(),
+        task_list_id
+    )
+}
+
+/// Google task list ID of a list ID exposed by the API; `None` when it is not a google list ID
+pub fn parse_list_id(list_id: &str) -> Option<&str> {
+    list_id
+        .strip_prefix(TodoProviderKind::Google.list_id_prefix()?)
+        .filter(|task_list_id| !task_list_id.trim().is_empty())
+}
+
+/// Google Tasks API, called through the provider proxy of the OAuth Manager, which injects
+/// the access token of the stored Google credential
+#[derive(Debug)]
+pub struct GoogleTasksApi {
+    client: Client,
+    base_url: String,
+}
+
+impl GoogleTasksApi {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: format!("{}/proxy/google/tasks/v1", ENV_CONFIG.oauth_manager_url),
+        }
+    }
+
+    /// Fetches every task list, following `nextPageToken` until the last page
+    pub async fn get_task_lists(
+        &self,
+        service_token: &str,
+    ) -> Result<BusinessResponse<Vec<GoogleTaskListResponse>>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching all task lists from Google Tasks");
+
+        let url = format!("{}/users/@me/lists", self.base_url);
+        let mut task_lists = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![("maxResults", PAGE_SIZE.to_string())];
+            query.extend(page_token.map(|token| ("pageToken", token)));
+
+            let request = self.client.get(&url).query(&query);
+            let response_text = match self
+                .send_request(
+                    request,
+                    service_token,
+                    "get task lists",
+                    "Todo list not found",
+                )
+                .await?
+            {
+                Ok(response_text) => response_text,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            let task_lists_response =
+                match parse_response::<GoogleTaskListsResponse, _>(&response_text) {
+                    Ok(task_lists_response) => task_lists_response,
+                    Err(error_response) => return Ok(error_response),
+                };
+
+            task_lists.extend(task_lists_response.items);
+
+            page_token = task_lists_response.next_page_token;
+            if page_token.is_none() {
+                info!(
+                    "Data layer: Successfully parsed {} task lists",
+                    task_lists.len()
+                );
+                return Ok(BusinessResponse::success(task_lists));
+            }
+        }
+
+        error!(
+            "Data layer: Stopped following nextPageToken after {} pages of task lists",
+            MAX_PAGES
+        );
+        Ok(BusinessResponse::error(
+            "Too many pages returned by Google Tasks",
+        ))
+    }
+
+    pub async fn get_task_list(
+        &self,
+        task_list_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskListResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching task list with ID: {}", task_list_id);
+
+        let url = format!("{}/users/@me/lists/{}", self.base_url, task_list_id);
+        let request = self.client.get(&url);
+
+        match self
+            .send_request(
+                request,
+                service_token,
+                "get todo list",
+                "Todo list not found",
+            )
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    pub async fn create_task_list(
+        &self,
+        title: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskListResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Creating task list with name: {}", title);
+
+        let url = format!("{}/users/@me/lists", self.base_url);
+        let request = self.client.post(&url).json(&json!({ "title": title }));
+
+        match self
+            .send_request(
+                request,
+                service_token,
+                "create todo list",
+                "Todo list not found",
+            )
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    pub async fn update_task_list(
+        &self,
+        task_list_id: &str,
+        title: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskListResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Updating task list with ID: {}", task_list_id);
+
+        let url = format!("{}/users/@me/lists/{}", self.base_url, task_list_id);
+        let request = self.client.patch(&url).json(&json!({ "title": title }));
+
+        match self
+            .send_request(
+                request,
+                service_token,
+                "update todo list",
+                "Todo list not found",
+            )
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Deletes the task list with its tasks
+    pub async fn delete_task_list(
+        &self,
+        task_list_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        info!("Data layer: Deleting task list with ID: {}", task_list_id);
+
+        let url = format!("{}/users/@me/lists/{}", self.base_url, task_list_id);
+        let request = self.client.delete(&url);
+
+        match self
+            .send_request(
+                request,
+                service_token,
+                "delete todo list",
+                "Todo list not found",
+            )
+            .await?
+        {
+            Ok(_) => Ok(BusinessResponse::success(
+                "Todo list deleted successfully".to_string(),
+            )),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Fetches every task of the list, subtasks and completed tasks included, following
+    /// `nextPageToken` until the last page
+    pub async fn get_tasks(
+        &self,
+        task_list_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<Vec<GoogleTaskResponse>>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching tasks of task list: {}", task_list_id);
+
+        let url = format!("{}/lists/{}/tasks", self.base_url, task_list_id);
+        let mut tasks = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("maxResults", PAGE_SIZE.to_string()),
+                ("showCompleted", "true".to_string()),
+                ("showHidden", "true".to_string()),
+            ];
+            query.extend(page_token.map(|token| ("pageToken", token)));
+
+            let request = self.client.get(&url).query(&query);
+            let response_text = match self
+                .send_request(request, service_token, "get tasks", "Todo list not found")
+                .await?
+            {
+                Ok(response_text) => response_text,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            let tasks_response = match parse_response::<GoogleTasksResponse, _>(&response_text) {
+                Ok(tasks_response) => tasks_response,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            tasks.extend(tasks_response.items);
+
+            page_token = tasks_response.next_page_token;
+            if page_token.is_none() {
+                info!("Data layer: Successfully parsed {} tasks", tasks.len());
+                return Ok(BusinessResponse::success(tasks));
+            }
+        }
+
+        error!(
+            "Data layer: Stopped following nextPageToken after {} pages of tasks",
+            MAX_PAGES
+        );
+        Ok(BusinessResponse::error(
+            "Too many pages returned by Google Tasks",
+        ))
+    }
+
+    pub async fn get_task(
+        &self,
+        task_list_id: &str,
+        task_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching task with ID: {}", task_id);
+
+        let url = format!("{}/lists/{}/tasks/{}", self.base_url, task_list_id, task_id);
+        let request = self.client.get(&url);
+
+        match self
+            .send_request(request, service_token, "get task", "Task not found")
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Creates a task, or a subtask of `parent` when given
+    pub async fn create_task(
+        &self,
+        task_list_id: &str,
+        parent: Option<&str>,
+        body: &Value,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Creating task in task list: {}", task_list_id);
+
+        let url = format!("{}/lists/{}/tasks", self.base_url, task_list_id);
+        let mut request = self.client.post(&url).json(body);
+        if let Some(parent) = parent {
+            request = request.query(&[("parent", parent)]);
+        }
+
+        let not_found = if parent.is_some() {
+            "Task not found"
+        } else {
+            "Todo list not found"
+        };
+
+        match self
+            .send_request(request, service_token, "create task", not_found)
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Updates the fields present in `body`, keeping the others
+    pub async fn update_task(
+        &self,
+        task_list_id: &str,
+        task_id: &str,
+        body: &Value,
+        service_token: &str,
+    ) -> Result<BusinessResponse<GoogleTaskResponse>, Box<dyn std::error::Error>> {
+        info!("Data layer: Updating task with ID: {}", task_id);
+
+        let url = format!("{}/lists/{}/tasks/{}", self.base_url, task_list_id, task_id);
+        let request = self.client.patch(&url).json(body);
+
+        match self
+            .send_request(request, service_token, "update task", "Task not found")
+            .await?
+        {
+            Ok(response_text) => Ok(into_business_response(parse_response(&response_text))),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Deletes the task with its subtasks
+    pub async fn delete_task(
+        &self,
+        task_list_id: &str,
+        task_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        info!("Data layer: Deleting task with ID: {}", task_id);
+
+        let url = format!("{}/lists/{}/tasks/{}", self.base_url, task_list_id, task_id);
+        let request = self.client.delete(&url);
+
+        match self
+            .send_request(request, service_token, "delete task", "Task not found")
+            .await?
+        {
+            Ok(_) => Ok(BusinessResponse::success(
+                "Task deleted successfully".to_string(),
+            )),
+            Err(error_response) => Ok(error_response),
+        }
+    }
+
+    /// Sends the request through the proxy and returns the body of a successful response. The
+    /// inner `Err` carries the business error to return: `not_found` when Google Tasks answers
+    /// 404, and the errors of the OAuth Manager when the proxy itself rejects the request.
+    async fn send_request<T>(
+        &self,
+        request: RequestBuilder,
+        service_token: &str,
+        action: &str,
+        not_found: &str,
+    ) -> Result<Result<String, BusinessResponse<T>>, Box<dyn std::error::Error>> {
+        let response = match request
+            .header("Authorization", format!("Bearer {}", service_token))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        let status = response.status();
+        info!("Data layer: Received response with status: {}", status);
+
+        let response_text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Data layer: Failed to read response body: {}", e);
+                return Ok(Err(BusinessResponse::error(
+                    "Failed to read Google Tasks response",
+                )));
+            }
+        };
+
+        if status.is_success() {
+            info!(
+                "Data layer: Raw response from Google Tasks: {}",
+                response_text
+            );
+            return Ok(Ok(response_text));
+        }
+
+        // Errors of the proxy are business responses; those of Google Tasks are not
+        if let Ok(proxy_response) = serde_json::from_str::<BusinessResponse<Value>>(&response_text)
+        {
+            error!(
+                "Data layer: OAuth manager proxy error ({}): {:?}",
+                status, proxy_response.errors
+            );
+            return Ok(Err(BusinessResponse::error(&format!(
+                "Failed to reach Google Tasks through the OAuth manager: {}",
+                proxy_response.errors.join(", ")
+            ))));
+        }
+
+        if status == 404 {
+            info!("Data layer: Google Tasks answered 404 to {}", action);
+            return Ok(Err(BusinessResponse::error(not_found)));
+        }
+
+        error!(
+            "Data layer: Google Tasks API error ({}): {}",
+            status, response_text
+        );
+        Ok(Err(BusinessResponse::error(&format!(
+            "Failed to {}: {} - {}",
+            action, status, response_text
+        ))))
+    }
+}
+
+/// Parses a Google Tasks response. The `Err` carries the business error to return.
+fn parse_response<R: DeserializeOwned, T>(response_text: &str) -> Result<R, BusinessResponse<T>> {
+    serde_json::from_str::<R>(response_text).map_err(|e| {
+        error!("Data layer: Failed to parse Google Tasks response: {}", e);
+        error!(
+            "Data layer: Response that failed to parse: {}",
+            response_text
+        );
+        BusinessResponse::error("Failed to parse Google Tasks response")
+    })
+}
+
+fn into_business_response<T>(parsed: Result<T, BusinessResponse<T>>) -> BusinessResponse<T> {
+    parsed.map_or_else(|error_response| error_response, BusinessResponse::success)
+}
@@ -0,0 +1,583 @@
+use crate::business_response::BusinessResponse;
+use crate::google_tasks_mod::google_date_time::to_google_due_date;
+use crate::google_tasks_mod::google_tasks_api::{parse_list_id, GoogleTasksApi};
+use crate::oauth_mod::oauth_api::OAuthApi;
+use crate::provider_mod::todo_provider::TodoProvider;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::task::{
+    CreateTaskRequest, Task, TaskFilterQuery, TaskImportance, TaskPage, TaskPageQuery, TaskStatus,
+    UpdateTaskRequest,
+};
+use crate::todo_mod::task_filter::{apply_graph_filters, apply_local_filters, apply_page};
+use crate::todo_mod::todo_list::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use tracing::{error, warn};
+
+/// Google Tasks through the provider proxy of the OAuth Manager. Subtasks are the checklist
+/// items of their parent; importance, reminders and recurrence do not exist in Google Tasks
+/// and are rejected. Filtering, ordering and paging are applied here, like in Postgres.
+#[derive(Debug)]
+pub struct GoogleTasksProvider {
+    client: GoogleTasksApi,
+}
+
+impl GoogleTasksProvider {
+    pub fn new() -> Self {
+        Self {
+            client: GoogleTasksApi::new(),
+        }
+    }
+
+    /// Subtasks of the task, as checklist items
+    async fn get_subtasks(
+        &self,
+        task_list_id: &str,
+        task_id: &str,
+        service_token: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+        let response = self.client.get_tasks(task_list_id, service_token).await?;
+        let Some(google_tasks) = response.data.filter(|_| response.success) else {
+            return Ok(retype_error(response.errors));
+        };
+
+        let checklist_items = google_tasks
+            .into_iter()
+            .filter(|google_task| google_task.parent.as_deref() == Some(task_id))
+            .map(ChecklistItem::try_from)
+            .collect::<Result<Vec<_>, _>>();
+
+        match checklist_items {
+            Ok(checklist_items) => Ok(BusinessResponse::success(checklist_items)),
+            Err(e) => Ok(invalid_response("checklist item", &e)),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl TodoProvider for GoogleTasksProvider {
+    async fn get_todo_lists(
+        &self,
+    ) -> Result<BusinessResponse<Vec<TodoList>>, Box<dyn std::error::Error>> {
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self.client.get_task_lists(&service_token).await?;
+        let Some(task_lists) = response.data.filter(|_| response.success) else {
+            return Ok(retype_error(response.errors));
+        };
+
+        match task_lists
+            .into_iter()
+            .map(TodoList::try_from)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(todo_lists) => Ok(BusinessResponse::success(todo_lists)),
+            Err(e) => Ok(invalid_response("todo list", &e)),
+        }
+    }
+
+    async fn get_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .get_task_list(task_list_id, &service_token)
+            .await?;
+        Ok(convert(response, "todo list"))
+    }
+
+    async fn create_todo_list(
+        &self,
+        request: CreateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .create_task_list(&request.display_name, &service_token)
+            .await?;
+        Ok(convert(response, "todo list"))
+    }
+
+    async fn update_todo_list(
+        &self,
+        request: UpdateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(&request.id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .update_task_list(task_list_id, &request.display_name, &service_token)
+            .await?;
+        Ok(convert(response, "todo list"))
+    }
+
+    async fn delete_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.client
+            .delete_task_list(task_list_id, &service_token)
+            .await
+    }
+
+    async fn get_tasks(
+        &self,
+        list_id: &str,
+        page: &TaskPageQuery,
+        filter: &TaskFilterQuery,
+    ) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self.client.get_tasks(task_list_id, &service_token).await?;
+        let Some(google_tasks) = response.data.filter(|_| response.success) else {
+            return Ok(retype_error(response.errors));
+        };
+
+        let tasks = google_tasks
+            .into_iter()
+            .filter(|google_task| google_task.parent.is_none())
+            .map(Task::try_from)
+            .collect::<Result<Vec<_>, _>>();
+        let mut tasks = match tasks {
+            Ok(tasks) => tasks,
+            Err(e) => return Ok(invalid_response("task", &e)),
+        };
+
+        apply_graph_filters(&mut tasks, filter);
+        apply_local_filters(&mut tasks, filter);
+
+        match apply_page(tasks, page) {
+            Some(task_page) => Ok(BusinessResponse::success(task_page)),
+            None => Ok(BusinessResponse::error("Invalid cursor")),
+        }
+    }
+
+    async fn get_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        include_checklist: bool,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .get_task(task_list_id, task_id, &service_token)
+            .await?;
+
+        // Subtasks are only reachable as checklist items
+        if response
+            .data
+            .as_ref()
+            .is_some_and(|google_task| google_task.parent.is_some())
+        {
+            return Ok(BusinessResponse::error("Task not found"));
+        }
+
+        let mut result: BusinessResponse<Task> = convert(response, "task");
+        if let Some(task) = result.data.as_mut().filter(|_| include_checklist) {
+            let checklist_items = self
+                .get_subtasks(task_list_id, task_id, &service_token)
+                .await?;
+            let Some(checklist_items) = checklist_items.data.filter(|_| checklist_items.success)
+            else {
+                return Ok(retype_error(checklist_items.errors));
+            };
+            task.checklist_items = Some(checklist_items);
+        }
+
+        Ok(result)
+    }
+
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let Some(task_list_id) = parse_list_id(&list_id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        if let Some(error_response) = validate_unsupported_fields(
+            request.importance,
+            request.is_reminder_on,
+            request.reminder_date_time.is_some(),
+            request.recurrence.is_some(),
+        ) {
+            return Ok(error_response);
+        }
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let mut body = Map::new();
+        body.insert("title".to_string(), json!(request.title));
+        if let Some(notes) = &request.body {
+            body.insert("notes".to_string(), json!(notes));
+        }
+        if let Some(due_date_time) = &request.due_date_time {
+            body.insert("due".to_string(), json!(to_google_due_date(due_date_time)));
+        }
+
+        let response = self
+            .client
+            .create_task(task_list_id, None, &Value::Object(body), &service_token)
+            .await?;
+        Ok(convert(response, "task"))
+    }
+
+    async fn update_task(
+        &self,
+        request: UpdateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let task_id = request.id.clone().unwrap_or_default();
+        let Some(task_list_id) = parse_list_id(&list_id) else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        if let Some(error_response) = validate_unsupported_fields(
+            request.importance,
+            request.is_reminder_on,
+            request.reminder_date_time.is_some(),
+            request.recurrence.is_some(),
+        ) {
+            return Ok(error_response);
+        }
+
+        let mut body = Map::new();
+        if let Some(title) = &request.title {
+            body.insert("title".to_string(), json!(title));
+        }
+        if let Some(notes) = &request.body {
+            body.insert("notes".to_string(), json!(notes));
+        }
+        if let Some(due_date_time) = &request.due_date_time {
+            body.insert("due".to_string(), json!(to_google_due_date(due_date_time)));
+        }
+        match request.status {
+            Some(TaskStatus::Completed) => {
+                body.insert("status".to_string(), json!("completed"));
+            }
+            Some(TaskStatus::NotStarted) => {
+                body.insert("status".to_string(), json!("needsAction"));
+                body.insert("completed".to_string(), Value::Null);
+            }
+            Some(status) => {
+                warn!(
+                    "Provider layer: Status {} is not supported by Google Tasks",
+                    status.as_str()
+                );
+                return Ok(BusinessResponse::error(
+                    "Google Tasks only supports the notStarted and completed statuses",
+                ));
+            }
+            None => {}
+        }
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .update_task(task_list_id, &task_id, &Value::Object(body), &service_token)
+            .await?;
+        Ok(convert(response, "task"))
+    }
+
+    async fn delete_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.client
+            .delete_task(task_list_id, task_id, &service_token)
+            .await
+    }
+
+    async fn get_checklist_items(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+        let task = self.get_task(list_id, task_id, true).await?;
+        match task.data.and_then(|task| task.checklist_items) {
+            Some(checklist_items) => Ok(BusinessResponse::success(checklist_items)),
+            None => Ok(retype_error(task.errors)),
+        }
+    }
+
+    async fn get_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Checklist item not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .get_task(task_list_id, checklist_item_id, &service_token)
+            .await?;
+
+        let is_not_found = response.errors.iter().any(|e| e.contains("not found"));
+        let is_subtask = response
+            .data
+            .as_ref()
+            .is_some_and(|google_task| google_task.parent.as_deref() == Some(task_id));
+        if is_not_found || (response.success && !is_subtask) {
+            return Ok(BusinessResponse::error("Checklist item not found"));
+        }
+
+        Ok(convert(response, "checklist item"))
+    }
+
+    async fn create_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &CreateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let task = self.get_task(list_id, task_id, false).await?;
+        if !task.success {
+            return Ok(retype_error(task.errors));
+        }
+
+        let Some(task_list_id) = parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let status = if request.is_checked.unwrap_or(false) {
+            "completed"
+        } else {
+            "needsAction"
+        };
+        let body = json!({
+            "title": request.display_name,
+            "status": status
+        });
+
+        let response = self
+            .client
+            .create_task(task_list_id, Some(task_id), &body, &service_token)
+            .await?;
+        Ok(convert(response, "checklist item"))
+    }
+
+    async fn update_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        request: &UpdateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let existing = self
+            .get_checklist_item(list_id, task_id, checklist_item_id)
+            .await?;
+        let (Some(_), Some(task_list_id)) = (existing.data.as_ref(), parse_list_id(list_id)) else {
+            return Ok(existing);
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let mut body = Map::new();
+        if let Some(display_name) = &request.display_name {
+            body.insert("title".to_string(), json!(display_name));
+        }
+        match request.is_checked {
+            Some(true) => {
+                body.insert("status".to_string(), json!("completed"));
+            }
+            Some(false) => {
+                body.insert("status".to_string(), json!("needsAction"));
+                body.insert("completed".to_string(), Value::Null);
+            }
+            None => {}
+        }
+
+        let response = self
+            .client
+            .update_task(
+                task_list_id,
+                checklist_item_id,
+                &Value::Object(body),
+                &service_token,
+            )
+            .await?;
+        Ok(convert(response, "checklist item"))
+    }
+
+    async fn delete_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let existing = self
+            .get_checklist_item(list_id, task_id, checklist_item_id)
+            .await?;
+        let (Some(_), Some(task_list_id)) = (existing.data.as_ref(), parse_list_id(list_id)) else {
+            return Ok(retype_error(existing.errors));
+        };
+
+        let service_token = match get_service_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let response = self
+            .client
+            .delete_task(task_list_id, checklist_item_id, &service_token)
+            .await?;
+        if !response.success {
+            return Ok(response);
+        }
+
+        Ok(BusinessResponse::success(
+            "Checklist item deleted successfully".to_string(),
+        ))
+    }
+}
+
+/// Rejects the task fields Google Tasks cannot store, instead of dropping them silently
+fn validate_unsupported_fields<T>(
+    importance: Option<TaskImportance>,
+    is_reminder_on: Option<bool>,
+    has_reminder_date_time: bool,
+    has_recurrence: bool,
+) -> Option<BusinessResponse<T>> {
+    let message = if importance.is_some_and(|importance| importance != TaskImportance::Normal) {
+        "Google Tasks does not support task importance"
+    } else if is_reminder_on == Some(true) || has_reminder_date_time {
+        "Google Tasks does not support reminders"
+    } else if has_recurrence {
+        "Google Tasks does not support recurrence through its API"
+    } else {
+        return None;
+    };
+
+    warn!("Provider layer: {}", message);
+    Some(BusinessResponse::error(message))
+}
+
+/// Converts a successful Google Tasks response into the model of the API
+fn convert<R, T>(response: BusinessResponse<R>, kind: &str) -> BusinessResponse<T>
+where
+    T: TryFrom<R, Error = String>,
+{
+    let Some(data) = response.data.filter(|_| response.success) else {
+        return retype_error(response.errors);
+    };
+
+    match T::try_from(data) {
+        Ok(converted) => BusinessResponse::success(converted),
+        Err(e) => invalid_response(kind, &e),
+    }
+}
+
+fn invalid_response<T>(kind: &str, error: &str) -> BusinessResponse<T> {
+    error!(
+        "Provider layer: Invalid {} returned by Google Tasks: {}",
+        kind, error
+    );
+    BusinessResponse::error(&format!(
+        "Invalid {} returned by Google Tasks: {}",
+        kind, error
+    ))
+}
+
+fn retype_error<T>(errors: Vec<String>) -> BusinessResponse<T> {
+    BusinessResponse {
+        success: false,
+        data: None,
+        errors,
+    }
+}
+
+async fn get_service_token<T>() -> Result<String, BusinessResponse<T>> {
+    let oauth_client = OAuthApi::new();
+    oauth_client.generate_service_token().await.map_err(|e| {
+        error!(
+            "Provider layer: Failed to get service token from OAuth manager: {}",
+            e
+        );
+        BusinessResponse::error("Failed to authenticate with Google Tasks")
+    })
+}
@@ -0,0 +1,5 @@
+pub mod google_date_time;
+pub mod google_task_list_response;
+pub mod google_task_response;
+pub mod google_tasks_api;
+pub mod google_tasks_provider;
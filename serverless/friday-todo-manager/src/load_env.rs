@@ -11,6 +11,31 @@ pub fn load_env_variables() -> EnvVariables {
         EnvVariables {
             is_prod: false,
             oauth_manager_url: "https://k8s.z33p.com/api/friday-oauth-manager".to_string(),
+            user_time_zone: std::env::var("USER_TIME_ZONE")
+                .unwrap_or_else(|_| default_user_time_zone()),
+            redis_url: std::env::var("REDIS_URL").ok(),
+            cache_max_age_seconds: std::env::var("CACHE_MAX_AGE_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_else(default_cache_max_age_seconds),
+            cache_refresh_interval_seconds: std::env::var("CACHE_REFRESH_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_else(default_cache_refresh_interval_seconds),
+            notification_url: std::env::var("NOTIFICATION_URL").ok(),
+            subscription_client_state: std::env::var("SUBSCRIPTION_CLIENT_STATE").ok(),
+            subscription_renewal_interval_seconds: std::env::var(
+                "SUBSCRIPTION_RENEWAL_INTERVAL_SECONDS",
+            )
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(default_subscription_renewal_interval_seconds),
+            rabbitmq_url: std::env::var("RABBITMQ_URL").ok(),
+            todo_providers: std::env::var("TODO_PROVIDERS")
+                .unwrap_or_else(|_| default_todo_providers()),
+            database_url: std::env::var("DATABASE_URL").ok(),
+            service_client_id: std::env::var("SERVICE_CLIENT_ID").ok(),
+            service_client_secret: std::env::var("SERVICE_CLIENT_SECRET").ok(),
         }
     };
 
@@ -28,4 +53,51 @@ pub struct EnvVariables {
     #[serde(skip)]
     pub is_prod: bool,
     pub oauth_manager_url: String,
+    /// IANA or Windows name of the time zone due dates and reminders are written in
+    #[serde(default = "default_user_time_zone")]
+    pub user_time_zone: String,
+    /// Redis used to cache lists and tasks; the cache is disabled when unset
+    pub redis_url: Option<String>,
+    /// Age after which a cached read waits for a delta sync first
+    #[serde(default = "default_cache_max_age_seconds")]
+    pub cache_max_age_seconds: u64,
+    #[serde(default = "default_cache_refresh_interval_seconds")]
+    pub cache_refresh_interval_seconds: u64,
+    /// Public URL of the notification webhook given to Microsoft Graph
+    pub notification_url: Option<String>,
+    /// Secret sent back by Graph in every notification; others are discarded
+    pub subscription_client_state: Option<String>,
+    #[serde(default = "default_subscription_renewal_interval_seconds")]
+    pub subscription_renewal_interval_seconds: u64,
+    /// RabbitMQ the task events are published to; they are dropped when unset
+    pub rabbitmq_url: Option<String>,
+    /// Comma-separated backends served, e.g. `microsoft,postgres`; new lists go to the first
+    #[serde(default = "default_todo_providers")]
+    pub todo_providers: String,
+    /// Postgres of the `postgres` provider
+    pub database_url: Option<String>,
+    /// Service client of the OAuth Manager used by the `google` provider, whose requests go
+    /// through the OAuth Manager proxy
+    pub service_client_id: Option<String>,
+    pub service_client_secret: Option<String>,
+}
+
+fn default_user_time_zone() -> String {
+    "UTC".to_string()
+}
+
+fn default_cache_max_age_seconds() -> u64 {
+    60
+}
+
+fn default_cache_refresh_interval_seconds() -> u64 {
+    30
+}
+
+fn default_subscription_renewal_interval_seconds() -> u64 {
+    3600
+}
+
+fn default_todo_providers() -> String {
+    "microsoft".to_string()
 }
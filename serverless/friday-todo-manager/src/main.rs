@@ -2,20 +2,30 @@ use actix_web::{get, App, HttpServer, Responder};
 use dotenv::dotenv;
 use load_env::{load_env_variables, EnvVariables};
 use once_cell::sync::Lazy;
-use tracing::Level;
+use tracing::{info, Level};
 
-use crate::todo_mod::{task_controller, todo_list_controller};
+use crate::cache_mod::task_cache_logic;
+use crate::microsoft_graph_mod::graph_date_time;
+use crate::provider_mod::todo_provider::TodoProviderKind;
+use crate::provider_mod::todo_provider_logic;
+use crate::subscription_mod::{subscription_controller, subscription_logic};
+use crate::todo_mod::{checklist_item_controller, task_controller, todo_list_controller};
 
 mod business_response;
+mod cache_mod;
+mod google_tasks_mod;
 mod load_env;
 mod microsoft_graph_mod;
 mod oauth_mod;
 mod openapi;
+mod postgres_mod;
+mod provider_mod;
+mod subscription_mod;
 mod todo_mod;
 
 extern crate dotenv;
 
-static ENV_CONFIG: Lazy<EnvVariables> = Lazy::new(|| load_env_variables());
+static ENV_CONFIG: Lazy<EnvVariables> = Lazy::new(load_env_variables);
 
 #[get("/")]
 async fn index() -> impl Responder {
@@ -28,6 +38,19 @@ async fn main() -> std::io::Result<()> {
 
     logging_init();
 
+    // Fail on startup rather than on the first write with an unknown time zone
+    let user_time_zone = graph_date_time::user_time_zone();
+    info!("Writing task dates in time zone: {}", user_time_zone.name());
+
+    let providers = todo_provider_logic::check_configuration();
+    info!("Serving todo providers: {:?}", providers);
+
+    // The cache and the subscriptions only concern Microsoft lists
+    if todo_provider_logic::is_enabled(TodoProviderKind::Microsoft) {
+        task_cache_logic::spawn_background_refresh();
+        subscription_logic::spawn_subscription_renewal();
+    }
+
     HttpServer::new(|| {
         App::new()
             .service(index)
@@ -36,11 +59,26 @@ async fn main() -> std::io::Result<()> {
             .service(todo_list_controller::create_todo_list)
             .service(todo_list_controller::update_todo_list)
             .service(todo_list_controller::delete_todo_list)
+            // Registered before the `{task_id}` routes, which would otherwise match `batch`
+            .service(task_controller::create_tasks)
+            .service(task_controller::update_tasks)
+            .service(task_controller::complete_tasks)
+            .service(task_controller::delete_tasks)
             .service(task_controller::get_all_tasks)
             .service(task_controller::get_task)
             .service(task_controller::create_task)
             .service(task_controller::update_task)
             .service(task_controller::delete_task)
+            .service(checklist_item_controller::get_checklist_items)
+            .service(checklist_item_controller::get_checklist_item)
+            .service(checklist_item_controller::create_checklist_item)
+            .service(checklist_item_controller::update_checklist_item)
+            .service(checklist_item_controller::delete_checklist_item)
+            .service(subscription_controller::get_subscriptions)
+            .service(subscription_controller::create_subscription)
+            .service(subscription_controller::receive_notifications)
+            .service(subscription_controller::renew_subscription)
+            .service(subscription_controller::delete_subscription)
             .service(openapi::swagger_config())
     })
     .workers(4)
@@ -0,0 +1,297 @@
+use crate::microsoft_graph_mod::batch_response::{BatchResponse, BatchResponseItem};
+use reqwest::Client;
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+/// Maximum number of requests Graph accepts in a single `$batch` call
+const MAX_BATCH_SIZE: usize = 20;
+
+/// A request of a JSON batch. `url` is relative to the API version, e.g. `/me/todo/lists`.
+#[derive(Debug)]
+pub struct BatchRequest {
+    pub id: String,
+    pub method: &'static str,
+    pub url: String,
+    pub body: Option<Value>,
+}
+
+#[derive(Debug)]
+pub struct BatchMicrosoftGraphApi {
+    client: Client,
+    base_url: String,
+}
+
+impl BatchMicrosoftGraphApi {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: "https://graph.microsoft.com/v1.0".to_string(),
+        }
+    }
+
+    /// Sends the requests through `$batch`, 20 at a time, and returns one response per
+    /// request in the order given. Chunks already sent are kept when a later one fails: the
+    /// requests of a failed chunk get a synthesized error response instead.
+    pub async fn send(
+        &self,
+        requests: Vec<BatchRequest>,
+        access_token: &str,
+    ) -> Vec<BatchResponseItem> {
+        let mut responses = Vec::with_capacity(requests.len());
+
+        for chunk in requests.chunks(MAX_BATCH_SIZE) {
+            responses.extend(self.send_chunk(chunk, access_token).await);
+        }
+
+        responses
+    }
+
+    async fn send_chunk(
+        &self,
+        chunk: &[BatchRequest],
+        access_token: &str,
+    ) -> Vec<BatchResponseItem> {
+        let requests: Vec<Value> = chunk
+            .iter()
+            .map(|request| {
+                let mut batch_request = json!({
+                    "id": request.id,
+                    "method": request.method,
+                    "url": request.url,
+                });
+
+                if let Some(body) = &request.body {
+                    batch_request["body"] = body.clone();
+                    batch_request["headers"] = json!({ "Content-Type": "application/json" });
+                }
+
+                batch_request
+            })
+            .collect();
+
+        let url = format!("{}/$batch", self.base_url);
+        info!(
+            "Data layer: Making POST request to: {} with {} requests",
+            url,
+            requests.len()
+        );
+
+        let response = match self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .json(&json!({ "requests": requests }))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return failed_chunk(chunk, 503, "Failed to reach Microsoft Graph");
+            }
+        };
+
+        let status = response.status();
+        info!("Data layer: Received response with status: {}", status);
+
+        let response_text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Data layer: Failed to read response body: {}", e);
+                return failed_chunk(chunk, 502, "Failed to read Microsoft Graph response");
+            }
+        };
+
+        if !status.is_success() {
+            error!(
+                "Data layer: Microsoft Graph API error ({}): {}",
+                status, response_text
+            );
+            return failed_chunk(
+                chunk,
+                status.as_u16(),
+                &format!("Batch request failed: {} - {}", status, response_text),
+            );
+        }
+
+        let mut batch_response = match serde_json::from_str::<BatchResponse>(&response_text) {
+            Ok(batch_response) => batch_response,
+            Err(e) => {
+                error!("Data layer: Failed to parse batch response: {}", e);
+                return failed_chunk(chunk, 502, "Failed to parse Microsoft Graph response");
+            }
+        };
+
+        // Graph may answer the requests of a batch in any order
+        chunk
+            .iter()
+            .map(|request| {
+                match batch_response
+                    .responses
+                    .iter()
+                    .position(|response| response.id == request.id)
+                {
+                    Some(position) => batch_response.responses.swap_remove(position),
+                    None => {
+                        error!("Data layer: No response to batch request {}", request.id);
+                        error_item(
+                            &request.id,
+                            502,
+                            "Missing response in Microsoft Graph batch",
+                        )
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn failed_chunk(chunk: &[BatchRequest], status: u16, message: &str) -> Vec<BatchResponseItem> {
+    chunk
+        .iter()
+        .map(|request| error_item(&request.id, status, message))
+        .collect()
+}
+
+/// Response in the shape of a Graph error, so that callers read every failure the same way
+fn error_item(id: &str, status: u16, message: &str) -> BatchResponseItem {
+    BatchResponseItem {
+        id: id.to_string(),
+        status,
+        body: Some(json!({ "error": { "message": message } })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    use actix_web::{web, App, HttpResponse, HttpServer};
+
+    use super::*;
+
+    /// `$batch` endpoint recording the ids of every call. It answers in reverse order, leaves
+    /// out the request with id `missing` and fails the whole call when it holds id `fail`.
+    #[derive(Default)]
+    struct MockBatch {
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl MockBatch {
+        fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    async fn batch(state: web::Data<MockBatch>, body: web::Json<Value>) -> HttpResponse {
+        let ids: Vec<String> = body["requests"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|request| request["id"].as_str().unwrap().to_string())
+            .collect();
+        state.calls.lock().unwrap().push(ids.clone());
+
+        if ids.iter().any(|id| id == "fail") {
+            return HttpResponse::InternalServerError().body("batch failed");
+        }
+
+        let responses: Vec<Value> = ids
+            .iter()
+            .rev()
+            .filter(|id| *id != "missing")
+            .map(|id| json!({ "id": id, "status": 201, "body": { "id": id } }))
+            .collect();
+        HttpResponse::Ok().json(json!({ "responses": responses }))
+    }
+
+    fn start_batch_server() -> (BatchMicrosoftGraphApi, Arc<MockBatch>) {
+        let state = Arc::new(MockBatch::default());
+        let data = web::Data::from(state.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/$batch", web::post().to(batch))
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap()
+        .run();
+        actix_web::rt::spawn(server);
+
+        let api = BatchMicrosoftGraphApi {
+            client: Client::new(),
+            base_url,
+        };
+        (api, state)
+    }
+
+    fn requests(ids: &[&str]) -> Vec<BatchRequest> {
+        ids.iter()
+            .map(|id| BatchRequest {
+                id: id.to_string(),
+                method: "POST",
+                url: "/me/todo/lists/AAMkAD=/tasks".to_string(),
+                body: Some(json!({ "title": id })),
+            })
+            .collect()
+    }
+
+    #[actix_web::test]
+    async fn sends_at_most_twenty_requests_per_call_and_keeps_their_order() {
+        let (api, mock) = start_batch_server();
+        let ids: Vec<String> = (0..45).map(|index| index.to_string()).collect();
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        let responses = api.send(requests(&ids), "token").await;
+
+        let chunk_sizes: Vec<usize> = mock.calls().iter().map(Vec::len).collect();
+        assert_eq!(chunk_sizes, vec![20, 20, 5]);
+        assert_eq!(
+            responses
+                .iter()
+                .map(|response| response.id.as_str())
+                .collect::<Vec<_>>(),
+            ids
+        );
+        assert!(responses.iter().all(BatchResponseItem::is_success));
+    }
+
+    #[actix_web::test]
+    async fn reports_missing_responses_and_failed_chunks_per_request() {
+        let (api, mock) = start_batch_server();
+        let mut ids: Vec<String> = (0..20).map(|index| index.to_string()).collect();
+        ids[3] = "missing".to_string();
+        ids.extend(["fail".to_string(), "20".to_string()]);
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        let responses = api.send(requests(&ids), "token").await;
+
+        assert_eq!(mock.calls().len(), 2);
+        assert_eq!(responses.len(), 22);
+
+        // Chunks already sent are kept
+        assert!(responses[2].is_success());
+        assert_eq!(responses[3].id, "missing");
+        assert_eq!(responses[3].status, 502);
+        assert_eq!(
+            responses[3].error_message(),
+            "Missing response in Microsoft Graph batch"
+        );
+
+        for response in &responses[20..] {
+            assert_eq!(response.status, 500);
+            assert!(response
+                .error_message()
+                .starts_with("Batch request failed: 500"));
+        }
+        assert_eq!(responses[21].id, "20");
+    }
+}
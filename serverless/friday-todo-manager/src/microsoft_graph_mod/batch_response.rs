@@ -0,0 +1,30 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchResponse {
+    pub responses: Vec<BatchResponseItem>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchResponseItem {
+    pub id: String,
+    pub status: u16,
+    pub body: Option<Value>,
+}
+
+impl BatchResponseItem {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// `error.message` of a failed response, or the status when Graph sent none
+    pub fn error_message(&self) -> String {
+        self.body
+            .as_ref()
+            .and_then(|body| body.pointer("/error/message"))
+            .and_then(Value::as_str)
+            .map(|message| message.to_string())
+            .unwrap_or_else(|| format!("HTTP {}", self.status))
+    }
+}
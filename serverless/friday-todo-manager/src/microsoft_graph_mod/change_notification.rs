@@ -0,0 +1,31 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Body Microsoft Graph POSTs to the notification URL
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangeNotificationCollection {
+    pub value: Vec<ChangeNotification>,
+}
+
+/// A change or lifecycle notification of a subscription
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangeNotification {
+    #[serde(rename = "subscriptionId")]
+    pub subscription_id: String,
+    #[serde(rename = "clientState")]
+    pub client_state: Option<String>,
+    /// `created`, `updated` or `deleted`; absent on lifecycle notifications
+    #[serde(rename = "changeType")]
+    pub change_type: Option<String>,
+    /// e.g. `Users/{user-id}/todo/lists/{list-id}/tasks/{task-id}`
+    pub resource: Option<String>,
+    #[serde(rename = "resourceData")]
+    pub resource_data: Option<ResourceData>,
+    /// `reauthorizationRequired`, `subscriptionRemoved` or `missed`
+    #[serde(rename = "lifecycleEvent")]
+    pub lifecycle_event: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResourceData {
+    pub id: Option<String>,
+}
@@ -0,0 +1,14 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChecklistItemResponse {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "isChecked")]
+    pub is_checked: bool,
+    #[serde(rename = "createdDateTime")]
+    pub created_date_time: Option<String>,
+    #[serde(rename = "checkedDateTime")]
+    pub checked_date_time: Option<String>,
+}
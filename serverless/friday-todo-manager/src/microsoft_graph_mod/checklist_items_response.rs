@@ -0,0 +1,9 @@
+use crate::microsoft_graph_mod::checklist_item_response::ChecklistItemResponse;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChecklistItemsResponse {
+    #[serde(rename = "@odata.context")]
+    pub odata_context: Option<String>,
+    pub value: Vec<ChecklistItemResponse>,
+}
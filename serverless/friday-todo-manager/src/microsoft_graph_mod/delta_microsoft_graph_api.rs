@@ -0,0 +1,374 @@
+use crate::business_response::BusinessResponse;
+use crate::microsoft_graph_mod::delta_response::DeltaResponse;
+use reqwest::Client;
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+/// Upper bound of nextLink pages followed in a single delta round
+const MAX_PAGES: usize = 100;
+
+/// Error returned when Graph no longer accepts a delta link and a full sync is needed
+pub const DELTA_TOKEN_EXPIRED: &str = "Delta token expired";
+
+/// A change reported by a delta query, in the order Graph returned it
+#[derive(Debug)]
+pub enum DeltaChange {
+    /// Created or updated item, raw Graph JSON
+    Upserted(Value),
+    Removed(String),
+}
+
+/// Every change since the previous delta link and the link for the next round
+#[derive(Debug)]
+pub struct DeltaChanges {
+    pub changes: Vec<DeltaChange>,
+    pub delta_link: String,
+}
+
+#[derive(Debug)]
+pub struct DeltaMicrosoftGraphApi {
+    client: Client,
+    base_url: String,
+}
+
+impl DeltaMicrosoftGraphApi {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: "https://graph.microsoft.com/v1.0".to_string(),
+        }
+    }
+
+    /// Starting point of a full sync of the todo lists
+    pub fn todo_lists_delta_url(&self) -> String {
+        format!("{}/me/todo/lists/delta", self.base_url)
+    }
+
+    /// Starting point of a full sync of the tasks of a list
+    pub fn tasks_delta_url(&self, list_id: &str) -> String {
+        format!("{}/me/todo/lists/{}/tasks/delta", self.base_url, list_id)
+    }
+
+    /// Runs a delta round from `url` (a starting point or a stored delta link), following
+    /// `@odata.nextLink` until Graph returns the `@odata.deltaLink` of the next round
+    pub async fn get_changes(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<BusinessResponse<DeltaChanges>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching delta changes from: {}", url);
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        if !url.starts_with(&self.base_url) {
+            error!("Data layer: Unexpected delta link: {}", url);
+            return Ok(BusinessResponse::error(
+                "Unexpected Microsoft Graph delta link",
+            ));
+        }
+
+        let mut changes = Vec::new();
+        let mut next_url = url.to_string();
+
+        for _ in 0..MAX_PAGES {
+            let delta_response = match self.get_delta_page(&next_url, access_token).await? {
+                Ok(delta_response) => delta_response,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            for item in delta_response.value {
+                match parse_change(item) {
+                    Some(change) => changes.push(change),
+                    None => warn!("Data layer: Ignoring delta item without id"),
+                }
+            }
+
+            match (
+                delta_response.odata_next_link,
+                delta_response.odata_delta_link,
+            ) {
+                (Some(next_link), _) if next_link.starts_with(&self.base_url) => {
+                    next_url = next_link;
+                }
+                (None, Some(delta_link)) if delta_link.starts_with(&self.base_url) => {
+                    info!(
+                        "Data layer: Successfully fetched {} delta changes",
+                        changes.len()
+                    );
+                    return Ok(BusinessResponse::success(DeltaChanges {
+                        changes,
+                        delta_link,
+                    }));
+                }
+                (next_link, delta_link) => {
+                    error!(
+                        "Data layer: Unexpected delta links: next {:?}, delta {:?}",
+                        next_link, delta_link
+                    );
+                    return Ok(BusinessResponse::error(
+                        "Unexpected Microsoft Graph delta link",
+                    ));
+                }
+            }
+        }
+
+        error!(
+            "Data layer: Stopped following nextLink after {} delta pages",
+            MAX_PAGES
+        );
+        Ok(BusinessResponse::error(
+            "Too many pages returned by Microsoft Graph",
+        ))
+    }
+
+    /// Fetches a single delta page. The inner `Err` carries the business error to return.
+    async fn get_delta_page(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<Result<DeltaResponse, BusinessResponse<DeltaChanges>>, Box<dyn std::error::Error>>
+    {
+        info!("Data layer: Making GET request to: {}", url);
+
+        let response = match self
+            .client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        let status = response.status();
+        info!("Data layer: Received response with status: {}", status);
+
+        let response_text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Data layer: Failed to read response body: {}", e);
+                return Ok(Err(BusinessResponse::error(
+                    "Failed to read Microsoft Graph response",
+                )));
+            }
+        };
+
+        if is_expired_delta_link(status.as_u16(), &response_text) {
+            warn!("Data layer: Delta link expired, a full sync is required");
+            return Ok(Err(BusinessResponse::error(DELTA_TOKEN_EXPIRED)));
+        }
+
+        if !status.is_success() {
+            error!(
+                "Data layer: Microsoft Graph API error ({}): {}",
+                status, response_text
+            );
+            return Ok(Err(BusinessResponse::error(&format!(
+                "Failed to get delta changes: {} - {}",
+                status, response_text
+            ))));
+        }
+
+        match serde_json::from_str::<DeltaResponse>(&response_text) {
+            Ok(delta_response) => Ok(Ok(delta_response)),
+            Err(e) => {
+                error!("Data layer: Failed to parse delta response: {}", e);
+                error!(
+                    "Data layer: Response that failed to parse: {}",
+                    response_text
+                );
+                Ok(Err(BusinessResponse::error(
+                    "Failed to parse Microsoft Graph response",
+                )))
+            }
+        }
+    }
+}
+
+/// Graph answers 410 Gone, or an error with a `syncStateNotFound`/`resyncRequired` code,
+/// when the delta link is too old to resume from
+fn is_expired_delta_link(status: u16, response_text: &str) -> bool {
+    if status == 410 {
+        return true;
+    }
+    if (200..300).contains(&status) {
+        return false;
+    }
+
+    serde_json::from_str::<Value>(response_text)
+        .ok()
+        .and_then(|body| body.pointer("/error/code")?.as_str().map(str::to_string))
+        .is_some_and(|code| {
+            code.eq_ignore_ascii_case("syncStateNotFound")
+                || code.eq_ignore_ascii_case("resyncRequired")
+        })
+}
+
+fn parse_change(item: Value) -> Option<DeltaChange> {
+    if item.get("@removed").is_some() {
+        let id = item.get("id")?.as_str()?.to_string();
+        return Some(DeltaChange::Removed(id));
+    }
+
+    item.get("id")?;
+    Some(DeltaChange::Upserted(item))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, net::TcpListener};
+
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn first_page(base_url: &str) -> HttpResponse {
+        HttpResponse::Ok().json(json!({
+            "value": [
+                { "id": "list-1", "displayName": "Groceries" },
+                { "id": "list-2", "displayName": "Work" }
+            ],
+            "@odata.nextLink": format!("{}/me/todo/lists/delta?$skiptoken=page-2", base_url)
+        }))
+    }
+
+    fn last_page(base_url: &str) -> HttpResponse {
+        HttpResponse::Ok().json(json!({
+            "value": [
+                { "id": "list-2", "@removed": { "reason": "deleted" } },
+                { "displayName": "Item without id" }
+            ],
+            "@odata.deltaLink": format!("{}/me/todo/lists/delta?$deltatoken=round-2", base_url)
+        }))
+    }
+
+    /// Delta round of the todo lists, spread over two pages
+    async fn delta(
+        base_url: web::Data<String>,
+        query: web::Query<HashMap<String, String>>,
+    ) -> HttpResponse {
+        match query.get("$skiptoken").map(String::as_str) {
+            Some("page-2") => last_page(&base_url),
+            _ => first_page(&base_url),
+        }
+    }
+
+    async fn gone() -> HttpResponse {
+        HttpResponse::Gone().json(json!({ "error": { "code": "syncStateNotFound" } }))
+    }
+
+    async fn sync_state_not_found() -> HttpResponse {
+        HttpResponse::BadRequest().json(json!({
+            "error": { "code": "syncStateNotFound", "message": "Sync state not found" }
+        }))
+    }
+
+    fn start_delta_server() -> DeltaMicrosoftGraphApi {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let data = web::Data::new(base_url.clone());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/me/todo/lists/delta", web::get().to(delta))
+                .route("/gone", web::get().to(gone))
+                .route("/sync-state-not-found", web::get().to(sync_state_not_found))
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap()
+        .run();
+        actix_web::rt::spawn(server);
+
+        DeltaMicrosoftGraphApi {
+            client: Client::new(),
+            base_url,
+        }
+    }
+
+    #[actix_web::test]
+    async fn splits_the_round_into_changes_and_the_next_delta_link() {
+        let graph_client = start_delta_server();
+
+        let response = graph_client
+            .get_changes(&graph_client.todo_lists_delta_url(), "token")
+            .await
+            .unwrap();
+
+        let delta_changes = response.data.unwrap();
+        assert_eq!(
+            delta_changes.delta_link,
+            format!(
+                "{}/me/todo/lists/delta?$deltatoken=round-2",
+                graph_client.base_url
+            )
+        );
+
+        // The item without id is skipped
+        assert_eq!(delta_changes.changes.len(), 3);
+        assert!(
+            matches!(&delta_changes.changes[0], DeltaChange::Upserted(value) if value["id"] == "list-1")
+        );
+        assert!(
+            matches!(&delta_changes.changes[1], DeltaChange::Upserted(value) if value["id"] == "list-2")
+        );
+        assert!(matches!(&delta_changes.changes[2], DeltaChange::Removed(id) if id == "list-2"));
+    }
+
+    #[actix_web::test]
+    async fn expired_delta_links_require_a_full_sync() {
+        let graph_client = start_delta_server();
+
+        for path in ["/gone", "/sync-state-not-found"] {
+            let url = format!("{}{}", graph_client.base_url, path);
+
+            let response = graph_client.get_changes(&url, "token").await.unwrap();
+
+            assert!(!response.success);
+            assert_eq!(response.errors, vec![DELTA_TOKEN_EXPIRED]);
+        }
+    }
+
+    #[actix_web::test]
+    async fn rejects_delta_links_of_other_hosts() {
+        let graph_client = start_delta_server();
+
+        let response = graph_client
+            .get_changes("https://example.com/me/todo/lists/delta", "token")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.errors,
+            vec!["Unexpected Microsoft Graph delta link"]
+        );
+    }
+
+    #[test]
+    fn only_sync_state_errors_expire_the_delta_link() {
+        assert!(is_expired_delta_link(410, ""));
+        assert!(is_expired_delta_link(
+            400,
+            r#"{"error":{"code":"resyncRequired"}}"#
+        ));
+        assert!(!is_expired_delta_link(
+            400,
+            r#"{"error":{"code":"invalidRequest"}}"#
+        ));
+        assert!(!is_expired_delta_link(500, "not json"));
+        assert!(!is_expired_delta_link(
+            200,
+            r#"{"error":{"code":"syncStateNotFound"}}"#
+        ));
+    }
+}
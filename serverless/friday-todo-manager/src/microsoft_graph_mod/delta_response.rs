@@ -0,0 +1,13 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One page of a delta query. Items are kept raw because removed items only carry their
+/// `id` and an `@removed` annotation.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeltaResponse {
+    pub value: Vec<Value>,
+    #[serde(rename = "@odata.nextLink")]
+    pub odata_next_link: Option<String>,
+    #[serde(rename = "@odata.deltaLink")]
+    pub odata_delta_link: Option<String>,
+}
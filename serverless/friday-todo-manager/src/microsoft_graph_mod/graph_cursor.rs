@@ -0,0 +1,101 @@
+use crate::todo_mod::task_cursor::TaskCursor;
+
+/// Paging position of a Graph `@odata.nextLink`: its `$top` and `$skip`/`$skiptoken`. `None`
+/// when the link carries neither `$skip` nor `$skiptoken`.
+pub fn cursor_from_next_link(next_link: &str) -> Option<TaskCursor> {
+    let url = reqwest::Url::parse(next_link).ok()?;
+
+    let mut cursor = TaskCursor::default();
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "$top" => cursor.top = Some(value.parse().ok()?),
+            "$skip" => cursor.skip = Some(value.parse().ok()?),
+            "$skiptoken" => cursor.skip_token = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    cursor.is_valid().then_some(cursor)
+}
+
+/// `$top`, `$skip` and `$skiptoken` query parameters of a cursor, percent-encoded
+pub fn to_graph_query(cursor: &TaskCursor) -> Vec<String> {
+    let mut query = Vec::new();
+    if let Some(top) = cursor.top {
+        query.push(format!("$top={}", top));
+    }
+    if let Some(skip) = cursor.skip {
+        query.push(format!("$skip={}", skip));
+    }
+    if let Some(skip_token) = &cursor.skip_token {
+        query.push(format!("$skiptoken={}", percent_encode(skip_token)));
+    }
+    query
+}
+
+/// Encodes every byte outside the RFC 3986 unreserved set
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS_URL: &str = "https://graph.microsoft.com/v1.0/me/todo/lists/AAMkAD=/tasks";
+
+    #[test]
+    fn keeps_only_the_paging_position_of_the_next_link() {
+        let next_link = format!(
+            "{}?$top=2&$skip=4&$filter=status%20eq%20'completed'&$orderby=createdDateTime",
+            TASKS_URL
+        );
+
+        let cursor = cursor_from_next_link(&next_link).unwrap();
+
+        assert_eq!(
+            cursor,
+            TaskCursor {
+                top: Some(2),
+                skip: Some(4),
+                skip_token: None
+            }
+        );
+        assert_eq!(to_graph_query(&cursor), vec!["$top=2", "$skip=4"]);
+    }
+
+    #[test]
+    fn skip_tokens_survive_the_opaque_encoding() {
+        let next_link = format!("{}?$skiptoken=X%27ab%2Bc%26%24top%3D1%27", TASKS_URL);
+        let cursor = cursor_from_next_link(&next_link).unwrap();
+
+        let decoded = TaskCursor::decode(&cursor.encode()).unwrap();
+
+        // The decoded token is encoded again, so it cannot add query parameters
+        assert_eq!(
+            to_graph_query(&decoded),
+            vec!["$skiptoken=X%27ab%2Bc%26%24top%3D1%27"]
+        );
+    }
+
+    #[test]
+    fn next_links_without_a_position_have_no_cursor() {
+        assert_eq!(
+            cursor_from_next_link(&format!("{}?$top=2", TASKS_URL)),
+            None
+        );
+        assert_eq!(
+            cursor_from_next_link(&format!("{}?$top=2&$skip=abc", TASKS_URL)),
+            None
+        );
+        assert_eq!(cursor_from_next_link("not a url"), None);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::microsoft_graph_mod::windows_time_zones::WINDOWS_TIME_ZONES;
+use crate::ENV_CONFIG;
+use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+
+/// Resolves an IANA (`America/Sao_Paulo`) or Windows (`E. South America Standard Time`)
+/// time zone name
+pub fn resolve_time_zone(name: &str) -> Result<Tz, String> {
+    let name = name.trim();
+
+    if let Ok(time_zone) = name.parse::<Tz>() {
+        return Ok(time_zone);
+    }
+
+    WINDOWS_TIME_ZONES
+        .iter()
+        .find(|(windows_name, _)| windows_name.eq_ignore_ascii_case(name))
+        .and_then(|(_, iana_name)| iana_name.parse::<Tz>().ok())
+        .ok_or_else(|| format!("Unknown time zone: {}", name))
+}
+
+/// Time zone the user's dates are written in, from `USER_TIME_ZONE`
+pub fn user_time_zone() -> Tz {
+    resolve_time_zone(&ENV_CONFIG.user_time_zone)
+        .unwrap_or_else(|e| panic!("Invalid USER_TIME_ZONE: {}", e))
+}
+
+/// Parses a Graph `dateTimeTimeZone`: a wall-clock `dateTime` without offset in `timeZone`
+pub fn parse_graph_date_time(date_time: &str, time_zone: &str) -> Result<DateTime<Utc>, String> {
+    // Some endpoints already send an offset, which wins over the time zone name
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(date_time) {
+        return Ok(parsed.with_timezone(&Utc));
+    }
+
+    let time_zone = resolve_time_zone(time_zone)?;
+    let local = NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M:%S%.f")
+        .map_err(|_| format!("Invalid date time: {}", date_time))?;
+
+    match time_zone.from_local_datetime(&local) {
+        LocalResult::Single(parsed) => Ok(parsed.with_timezone(&Utc)),
+        // Repeated hour when clocks go back: take the first occurrence
+        LocalResult::Ambiguous(earliest, _) => Ok(earliest.with_timezone(&Utc)),
+        LocalResult::None => Err(format!(
+            "Date time {} does not exist in time zone {}",
+            date_time,
+            time_zone.name()
+        )),
+    }
+}
+
+/// Parses a Graph timestamp in UTC such as `createdDateTime`
+pub fn parse_graph_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .map_err(|_| format!("Invalid timestamp: {}", value))
+}
+
+/// Builds a Graph `dateTimeTimeZone` in the user's time zone, so that Microsoft To Do shows
+/// due dates on the user's calendar day
+pub fn to_graph_date_time(date_time: &DateTime<Utc>) -> Value {
+    let time_zone = user_time_zone();
+
+    json!({
+        "dateTime": date_time
+            .with_timezone(&time_zone)
+            .naive_local()
+            .format("%Y-%m-%dT%H:%M:%S")
+            .to_string(),
+        "timeZone": time_zone.name()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn resolves_iana_and_windows_time_zone_names() {
+        assert_eq!(
+            resolve_time_zone("America/Sao_Paulo"),
+            Ok(chrono_tz::America::Sao_Paulo)
+        );
+        assert_eq!(
+            resolve_time_zone(" e. south america standard time "),
+            Ok(chrono_tz::America::Sao_Paulo)
+        );
+        assert!(resolve_time_zone("Mars Standard Time").is_err());
+    }
+
+    #[test]
+    fn parses_wall_clock_date_times_in_their_time_zone() {
+        assert_eq!(
+            parse_graph_date_time(
+                "2024-05-10T09:00:00.0000000",
+                "E. South America Standard Time"
+            ),
+            Ok(utc("2024-05-10T12:00:00Z"))
+        );
+        assert_eq!(
+            parse_graph_date_time("2024-05-10T09:00:00", "America/New_York"),
+            Ok(utc("2024-05-10T13:00:00Z"))
+        );
+        assert_eq!(
+            parse_graph_date_time("2024-05-10T09:00:00", "UTC"),
+            Ok(utc("2024-05-10T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn an_explicit_offset_wins_over_the_time_zone() {
+        assert_eq!(
+            parse_graph_date_time("2024-05-10T09:00:00-03:00", "Tokyo Standard Time"),
+            Ok(utc("2024-05-10T12:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn handles_daylight_saving_transitions() {
+        // Clocks skip from 02:00 to 03:00
+        assert!(parse_graph_date_time("2024-03-10T02:30:00", "Eastern Standard Time").is_err());
+
+        // 01:30 happens twice: the first one is still in EDT
+        assert_eq!(
+            parse_graph_date_time("2024-11-03T01:30:00", "Eastern Standard Time"),
+            Ok(utc("2024-11-03T05:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(parse_graph_date_time("10/05/2024 09:00", "UTC").is_err());
+        assert!(parse_graph_date_time("2024-02-30T09:00:00", "UTC").is_err());
+        assert!(parse_graph_date_time("2024-05-10T09:00:00", "Nowhere/Unknown").is_err());
+        assert!(parse_graph_timestamp("2024-05-10T09:00:00").is_err());
+        assert_eq!(
+            parse_graph_timestamp("2024-05-10T09:00:00.1234567Z"),
+            Ok(utc("2024-05-10T09:00:00.1234567Z"))
+        );
+    }
+
+    #[test]
+    fn writes_date_times_in_the_user_time_zone() {
+        let time_zone = user_time_zone();
+        let date_time = utc("2024-05-10T12:00:00Z");
+
+        let value = to_graph_date_time(&date_time);
+
+        assert_eq!(value["timeZone"], time_zone.name());
+        assert_eq!(
+            parse_graph_date_time(
+                value["dateTime"].as_str().unwrap(),
+                value["timeZone"].as_str().unwrap()
+            ),
+            Ok(date_time)
+        );
+    }
+}
@@ -6,6 +6,9 @@ use reqwest::Client;
 use serde_json::json;
 use tracing::{error, info};
 
+/// Upper bound of nextLink pages followed when fetching all todo lists
+const MAX_PAGES: usize = 100;
+
 #[derive(Debug)]
 pub struct MicrosoftGraphApi {
     client: Client,
@@ -20,6 +23,7 @@ impl MicrosoftGraphApi {
         }
     }
 
+    /// Fetches every todo list, following `@odata.nextLink` until the last page
     pub async fn get_todo_lists(
         &self,
         access_token: &str,
@@ -31,12 +35,66 @@ impl MicrosoftGraphApi {
             return Ok(BusinessResponse::error("Access token is required"));
         }
 
-        let url = format!("{}/me/todo/lists", self.base_url);
+        let mut todo_lists: Vec<TodoList> = Vec::new();
+        let mut next_url = Some(format!("{}/me/todo/lists", self.base_url));
+        let mut pages = 0;
+
+        while let Some(url) = next_url {
+            if pages == MAX_PAGES {
+                error!(
+                    "Data layer: Stopped following nextLink after {} pages of todo lists",
+                    MAX_PAGES
+                );
+                return Ok(BusinessResponse::error(
+                    "Too many pages returned by Microsoft Graph",
+                ));
+            }
+
+            let todo_lists_response = match self.get_todo_lists_page(&url, access_token).await? {
+                Ok(todo_lists_response) => todo_lists_response,
+                Err(error_response) => return Ok(error_response),
+            };
+            pages += 1;
+
+            match parse_todo_lists(todo_lists_response.value) {
+                Ok(page) => todo_lists.extend(page),
+                Err(error_response) => return Ok(error_response),
+            }
+
+            next_url = match todo_lists_response.odata_next_link {
+                Some(next_link) if next_link.starts_with(&self.base_url) => Some(next_link),
+                Some(next_link) => {
+                    error!("Data layer: Unexpected nextLink: {}", next_link);
+                    return Ok(BusinessResponse::error(
+                        "Unexpected Microsoft Graph pagination link",
+                    ));
+                }
+                None => None,
+            };
+        }
+
+        info!(
+            "Data layer: Successfully parsed {} todo lists from {} pages",
+            todo_lists.len(),
+            pages
+        );
+        Ok(BusinessResponse::success(todo_lists))
+    }
+
+    /// Fetches a single page of todo lists. The inner `Err` carries the business error to return.
+    async fn get_todo_lists_page(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<
+        Result<TodoListsResponse, BusinessResponse<Vec<TodoList>>>,
+        Box<dyn std::error::Error>,
+    > {
         info!("Data layer: Making GET request to: {}", url);
 
         let response = match self
             .client
-            .get(&url)
+            .get(url)
             .header("Authorization", format!("Bearer {}", access_token))
             .header("Content-Type", "application/json")
             .send()
@@ -57,9 +115,9 @@ impl MicrosoftGraphApi {
                 Ok(text) => text,
                 Err(e) => {
                     error!("Data layer: Failed to read response body: {}", e);
-                    return Ok(BusinessResponse::error(
+                    return Ok(Err(BusinessResponse::error(
                         "Failed to read Microsoft Graph response",
-                    ));
+                    )));
                 }
             };
 
@@ -69,27 +127,15 @@ impl MicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TodoListsResponse>(&response_text) {
-                Ok(todo_lists_response) => {
-                    let todo_lists: Vec<TodoList> = todo_lists_response
-                        .value
-                        .into_iter()
-                        .map(TodoList::from)
-                        .collect();
-
-                    info!(
-                        "Data layer: Successfully parsed {} todo lists",
-                        todo_lists.len()
-                    );
-                    Ok(BusinessResponse::success(todo_lists))
-                }
+                Ok(todo_lists_response) => Ok(Ok(todo_lists_response)),
                 Err(_e) => {
                     error!(
                         "Data layer: Response that failed to parse: {}",
                         response_text
                     );
-                    Ok(BusinessResponse::error(
+                    Ok(Err(BusinessResponse::error(
                         "Failed to parse Microsoft Graph response",
-                    ))
+                    )))
                 }
             }
         } else {
@@ -99,17 +145,17 @@ impl MicrosoftGraphApi {
                         "Data layer: Microsoft Graph API error ({}): {}",
                         status, error_text
                     );
-                    Ok(BusinessResponse::error(&format!(
+                    Ok(Err(BusinessResponse::error(&format!(
                         "Failed to get todo lists: {} - {}",
                         status, error_text
-                    )))
+                    ))))
                 }
                 Err(e) => {
                     error!("Data layer: Failed to read error response: {}", e);
-                    Ok(BusinessResponse::error(&format!(
+                    Ok(Err(BusinessResponse::error(&format!(
                         "Failed to get todo lists: HTTP {}",
                         status
-                    )))
+                    ))))
                 }
             }
         }
@@ -165,11 +211,22 @@ impl MicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TodoListResponse>(&response_text) {
-                Ok(list_response) => {
-                    let todo_list = TodoList::from(list_response);
-                    info!("Data layer: Successfully parsed todo list");
-                    Ok(BusinessResponse::success(todo_list))
-                }
+                Ok(list_response) => match TodoList::try_from(list_response) {
+                    Ok(todo_list) => {
+                        info!("Data layer: Successfully parsed todo list");
+                        Ok(BusinessResponse::success(todo_list))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!("Data layer: Failed to parse todo list response: {}", e);
                     error!(
@@ -265,11 +322,22 @@ impl MicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TodoListResponse>(&response_text) {
-                Ok(list_response) => {
-                    let todo_list = TodoList::from(list_response);
-                    info!("Data layer: Successfully created todo list");
-                    Ok(BusinessResponse::success(todo_list))
-                }
+                Ok(list_response) => match TodoList::try_from(list_response) {
+                    Ok(todo_list) => {
+                        info!("Data layer: Successfully created todo list");
+                        Ok(BusinessResponse::success(todo_list))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!(
                         "Data layer: Failed to parse create todo list response: {}",
@@ -365,11 +433,22 @@ impl MicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TodoListResponse>(&response_text) {
-                Ok(list_response) => {
-                    let todo_list = TodoList::from(list_response);
-                    info!("Data layer: Successfully updated todo list");
-                    Ok(BusinessResponse::success(todo_list))
-                }
+                Ok(list_response) => match TodoList::try_from(list_response) {
+                    Ok(todo_list) => {
+                        info!("Data layer: Successfully updated todo list");
+                        Ok(BusinessResponse::success(todo_list))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid todo list returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!(
                         "Data layer: Failed to parse update todo list response: {}",
@@ -479,3 +558,23 @@ impl MicrosoftGraphApi {
         }
     }
 }
+
+/// Converts a page of Graph todo lists, failing the whole page on the first invalid list
+fn parse_todo_lists(
+    list_responses: Vec<TodoListResponse>,
+) -> Result<Vec<TodoList>, BusinessResponse<Vec<TodoList>>> {
+    list_responses
+        .into_iter()
+        .map(TodoList::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!(
+                "Data layer: Invalid todo list returned by Microsoft Graph: {}",
+                e
+            );
+            BusinessResponse::error(&format!(
+                "Invalid todo list returned by Microsoft Graph: {}",
+                e
+            ))
+        })
+}
@@ -0,0 +1,459 @@
+use crate::business_response::BusinessResponse;
+use crate::cache_mod::task_cache_logic;
+use crate::microsoft_graph_mod::lists_microsoft_graph_api::MicrosoftGraphApi;
+use crate::microsoft_graph_mod::tasks_microsoft_graph_api::TasksMicrosoftGraphApi;
+use crate::oauth_mod::oauth_api::OAuthApi;
+use crate::provider_mod::todo_provider::TodoProvider;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::task::{
+    CreateTaskRequest, Task, TaskBatchItemResult, TaskFilterQuery, TaskPage, TaskPageQuery,
+    UpdateTaskRequest,
+};
+use crate::todo_mod::task_filter::{apply_graph_filters, apply_local_filters};
+use crate::todo_mod::todo_list::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use async_trait::async_trait;
+use tracing::{error, warn};
+
+/// Microsoft To Do through Microsoft Graph. Reads are served from the task cache when it is
+/// enabled and writes go through to it.
+#[derive(Debug)]
+pub struct MicrosoftGraphProvider {
+    lists_client: MicrosoftGraphApi,
+    tasks_client: TasksMicrosoftGraphApi,
+}
+
+impl MicrosoftGraphProvider {
+    pub fn new() -> Self {
+        Self {
+            lists_client: MicrosoftGraphApi::new(),
+            tasks_client: TasksMicrosoftGraphApi::new(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl TodoProvider for MicrosoftGraphProvider {
+    async fn get_todo_lists(
+        &self,
+    ) -> Result<BusinessResponse<Vec<TodoList>>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        if let Some(todo_lists) = task_cache_logic::get_todo_lists(&access_token).await {
+            return Ok(BusinessResponse::success(todo_lists));
+        }
+
+        self.lists_client.get_todo_lists(&access_token).await
+    }
+
+    async fn get_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        // A list missing from the cache may have been created since the last sync
+        if let Some(todo_list) = task_cache_logic::get_todo_lists(&access_token)
+            .await
+            .and_then(|todo_lists| {
+                todo_lists
+                    .into_iter()
+                    .find(|todo_list| todo_list.id == list_id)
+            })
+        {
+            return Ok(BusinessResponse::success(todo_list));
+        }
+
+        self.lists_client
+            .get_todo_list(list_id, &access_token)
+            .await
+    }
+
+    async fn create_todo_list(
+        &self,
+        request: CreateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let result = self
+            .lists_client
+            .create_todo_list(request, &access_token)
+            .await?;
+        if let Some(todo_list) = result.data.as_ref().filter(|_| result.success) {
+            task_cache_logic::store_todo_list(todo_list).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn update_todo_list(
+        &self,
+        request: UpdateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let result = self
+            .lists_client
+            .update_todo_list(request, &access_token)
+            .await?;
+        if let Some(todo_list) = result.data.as_ref().filter(|_| result.success) {
+            task_cache_logic::store_todo_list(todo_list).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn delete_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let result = self
+            .lists_client
+            .delete_todo_list(list_id, &access_token)
+            .await?;
+        if result.success {
+            task_cache_logic::remove_todo_list(list_id).await;
+        }
+
+        Ok(result)
+    }
+
+    /// Unpaged requests are served from the cache when it is enabled, applying every option
+    /// here. The options Graph does not support are rejected on paged requests: applied
+    /// within each page, they would return short pages and a wrong order across pages.
+    async fn get_tasks(
+        &self,
+        list_id: &str,
+        page: &TaskPageQuery,
+        filter: &TaskFilterQuery,
+    ) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>> {
+        if page.is_paged() && filter.has_local_options() {
+            warn!(
+                "Provider layer: Local filters requested on a paged listing of list {}",
+                list_id
+            );
+            return Ok(BusinessResponse::error(
+                "due_before, due_after, search and order_by=due_date_time cannot be combined with paging on Microsoft To Do lists",
+            ));
+        }
+
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        if !page.is_paged() {
+            if let Some(mut tasks) = task_cache_logic::get_tasks(list_id, &access_token).await {
+                apply_graph_filters(&mut tasks, filter);
+                apply_local_filters(&mut tasks, filter);
+                return Ok(BusinessResponse::success(TaskPage {
+                    tasks,
+                    next_cursor: None,
+                }));
+            }
+        }
+
+        let mut response = self
+            .tasks_client
+            .get_tasks(list_id, page, filter, &access_token)
+            .await?;
+        if let Some(task_page) = response.data.as_mut() {
+            apply_local_filters(&mut task_page.tasks, filter);
+        }
+
+        Ok(response)
+    }
+
+    async fn get_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        include_checklist: bool,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        // Checklists are not cached; a task missing from the cache may be newer than the last sync
+        if !include_checklist {
+            if let Some(task) = task_cache_logic::get_tasks(list_id, &access_token)
+                .await
+                .and_then(|tasks| tasks.into_iter().find(|task| task.id == task_id))
+            {
+                return Ok(BusinessResponse::success(task));
+            }
+        }
+
+        self.tasks_client
+            .get_task(list_id, task_id, include_checklist, &access_token)
+            .await
+    }
+
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let result = self
+            .tasks_client
+            .create_task(request, &access_token)
+            .await?;
+        if let Some(task) = result.data.as_ref().filter(|_| result.success) {
+            task_cache_logic::store_task(&list_id, task).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn update_task(
+        &self,
+        request: UpdateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let result = self
+            .tasks_client
+            .update_task(request, &access_token)
+            .await?;
+        if let Some(task) = result.data.as_ref().filter(|_| result.success) {
+            task_cache_logic::store_task(&list_id, task).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn delete_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let result = self
+            .tasks_client
+            .delete_task(list_id, task_id, &access_token)
+            .await?;
+        if result.success {
+            task_cache_logic::remove_task(list_id, task_id).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn get_checklist_items(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.tasks_client
+            .get_checklist_items(list_id, task_id, &access_token)
+            .await
+    }
+
+    async fn get_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.tasks_client
+            .get_checklist_item(list_id, task_id, checklist_item_id, &access_token)
+            .await
+    }
+
+    async fn create_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &CreateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.tasks_client
+            .create_checklist_item(list_id, task_id, request, &access_token)
+            .await
+    }
+
+    async fn update_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        request: &UpdateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.tasks_client
+            .update_checklist_item(list_id, task_id, checklist_item_id, request, &access_token)
+            .await
+    }
+
+    async fn delete_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        self.tasks_client
+            .delete_checklist_item(list_id, task_id, checklist_item_id, &access_token)
+            .await
+    }
+
+    /// Creates the tasks through `$batch`, 20 per call
+    async fn create_tasks(
+        &self,
+        list_id: &str,
+        requests: Vec<(usize, CreateTaskRequest)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let results = self
+            .tasks_client
+            .create_tasks(list_id, &requests, &access_token)
+            .await;
+        store_batch_tasks(list_id, &results).await;
+
+        Ok(BusinessResponse::success(results))
+    }
+
+    /// Updates the tasks through `$batch`, 20 per call
+    async fn update_tasks(
+        &self,
+        list_id: &str,
+        requests: Vec<(usize, UpdateTaskRequest)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let results = self
+            .tasks_client
+            .update_tasks(list_id, &requests, &access_token)
+            .await;
+        store_batch_tasks(list_id, &results).await;
+
+        Ok(BusinessResponse::success(results))
+    }
+
+    /// Deletes the tasks through `$batch`, 20 per call
+    async fn delete_tasks(
+        &self,
+        list_id: &str,
+        task_ids: Vec<(usize, String)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let access_token = match get_access_token().await {
+            Ok(token) => token,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        let results = self
+            .tasks_client
+            .delete_tasks(list_id, &task_ids, &access_token)
+            .await;
+        for result in results.iter().filter(|result| result.success) {
+            if let Some(task_id) = &result.task_id {
+                task_cache_logic::remove_task(list_id, task_id).await;
+            }
+        }
+
+        Ok(BusinessResponse::success(results))
+    }
+}
+
+/// Writes the tasks returned by a bulk operation through to the cache
+async fn store_batch_tasks(list_id: &str, results: &[TaskBatchItemResult]) {
+    for task in results.iter().filter_map(|result| result.task.as_ref()) {
+        task_cache_logic::store_task(list_id, task).await;
+    }
+}
+
+async fn get_access_token<T>() -> Result<String, BusinessResponse<T>> {
+    let oauth_client = OAuthApi::new();
+    oauth_client.generate_access_token().await.map_err(|e| {
+        error!(
+            "Provider layer: Failed to get access token from OAuth manager: {}",
+            e
+        );
+        BusinessResponse::error("Failed to authenticate with Microsoft Graph")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo_mod::task::TaskOrderBy;
+
+    #[actix_web::test]
+    async fn paged_listings_reject_the_options_graph_cannot_apply() {
+        let provider = MicrosoftGraphProvider::new();
+        let page = TaskPageQuery {
+            top: Some(10),
+            ..Default::default()
+        };
+        let filter = TaskFilterQuery {
+            order_by: Some(TaskOrderBy::DueDateTime),
+            ..Default::default()
+        };
+
+        let response = provider.get_tasks("AAMkAD=", &page, &filter).await.unwrap();
+
+        assert!(!response.success);
+        assert!(response.errors[0].contains("cannot be combined with paging"));
+    }
+}
@@ -1,6 +1,20 @@
+pub mod batch_microsoft_graph_api;
+pub mod batch_response;
+pub mod change_notification;
+pub mod checklist_item_response;
+pub mod checklist_items_response;
+pub mod delta_microsoft_graph_api;
+pub mod delta_response;
+pub mod graph_cursor;
+pub mod graph_date_time;
 pub mod lists_microsoft_graph_api;
+pub mod microsoft_graph_provider;
+pub mod recurrence_response;
+pub mod subscription_response;
+pub mod subscriptions_microsoft_graph_api;
 pub mod task_response;
 pub mod tasks_microsoft_graph_api;
 pub mod tasks_response;
 pub mod todo_list_response;
 pub mod todo_lists_response;
+pub mod windows_time_zones;
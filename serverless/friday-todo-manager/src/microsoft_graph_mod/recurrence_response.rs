@@ -0,0 +1,40 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PatternedRecurrenceResponse {
+    pub pattern: RecurrencePatternResponse,
+    pub range: RecurrenceRangeResponse,
+}
+
+/// Graph always sends every field; the ones that do not apply to the pattern type come
+/// zeroed (`0`, empty list, `"first"`)
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecurrencePatternResponse {
+    #[serde(rename = "type")]
+    pub pattern_type: String,
+    pub interval: u32,
+    #[serde(default)]
+    pub month: u32,
+    #[serde(rename = "dayOfMonth", default)]
+    pub day_of_month: u32,
+    #[serde(rename = "daysOfWeek", default)]
+    pub days_of_week: Vec<String>,
+    #[serde(rename = "firstDayOfWeek")]
+    pub first_day_of_week: Option<String>,
+    pub index: Option<String>,
+}
+
+/// Graph sends `"0001-01-01"` as the end date and `0` occurrences when they do not apply
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecurrenceRangeResponse {
+    #[serde(rename = "type")]
+    pub range_type: String,
+    #[serde(rename = "startDate")]
+    pub start_date: String,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    #[serde(rename = "numberOfOccurrences", default)]
+    pub number_of_occurrences: u32,
+    #[serde(rename = "recurrenceTimeZone")]
+    pub recurrence_time_zone: Option<String>,
+}
@@ -0,0 +1,21 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubscriptionResponse {
+    pub id: String,
+    pub resource: String,
+    /// Comma-separated list, e.g. `created,updated,deleted`
+    #[serde(rename = "changeType")]
+    pub change_type: String,
+    #[serde(rename = "notificationUrl")]
+    pub notification_url: String,
+    #[serde(rename = "expirationDateTime")]
+    pub expiration_date_time: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubscriptionsResponse {
+    pub value: Vec<SubscriptionResponse>,
+    #[serde(rename = "@odata.nextLink")]
+    pub odata_next_link: Option<String>,
+}
@@ -0,0 +1,343 @@
+use crate::business_response::BusinessResponse;
+use crate::microsoft_graph_mod::subscription_response::{
+    SubscriptionResponse, SubscriptionsResponse,
+};
+use crate::subscription_mod::subscription::{Subscription, SubscriptionChangeType};
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{Client, Response};
+use serde_json::json;
+use tracing::{error, info};
+
+/// Upper bound of nextLink pages followed when fetching all subscriptions
+const MAX_PAGES: usize = 100;
+
+#[derive(Debug)]
+pub struct SubscriptionsMicrosoftGraphApi {
+    client: Client,
+    base_url: String,
+}
+
+impl SubscriptionsMicrosoftGraphApi {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: "https://graph.microsoft.com/v1.0".to_string(),
+        }
+    }
+
+    /// Subscribes to changes of the tasks of a list. Lifecycle notifications are sent to the
+    /// same URL.
+    pub async fn create_subscription(
+        &self,
+        list_id: &str,
+        change_types: &[SubscriptionChangeType],
+        notification_url: &str,
+        client_state: &str,
+        expiration_date_time: DateTime<Utc>,
+        access_token: &str,
+    ) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Creating subscription on tasks of list: {}",
+            list_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let change_type = change_types
+            .iter()
+            .map(|change_type| change_type.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let body = json!({
+            "changeType": change_type,
+            "notificationUrl": notification_url,
+            "lifecycleNotificationUrl": notification_url,
+            "resource": format!("/me/todo/lists/{}/tasks", list_id),
+            "expirationDateTime": expiration_date_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "clientState": client_state,
+        });
+
+        let url = format!("{}/subscriptions", self.base_url);
+        info!("Data layer: Making POST request to: {}", url);
+
+        let response = match self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        read_subscription(response, "create subscription").await
+    }
+
+    /// Fetches every subscription of the application, following `@odata.nextLink`
+    pub async fn get_subscriptions(
+        &self,
+        access_token: &str,
+    ) -> Result<BusinessResponse<Vec<Subscription>>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching all subscriptions from Microsoft Graph");
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let mut subscriptions = Vec::new();
+        let mut next_url = Some(format!("{}/subscriptions", self.base_url));
+        let mut pages = 0;
+
+        while let Some(url) = next_url {
+            if pages == MAX_PAGES {
+                error!(
+                    "Data layer: Stopped following nextLink after {} pages of subscriptions",
+                    MAX_PAGES
+                );
+                return Ok(BusinessResponse::error(
+                    "Too many pages returned by Microsoft Graph",
+                ));
+            }
+
+            info!("Data layer: Making GET request to: {}", url);
+            let response = match self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", access_token))
+                .header("Content-Type", "application/json")
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    error!("Data layer: HTTP request failed: {}", e);
+                    return Err(e.into());
+                }
+            };
+            pages += 1;
+
+            let response_text = match read_success_text(response, "get subscriptions").await {
+                Ok(response_text) => response_text,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            let subscriptions_response =
+                match serde_json::from_str::<SubscriptionsResponse>(&response_text) {
+                    Ok(subscriptions_response) => subscriptions_response,
+                    Err(e) => {
+                        error!("Data layer: Failed to parse subscriptions response: {}", e);
+                        return Ok(BusinessResponse::error(
+                            "Failed to parse Microsoft Graph response",
+                        ));
+                    }
+                };
+
+            for subscription_response in subscriptions_response.value {
+                match Subscription::try_from(subscription_response) {
+                    Ok(subscription) => subscriptions.push(subscription),
+                    Err(e) => {
+                        error!("Data layer: Invalid subscription: {}", e);
+                        return Ok(BusinessResponse::error(&format!(
+                            "Invalid subscription returned by Microsoft Graph: {}",
+                            e
+                        )));
+                    }
+                }
+            }
+
+            next_url = match subscriptions_response.odata_next_link {
+                Some(next_link) if next_link.starts_with(&self.base_url) => Some(next_link),
+                Some(next_link) => {
+                    error!("Data layer: Unexpected nextLink: {}", next_link);
+                    return Ok(BusinessResponse::error(
+                        "Unexpected Microsoft Graph pagination link",
+                    ));
+                }
+                None => None,
+            };
+        }
+
+        info!(
+            "Data layer: Successfully parsed {} subscriptions",
+            subscriptions.len()
+        );
+        Ok(BusinessResponse::success(subscriptions))
+    }
+
+    /// Extends the expiration of a subscription; also reauthorizes it
+    pub async fn renew_subscription(
+        &self,
+        subscription_id: &str,
+        expiration_date_time: DateTime<Utc>,
+        access_token: &str,
+    ) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Renewing subscription with ID: {}",
+            subscription_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let body = json!({
+            "expirationDateTime": expiration_date_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        });
+
+        let url = format!("{}/subscriptions/{}", self.base_url, subscription_id);
+        info!("Data layer: Making PATCH request to: {}", url);
+
+        let response = match self
+            .client
+            .patch(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        read_subscription(response, "renew subscription").await
+    }
+
+    pub async fn delete_subscription(
+        &self,
+        subscription_id: &str,
+        access_token: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Deleting subscription with ID: {}",
+            subscription_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let url = format!("{}/subscriptions/{}", self.base_url, subscription_id);
+        info!("Data layer: Making DELETE request to: {}", url);
+
+        let response = match self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        match read_success_text(response, "delete subscription").await {
+            Ok(_) => {
+                info!("Data layer: Successfully deleted subscription");
+                Ok(BusinessResponse::success(
+                    "Subscription deleted successfully".to_string(),
+                ))
+            }
+            Err(error_response) => Ok(error_response),
+        }
+    }
+}
+
+async fn read_subscription(
+    response: Response,
+    action: &str,
+) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+    let response_text = match read_success_text(response, action).await {
+        Ok(response_text) => response_text,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    let subscription_response = match serde_json::from_str::<SubscriptionResponse>(&response_text) {
+        Ok(subscription_response) => subscription_response,
+        Err(e) => {
+            error!("Data layer: Failed to parse subscription response: {}", e);
+            error!(
+                "Data layer: Response that failed to parse: {}",
+                response_text
+            );
+            return Ok(BusinessResponse::error(
+                "Failed to parse Microsoft Graph response",
+            ));
+        }
+    };
+
+    match Subscription::try_from(subscription_response) {
+        Ok(subscription) => {
+            info!(
+                "Data layer: Subscription {} expires at {}",
+                subscription.id, subscription.expiration_date_time
+            );
+            Ok(BusinessResponse::success(subscription))
+        }
+        Err(e) => {
+            error!("Data layer: Invalid subscription: {}", e);
+            Ok(BusinessResponse::error(&format!(
+                "Invalid subscription returned by Microsoft Graph: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Body of a successful response; otherwise the business error to return, "Subscription not
+/// found" on 404
+async fn read_success_text<T>(
+    response: Response,
+    action: &str,
+) -> Result<String, BusinessResponse<T>> {
+    let status = response.status();
+    info!("Data layer: Received response with status: {}", status);
+
+    if status == 404 {
+        info!("Data layer: Subscription not found");
+        return Err(BusinessResponse::error("Subscription not found"));
+    }
+
+    let response_text = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Data layer: Failed to read response body: {}", e);
+            return Err(BusinessResponse::error(&format!(
+                "Failed to {}: HTTP {}",
+                action, status
+            )));
+        }
+    };
+
+    if !status.is_success() {
+        error!(
+            "Data layer: Microsoft Graph API error ({}): {}",
+            status, response_text
+        );
+        return Err(BusinessResponse::error(&format!(
+            "Failed to {}: {} - {}",
+            action, status, response_text
+        )));
+    }
+
+    Ok(response_text)
+}
@@ -1,3 +1,5 @@
+use crate::microsoft_graph_mod::checklist_item_response::ChecklistItemResponse;
+use crate::microsoft_graph_mod::recurrence_response::PatternedRecurrenceResponse;
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -13,10 +15,16 @@ pub struct TaskResponse {
     pub reminder_date_time: Option<TaskDateTimeResponse>,
     #[serde(rename = "dueDateTime")]
     pub due_date_time: Option<TaskDateTimeResponse>,
+    pub recurrence: Option<PatternedRecurrenceResponse>,
+    #[serde(rename = "completedDateTime")]
+    pub completed_date_time: Option<TaskDateTimeResponse>,
     #[serde(rename = "createdDateTime")]
     pub created_date_time: String,
     #[serde(rename = "lastModifiedDateTime")]
     pub last_modified_date_time: String,
+    /// Only present when requested with `$expand=checklistItems`
+    #[serde(rename = "checklistItems")]
+    pub checklist_items: Option<Vec<ChecklistItemResponse>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -32,4 +40,4 @@ pub struct TaskDateTimeResponse {
     pub date_time: String,
     #[serde(rename = "timeZone")]
     pub time_zone: String,
-}
\ No newline at end of file
+}
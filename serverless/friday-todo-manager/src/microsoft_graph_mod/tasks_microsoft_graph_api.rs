@@ -1,11 +1,28 @@
 use crate::business_response::BusinessResponse;
+use crate::microsoft_graph_mod::batch_microsoft_graph_api::{BatchMicrosoftGraphApi, BatchRequest};
+use crate::microsoft_graph_mod::batch_response::BatchResponseItem;
+use crate::microsoft_graph_mod::checklist_item_response::ChecklistItemResponse;
+use crate::microsoft_graph_mod::checklist_items_response::ChecklistItemsResponse;
+use crate::microsoft_graph_mod::graph_cursor::{cursor_from_next_link, to_graph_query};
+use crate::microsoft_graph_mod::graph_date_time::{to_graph_date_time, user_time_zone};
 use crate::microsoft_graph_mod::task_response::TaskResponse;
 use crate::microsoft_graph_mod::tasks_response::TasksResponse;
-use crate::todo_mod::task::{CreateTaskRequest, Task, UpdateTaskRequest};
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::recurrence::TaskRecurrence;
+use crate::todo_mod::task::{
+    CreateTaskRequest, SortOrder, Task, TaskBatchItemResult, TaskFilterQuery, TaskImportance,
+    TaskOrderBy, TaskPage, TaskPageQuery, TaskStatus, UpdateTaskRequest,
+};
+use crate::todo_mod::task_cursor::TaskCursor;
 use reqwest::Client;
-use serde_json::json;
+use serde_json::{json, Value};
 use tracing::{error, info};
 
+/// Upper bound of nextLink pages followed when fetching a whole list
+const MAX_PAGES: usize = 100;
+
 #[derive(Debug)]
 pub struct TasksMicrosoftGraphApi {
     client: Client,
@@ -20,12 +37,18 @@ impl TasksMicrosoftGraphApi {
         }
     }
 
+    /// Fetches the tasks of a list. Without paging options every `@odata.nextLink` is
+    /// followed and the whole list is returned; otherwise a single page is returned with
+    /// the cursor of the next one. Only the filters Graph supports are sent, see
+    /// `TaskFilterQuery`.
     pub async fn get_tasks(
         &self,
         list_id: &str,
+        page: &TaskPageQuery,
+        filter: &TaskFilterQuery,
         access_token: &str,
-    ) -> Result<BusinessResponse<Vec<Task>>, Box<dyn std::error::Error>> {
-        info!("Data layer: Fetching all tasks from list ID: {}", list_id);
+    ) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>> {
+        info!("Data layer: Fetching tasks from list ID: {}", list_id);
 
         if access_token.trim().is_empty() {
             error!("Data layer: Access token is empty");
@@ -37,12 +60,142 @@ impl TasksMicrosoftGraphApi {
             return Ok(BusinessResponse::error("List ID is required"));
         }
 
-        let url = format!("{}/me/todo/lists/{}/tasks", self.base_url, list_id);
+        let tasks_url = format!("{}/me/todo/lists/{}/tasks", self.base_url, list_id);
+
+        // A cursor only carries the paging position; filters and ordering come from the request
+        let position = match &page.cursor {
+            Some(cursor) => match TaskCursor::decode(cursor) {
+                Some(position) => position,
+                None => {
+                    error!("Data layer: Invalid cursor for list: {}", list_id);
+                    return Ok(BusinessResponse::error("Invalid cursor"));
+                }
+            },
+            None => TaskCursor {
+                top: page.top,
+                skip: page.skip,
+                skip_token: None,
+            },
+        };
+
+        let mut query = to_graph_query(&position);
+        // The expressions are built from fixed names and values, only spaces need encoding
+        if let Some(graph_filter) = build_graph_filter(filter) {
+            query.push(format!("$filter={}", graph_filter.replace(' ', "%20")));
+        }
+        if let Some(graph_order_by) = build_graph_order_by(filter) {
+            query.push(format!("$orderby={}", graph_order_by.replace(' ', "%20")));
+        }
+
+        let url = if query.is_empty() {
+            tasks_url
+        } else {
+            format!("{}?{}", tasks_url, query.join("&"))
+        };
+
+        if page.is_paged() {
+            let tasks_response = match self.get_tasks_page(&url, access_token).await? {
+                Ok(tasks_response) => tasks_response,
+                Err(error_response) => return Ok(error_response),
+            };
+
+            let next_cursor = match tasks_response.odata_next_link.as_deref() {
+                Some(next_link) if next_link.starts_with(&self.base_url) => {
+                    match cursor_from_next_link(next_link) {
+                        Some(position) => Some(position.encode()),
+                        None => {
+                            error!(
+                                "Data layer: nextLink without paging position: {}",
+                                next_link
+                            );
+                            return Ok(BusinessResponse::error(
+                                "Unexpected Microsoft Graph pagination link",
+                            ));
+                        }
+                    }
+                }
+                Some(next_link) => {
+                    error!("Data layer: Unexpected nextLink: {}", next_link);
+                    return Ok(BusinessResponse::error(
+                        "Unexpected Microsoft Graph pagination link",
+                    ));
+                }
+                None => None,
+            };
+
+            let tasks = match parse_tasks(tasks_response.value) {
+                Ok(tasks) => tasks,
+                Err(error_response) => return Ok(error_response),
+            };
+            info!(
+                "Data layer: Successfully parsed page with {} tasks (has next page: {})",
+                tasks.len(),
+                next_cursor.is_some()
+            );
+
+            return Ok(BusinessResponse::success(TaskPage { tasks, next_cursor }));
+        }
+
+        let mut tasks: Vec<Task> = Vec::new();
+        let mut next_url = Some(url);
+        let mut pages = 0;
+
+        while let Some(url) = next_url {
+            if pages == MAX_PAGES {
+                error!(
+                    "Data layer: Stopped following nextLink after {} pages for list: {}",
+                    MAX_PAGES, list_id
+                );
+                return Ok(BusinessResponse::error(
+                    "Too many pages returned by Microsoft Graph",
+                ));
+            }
+
+            let tasks_response = match self.get_tasks_page(&url, access_token).await? {
+                Ok(tasks_response) => tasks_response,
+                Err(error_response) => return Ok(error_response),
+            };
+            pages += 1;
+
+            match parse_tasks(tasks_response.value) {
+                Ok(page_tasks) => tasks.extend(page_tasks),
+                Err(error_response) => return Ok(error_response),
+            }
+
+            next_url = match tasks_response.odata_next_link {
+                Some(next_link) if next_link.starts_with(&self.base_url) => Some(next_link),
+                Some(next_link) => {
+                    error!("Data layer: Unexpected nextLink: {}", next_link);
+                    return Ok(BusinessResponse::error(
+                        "Unexpected Microsoft Graph pagination link",
+                    ));
+                }
+                None => None,
+            };
+        }
+
+        info!(
+            "Data layer: Successfully parsed {} tasks from {} pages",
+            tasks.len(),
+            pages
+        );
+        Ok(BusinessResponse::success(TaskPage {
+            tasks,
+            next_cursor: None,
+        }))
+    }
+
+    /// Fetches a single page of tasks. The inner `Err` carries the business error to return.
+    async fn get_tasks_page(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<Result<TasksResponse, BusinessResponse<TaskPage>>, Box<dyn std::error::Error>> {
         info!("Data layer: Making GET request to: {}", url);
 
         let response = match self
             .client
-            .get(&url)
+            .get(url)
             .header("Authorization", format!("Bearer {}", access_token))
             .header("Content-Type", "application/json")
             .send()
@@ -63,9 +216,9 @@ impl TasksMicrosoftGraphApi {
                 Ok(text) => text,
                 Err(e) => {
                     error!("Data layer: Failed to read response body: {}", e);
-                    return Ok(BusinessResponse::error(
+                    return Ok(Err(BusinessResponse::error(
                         "Failed to read Microsoft Graph response",
-                    ));
+                    )));
                 }
             };
 
@@ -75,21 +228,15 @@ impl TasksMicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TasksResponse>(&response_text) {
-                Ok(tasks_response) => {
-                    let tasks: Vec<Task> =
-                        tasks_response.value.into_iter().map(Task::from).collect();
-
-                    info!("Data layer: Successfully parsed {} tasks", tasks.len());
-                    Ok(BusinessResponse::success(tasks))
-                }
+                Ok(tasks_response) => Ok(Ok(tasks_response)),
                 Err(_e) => {
                     error!(
                         "Data layer: Response that failed to parse: {}",
                         response_text
                     );
-                    Ok(BusinessResponse::error(
+                    Ok(Err(BusinessResponse::error(
                         "Failed to parse Microsoft Graph response",
-                    ))
+                    )))
                 }
             }
         } else {
@@ -99,26 +246,29 @@ impl TasksMicrosoftGraphApi {
                         "Data layer: Microsoft Graph API error ({}): {}",
                         status, error_text
                     );
-                    Ok(BusinessResponse::error(&format!(
+                    Ok(Err(BusinessResponse::error(&format!(
                         "Failed to get tasks: {} - {}",
                         status, error_text
-                    )))
+                    ))))
                 }
                 Err(e) => {
                     error!("Data layer: Failed to read error response: {}", e);
-                    Ok(BusinessResponse::error(&format!(
+                    Ok(Err(BusinessResponse::error(&format!(
                         "Failed to get tasks: HTTP {}",
                         status
-                    )))
+                    ))))
                 }
             }
         }
     }
 
+    /// Fetches a task; with `include_checklist` its checklist items are expanded in the
+    /// same request
     pub async fn get_task(
         &self,
         list_id: &str,
         task_id: &str,
+        include_checklist: bool,
         access_token: &str,
     ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
         info!(
@@ -141,10 +291,13 @@ impl TasksMicrosoftGraphApi {
             return Ok(BusinessResponse::error("Task ID is required"));
         }
 
-        let url = format!(
+        let mut url = format!(
             "{}/me/todo/lists/{}/tasks/{}",
             self.base_url, list_id, task_id
         );
+        if include_checklist {
+            url.push_str("?$expand=checklistItems");
+        }
         info!("Data layer: Making GET request to: {}", url);
 
         let response = match self
@@ -182,11 +335,22 @@ impl TasksMicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TaskResponse>(&response_text) {
-                Ok(task_response) => {
-                    let task = Task::from(task_response);
-                    info!("Data layer: Successfully parsed task");
-                    Ok(BusinessResponse::success(task))
-                }
+                Ok(task_response) => match Task::try_from(task_response) {
+                    Ok(task) => {
+                        info!("Data layer: Successfully parsed task");
+                        Ok(BusinessResponse::success(task))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid task returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid task returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!("Data layer: Failed to parse task response: {}", e);
                     error!(
@@ -242,42 +406,7 @@ impl TasksMicrosoftGraphApi {
             return Ok(BusinessResponse::error("Access token is required"));
         }
 
-        let mut body = json!({
-            "title": request.title
-        });
-
-        if let Some(body_content) = &request.body {
-            body["body"] = json!({
-                "content": body_content,
-                "contentType": "text"
-            });
-        }
-
-        if let Some(importance) = &request.importance {
-            body["importance"] = json!(match importance {
-                crate::todo_mod::task::TaskImportance::Low => "low",
-                crate::todo_mod::task::TaskImportance::Normal => "normal",
-                crate::todo_mod::task::TaskImportance::High => "high",
-            });
-        }
-
-        if let Some(is_reminder_on) = request.is_reminder_on {
-            body["isReminderOn"] = json!(is_reminder_on);
-        }
-
-        if let Some(reminder_date_time) = &request.reminder_date_time {
-            body["reminderDateTime"] = json!({
-                "dateTime": reminder_date_time.to_rfc3339(),
-                "timeZone": "UTC"
-            });
-        }
-
-        if let Some(due_date_time) = &request.due_date_time {
-            body["dueDateTime"] = json!({
-                "dateTime": due_date_time.to_rfc3339(),
-                "timeZone": "UTC"
-            });
-        }
+        let body = create_task_body(&request);
 
         let list_id = request.list_id.as_deref().unwrap_or("");
         let url = format!("{}/me/todo/lists/{}/tasks", self.base_url, list_id);
@@ -319,11 +448,22 @@ impl TasksMicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TaskResponse>(&response_text) {
-                Ok(task_response) => {
-                    let task = Task::from(task_response);
-                    info!("Data layer: Successfully created task");
-                    Ok(BusinessResponse::success(task))
-                }
+                Ok(task_response) => match Task::try_from(task_response) {
+                    Ok(task) => {
+                        info!("Data layer: Successfully created task");
+                        Ok(BusinessResponse::success(task))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid task returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid task returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!("Data layer: Failed to parse create task response: {}", e);
                     error!(
@@ -373,54 +513,7 @@ impl TasksMicrosoftGraphApi {
             return Ok(BusinessResponse::error("Access token is required"));
         }
 
-        let mut body = json!({});
-
-        if let Some(title) = &request.title {
-            body["title"] = json!(title);
-        }
-
-        if let Some(body_content) = &request.body {
-            body["body"] = json!({
-                "content": body_content,
-                "contentType": "text"
-            });
-        }
-
-        if let Some(status) = &request.status {
-            body["status"] = json!(match status {
-                crate::todo_mod::task::TaskStatus::NotStarted => "notStarted",
-                crate::todo_mod::task::TaskStatus::InProgress => "inProgress",
-                crate::todo_mod::task::TaskStatus::Completed => "completed",
-                crate::todo_mod::task::TaskStatus::WaitingOnOthers => "waitingOnOthers",
-                crate::todo_mod::task::TaskStatus::Deferred => "deferred",
-            });
-        }
-
-        if let Some(importance) = &request.importance {
-            body["importance"] = json!(match importance {
-                crate::todo_mod::task::TaskImportance::Low => "low",
-                crate::todo_mod::task::TaskImportance::Normal => "normal",
-                crate::todo_mod::task::TaskImportance::High => "high",
-            });
-        }
-
-        if let Some(is_reminder_on) = request.is_reminder_on {
-            body["isReminderOn"] = json!(is_reminder_on);
-        }
-
-        if let Some(reminder_date_time) = &request.reminder_date_time {
-            body["reminderDateTime"] = json!({
-                "dateTime": reminder_date_time.to_rfc3339(),
-                "timeZone": "UTC"
-            });
-        }
-
-        if let Some(due_date_time) = &request.due_date_time {
-            body["dueDateTime"] = json!({
-                "dateTime": due_date_time.to_rfc3339(),
-                "timeZone": "UTC"
-            });
-        }
+        let body = update_task_body(&request);
 
         let list_id = request.list_id.as_deref().unwrap_or("");
         let task_id = request.id.as_deref().unwrap_or("");
@@ -466,11 +559,22 @@ impl TasksMicrosoftGraphApi {
             );
 
             match serde_json::from_str::<TaskResponse>(&response_text) {
-                Ok(task_response) => {
-                    let task = Task::from(task_response);
-                    info!("Data layer: Successfully updated task");
-                    Ok(BusinessResponse::success(task))
-                }
+                Ok(task_response) => match Task::try_from(task_response) {
+                    Ok(task) => {
+                        info!("Data layer: Successfully updated task");
+                        Ok(BusinessResponse::success(task))
+                    }
+                    Err(e) => {
+                        error!(
+                            "Data layer: Invalid task returned by Microsoft Graph: {}",
+                            e
+                        );
+                        Ok(BusinessResponse::error(&format!(
+                            "Invalid task returned by Microsoft Graph: {}",
+                            e
+                        )))
+                    }
+                },
                 Err(e) => {
                     error!("Data layer: Failed to parse update task response: {}", e);
                     error!(
@@ -593,4 +697,958 @@ impl TasksMicrosoftGraphApi {
             }
         }
     }
+    pub async fn get_checklist_items(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        access_token: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Fetching checklist items of task: {} from list: {}",
+            task_id, list_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let url = self.checklist_items_url(list_id, task_id);
+        info!("Data layer: Making GET request to: {}", url);
+
+        let response = match self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        let status = response.status();
+        info!("Data layer: Received response with status: {}", status);
+
+        if status.is_success() {
+            let response_text = match response.text().await {
+                Ok(text) => text,
+                Err(e) => {
+                    error!("Data layer: Failed to read response body: {}", e);
+                    return Ok(BusinessResponse::error(
+                        "Failed to read Microsoft Graph response",
+                    ));
+                }
+            };
+
+            match serde_json::from_str::<ChecklistItemsResponse>(&response_text) {
+                Ok(checklist_items_response) => {
+                    let checklist_items = match checklist_items_response
+                        .value
+                        .into_iter()
+                        .map(ChecklistItem::try_from)
+                        .collect::<Result<Vec<_>, _>>()
+                    {
+                        Ok(checklist_items) => checklist_items,
+                        Err(e) => {
+                            error!(
+                                "Data layer: Invalid checklist item returned by Microsoft Graph: {}",
+                                e
+                            );
+                            return Ok(BusinessResponse::error(&format!(
+                                "Invalid checklist item returned by Microsoft Graph: {}",
+                                e
+                            )));
+                        }
+                    };
+
+                    info!(
+                        "Data layer: Successfully parsed {} checklist items",
+                        checklist_items.len()
+                    );
+                    Ok(BusinessResponse::success(checklist_items))
+                }
+                Err(e) => {
+                    error!(
+                        "Data layer: Failed to parse checklist items response: {}",
+                        e
+                    );
+                    error!(
+                        "Data layer: Response that failed to parse: {}",
+                        response_text
+                    );
+                    Ok(BusinessResponse::error(
+                        "Failed to parse Microsoft Graph response",
+                    ))
+                }
+            }
+        } else if status == 404 {
+            info!(
+                "Data layer: Task not found with ID: {} in list: {}",
+                task_id, list_id
+            );
+            Ok(BusinessResponse::error("Task not found"))
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!(
+                "Data layer: Microsoft Graph API error ({}): {}",
+                status, error_text
+            );
+            Ok(BusinessResponse::error(&format!(
+                "Failed to get checklist items: {} - {}",
+                status, error_text
+            )))
+        }
+    }
+
+    pub async fn get_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        access_token: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Fetching checklist item: {} of task: {}",
+            checklist_item_id, task_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let url = format!(
+            "{}/{}",
+            self.checklist_items_url(list_id, task_id),
+            checklist_item_id
+        );
+        info!("Data layer: Making GET request to: {}", url);
+
+        let response = match self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        Ok(read_checklist_item_response(response, "get").await)
+    }
+
+    pub async fn create_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &CreateChecklistItemRequest,
+        access_token: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Creating checklist item: {} in task: {}",
+            request.display_name, task_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let mut body = json!({
+            "displayName": request.display_name
+        });
+
+        if let Some(is_checked) = request.is_checked {
+            body["isChecked"] = json!(is_checked);
+        }
+
+        let url = self.checklist_items_url(list_id, task_id);
+        info!("Data layer: Making POST request to: {}", url);
+
+        let response = match self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        Ok(read_checklist_item_response(response, "create").await)
+    }
+
+    pub async fn update_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        request: &UpdateChecklistItemRequest,
+        access_token: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Updating checklist item: {} of task: {}",
+            checklist_item_id, task_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let mut body = json!({});
+
+        if let Some(display_name) = &request.display_name {
+            body["displayName"] = json!(display_name);
+        }
+
+        if let Some(is_checked) = request.is_checked {
+            body["isChecked"] = json!(is_checked);
+        }
+
+        let url = format!(
+            "{}/{}",
+            self.checklist_items_url(list_id, task_id),
+            checklist_item_id
+        );
+        info!("Data layer: Making PATCH request to: {}", url);
+
+        let response = match self
+            .client
+            .patch(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        Ok(read_checklist_item_response(response, "update").await)
+    }
+
+    pub async fn delete_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        access_token: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        info!(
+            "Data layer: Deleting checklist item: {} of task: {}",
+            checklist_item_id, task_id
+        );
+
+        if access_token.trim().is_empty() {
+            error!("Data layer: Access token is empty");
+            return Ok(BusinessResponse::error("Access token is required"));
+        }
+
+        let url = format!(
+            "{}/{}",
+            self.checklist_items_url(list_id, task_id),
+            checklist_item_id
+        );
+        info!("Data layer: Making DELETE request to: {}", url);
+
+        let response = match self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Data layer: HTTP request failed: {}", e);
+                return Err(e.into());
+            }
+        };
+
+        let status = response.status();
+        info!("Data layer: Received response with status: {}", status);
+
+        if status.is_success() {
+            info!("Data layer: Successfully deleted checklist item");
+            Ok(BusinessResponse::success(
+                "Checklist item deleted successfully".to_string(),
+            ))
+        } else if status == 404 {
+            info!(
+                "Data layer: Checklist item not found for deletion with ID: {}",
+                checklist_item_id
+            );
+            Ok(BusinessResponse::error("Checklist item not found"))
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            error!(
+                "Data layer: Microsoft Graph API error ({}): {}",
+                status, error_text
+            );
+            Ok(BusinessResponse::error(&format!(
+                "Failed to delete checklist item: {} - {}",
+                status, error_text
+            )))
+        }
+    }
+
+    /// Creates the tasks through `$batch`; each request is paired with its index in the bulk
+    /// request, which the result carries
+    pub async fn create_tasks(
+        &self,
+        list_id: &str,
+        requests: &[(usize, CreateTaskRequest)],
+        access_token: &str,
+    ) -> Vec<TaskBatchItemResult> {
+        info!(
+            "Data layer: Creating {} tasks in list: {}",
+            requests.len(),
+            list_id
+        );
+
+        let batch_requests = requests
+            .iter()
+            .map(|(index, request)| BatchRequest {
+                id: index.to_string(),
+                method: "POST",
+                url: format!("/me/todo/lists/{}/tasks", list_id),
+                body: Some(create_task_body(request)),
+            })
+            .collect();
+
+        let responses = BatchMicrosoftGraphApi::new()
+            .send(batch_requests, access_token)
+            .await;
+
+        requests
+            .iter()
+            .zip(responses)
+            .map(|((index, _), response)| task_batch_item_result(*index, None, response))
+            .collect()
+    }
+
+    /// Updates the tasks through `$batch`, see `create_tasks`
+    pub async fn update_tasks(
+        &self,
+        list_id: &str,
+        requests: &[(usize, UpdateTaskRequest)],
+        access_token: &str,
+    ) -> Vec<TaskBatchItemResult> {
+        info!(
+            "Data layer: Updating {} tasks in list: {}",
+            requests.len(),
+            list_id
+        );
+
+        let batch_requests = requests
+            .iter()
+            .map(|(index, request)| BatchRequest {
+                id: index.to_string(),
+                method: "PATCH",
+                url: format!(
+                    "/me/todo/lists/{}/tasks/{}",
+                    list_id,
+                    request.id.as_deref().unwrap_or("")
+                ),
+                body: Some(update_task_body(request)),
+            })
+            .collect();
+
+        let responses = BatchMicrosoftGraphApi::new()
+            .send(batch_requests, access_token)
+            .await;
+
+        requests
+            .iter()
+            .zip(responses)
+            .map(|((index, request), response)| {
+                task_batch_item_result(*index, request.id.clone(), response)
+            })
+            .collect()
+    }
+
+    /// Deletes the tasks through `$batch`, see `create_tasks`
+    pub async fn delete_tasks(
+        &self,
+        list_id: &str,
+        task_ids: &[(usize, String)],
+        access_token: &str,
+    ) -> Vec<TaskBatchItemResult> {
+        info!(
+            "Data layer: Deleting {} tasks from list: {}",
+            task_ids.len(),
+            list_id
+        );
+
+        let batch_requests = task_ids
+            .iter()
+            .map(|(index, task_id)| BatchRequest {
+                id: index.to_string(),
+                method: "DELETE",
+                url: format!("/me/todo/lists/{}/tasks/{}", list_id, task_id),
+                body: None,
+            })
+            .collect();
+
+        let responses = BatchMicrosoftGraphApi::new()
+            .send(batch_requests, access_token)
+            .await;
+
+        task_ids
+            .iter()
+            .zip(responses)
+            .map(|((index, task_id), response)| {
+                task_batch_item_result(*index, Some(task_id.clone()), response)
+            })
+            .collect()
+    }
+
+    fn checklist_items_url(&self, list_id: &str, task_id: &str) -> String {
+        format!(
+            "{}/me/todo/lists/{}/tasks/{}/checklistItems",
+            self.base_url, list_id, task_id
+        )
+    }
+}
+
+/// Converts the response of a batch item; a task in the body is returned with the result
+fn task_batch_item_result(
+    index: usize,
+    task_id: Option<String>,
+    response: BatchResponseItem,
+) -> TaskBatchItemResult {
+    if response.status == 404 {
+        return TaskBatchItemResult::error(index, task_id, response.status, "Task not found");
+    }
+
+    if !response.is_success() {
+        error!(
+            "Data layer: Batch item {} failed ({}): {}",
+            index,
+            response.status,
+            response.error_message()
+        );
+        return TaskBatchItemResult::error(
+            index,
+            task_id,
+            response.status,
+            &response.error_message(),
+        );
+    }
+
+    let Some(body) = response.body.filter(|body| !body.is_null()) else {
+        return match task_id {
+            Some(task_id) => TaskBatchItemResult::success(index, task_id, response.status, None),
+            None => TaskBatchItemResult::error(
+                index,
+                None,
+                502,
+                "Missing task in Microsoft Graph response",
+            ),
+        };
+    };
+
+    let task = serde_json::from_value::<TaskResponse>(body)
+        .map_err(|e| e.to_string())
+        .and_then(Task::try_from);
+
+    match task {
+        Ok(task) => {
+            TaskBatchItemResult::success(index, task.id.clone(), response.status, Some(task))
+        }
+        Err(e) => {
+            error!("Data layer: Invalid task in batch item {}: {}", index, e);
+            TaskBatchItemResult::error(
+                index,
+                task_id,
+                502,
+                &format!("Invalid task returned by Microsoft Graph: {}", e),
+            )
+        }
+    }
+}
+
+/// Converts a page of Graph tasks, failing the whole page on the first invalid task
+fn parse_tasks(task_responses: Vec<TaskResponse>) -> Result<Vec<Task>, BusinessResponse<TaskPage>> {
+    task_responses
+        .into_iter()
+        .map(Task::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!(
+                "Data layer: Invalid task returned by Microsoft Graph: {}",
+                e
+            );
+            BusinessResponse::error(&format!("Invalid task returned by Microsoft Graph: {}", e))
+        })
+}
+
+/// Reads a single checklist item returned by Microsoft Graph; `action` names the operation
+/// in the error messages
+async fn read_checklist_item_response(
+    response: reqwest::Response,
+    action: &str,
+) -> BusinessResponse<ChecklistItem> {
+    let status = response.status();
+    info!("Data layer: Received response with status: {}", status);
+
+    if status == 404 {
+        info!("Data layer: Checklist item or task not found");
+        return BusinessResponse::error("Checklist item not found");
+    }
+
+    let response_text = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Data layer: Failed to read response body: {}", e);
+            return BusinessResponse::error("Failed to read Microsoft Graph response");
+        }
+    };
+
+    if !status.is_success() {
+        error!(
+            "Data layer: Microsoft Graph API error ({}): {}",
+            status, response_text
+        );
+        return BusinessResponse::error(&format!(
+            "Failed to {} checklist item: {} - {}",
+            action, status, response_text
+        ));
+    }
+
+    match serde_json::from_str::<ChecklistItemResponse>(&response_text) {
+        Ok(checklist_item_response) => match ChecklistItem::try_from(checklist_item_response) {
+            Ok(checklist_item) => {
+                info!("Data layer: Successfully parsed checklist item");
+                BusinessResponse::success(checklist_item)
+            }
+            Err(e) => {
+                error!(
+                    "Data layer: Invalid checklist item returned by Microsoft Graph: {}",
+                    e
+                );
+                BusinessResponse::error(&format!(
+                    "Invalid checklist item returned by Microsoft Graph: {}",
+                    e
+                ))
+            }
+        },
+        Err(e) => {
+            error!("Data layer: Failed to parse checklist item response: {}", e);
+            error!(
+                "Data layer: Response that failed to parse: {}",
+                response_text
+            );
+            BusinessResponse::error("Failed to parse Microsoft Graph response")
+        }
+    }
+}
+
+/// `$filter` expression for the options Microsoft Graph filters on: status, importance and
+/// reminder. Due dates are stored as wall-clock times in each task's own time zone, so
+/// comparing them in Graph would be wrong; they are filtered after parsing.
+fn build_graph_filter(filter: &TaskFilterQuery) -> Option<String> {
+    let mut conditions = Vec::new();
+
+    if let Some(status) = &filter.status {
+        conditions.push(format!(
+            "status eq '{}'",
+            match status {
+                TaskStatus::NotStarted => "notStarted",
+                TaskStatus::InProgress => "inProgress",
+                TaskStatus::Completed => "completed",
+                TaskStatus::WaitingOnOthers => "waitingOnOthers",
+                TaskStatus::Deferred => "deferred",
+            }
+        ));
+    }
+
+    if let Some(importance) = &filter.importance {
+        conditions.push(format!(
+            "importance eq '{}'",
+            match importance {
+                TaskImportance::Low => "low",
+                TaskImportance::Normal => "normal",
+                TaskImportance::High => "high",
+            }
+        ));
+    }
+
+    if let Some(has_reminder) = filter.has_reminder {
+        conditions.push(format!("isReminderOn eq {}", has_reminder));
+    }
+
+    if conditions.is_empty() {
+        None
+    } else {
+        Some(conditions.join(" and "))
+    }
+}
+
+/// `$orderby` expression, `None` when there is no ordering or it is applied locally
+fn build_graph_order_by(filter: &TaskFilterQuery) -> Option<String> {
+    let field = match filter.order_by? {
+        TaskOrderBy::Title => "title",
+        TaskOrderBy::Importance => "importance",
+        TaskOrderBy::CreatedDateTime => "createdDateTime",
+        TaskOrderBy::LastModifiedDateTime => "lastModifiedDateTime",
+        TaskOrderBy::DueDateTime => return None,
+    };
+
+    let direction = match filter.order.unwrap_or(SortOrder::Asc) {
+        SortOrder::Asc => "asc",
+        SortOrder::Desc => "desc",
+    };
+
+    Some(format!("{} {}", field, direction))
+}
+
+fn create_task_body(request: &CreateTaskRequest) -> Value {
+    let mut body = json!({
+        "title": request.title
+    });
+
+    if let Some(body_content) = &request.body {
+        body["body"] = json!({
+            "content": body_content,
+            "contentType": "text"
+        });
+    }
+
+    if let Some(importance) = &request.importance {
+        body["importance"] = json!(match importance {
+            crate::todo_mod::task::TaskImportance::Low => "low",
+            crate::todo_mod::task::TaskImportance::Normal => "normal",
+            crate::todo_mod::task::TaskImportance::High => "high",
+        });
+    }
+
+    if let Some(is_reminder_on) = request.is_reminder_on {
+        body["isReminderOn"] = json!(is_reminder_on);
+    }
+
+    if let Some(reminder_date_time) = &request.reminder_date_time {
+        body["reminderDateTime"] = to_graph_date_time(reminder_date_time);
+    }
+
+    if let Some(due_date_time) = &request.due_date_time {
+        body["dueDateTime"] = to_graph_date_time(due_date_time);
+    }
+
+    if let Some(recurrence) = &request.recurrence {
+        body["recurrence"] = recurrence_body(recurrence);
+    }
+
+    body
+}
+
+/// Only the fields present in the request are sent
+fn update_task_body(request: &UpdateTaskRequest) -> Value {
+    let mut body = json!({});
+
+    if let Some(title) = &request.title {
+        body["title"] = json!(title);
+    }
+
+    if let Some(body_content) = &request.body {
+        body["body"] = json!({
+            "content": body_content,
+            "contentType": "text"
+        });
+    }
+
+    if let Some(status) = &request.status {
+        body["status"] = json!(match status {
+            crate::todo_mod::task::TaskStatus::NotStarted => "notStarted",
+            crate::todo_mod::task::TaskStatus::InProgress => "inProgress",
+            crate::todo_mod::task::TaskStatus::Completed => "completed",
+            crate::todo_mod::task::TaskStatus::WaitingOnOthers => "waitingOnOthers",
+            crate::todo_mod::task::TaskStatus::Deferred => "deferred",
+        });
+    }
+
+    if let Some(importance) = &request.importance {
+        body["importance"] = json!(match importance {
+            crate::todo_mod::task::TaskImportance::Low => "low",
+            crate::todo_mod::task::TaskImportance::Normal => "normal",
+            crate::todo_mod::task::TaskImportance::High => "high",
+        });
+    }
+
+    if let Some(is_reminder_on) = request.is_reminder_on {
+        body["isReminderOn"] = json!(is_reminder_on);
+    }
+
+    if let Some(reminder_date_time) = &request.reminder_date_time {
+        body["reminderDateTime"] = to_graph_date_time(reminder_date_time);
+    }
+
+    if let Some(due_date_time) = &request.due_date_time {
+        body["dueDateTime"] = to_graph_date_time(due_date_time);
+    }
+
+    if let Some(recurrence) = &request.recurrence {
+        body["recurrence"] = recurrence_body(recurrence);
+    }
+
+    body
+}
+
+/// Builds Graph's `patternedRecurrence`, leaving out the fields the request does not set
+fn recurrence_body(recurrence: &TaskRecurrence) -> Value {
+    let pattern = &recurrence.pattern;
+    let mut pattern_body = json!({
+        "type": pattern.pattern_type,
+        "interval": pattern.interval
+    });
+
+    if let Some(days_of_week) = &pattern.days_of_week {
+        pattern_body["daysOfWeek"] = json!(days_of_week);
+    }
+
+    if let Some(first_day_of_week) = pattern.first_day_of_week {
+        pattern_body["firstDayOfWeek"] = json!(first_day_of_week);
+    }
+
+    if let Some(day_of_month) = pattern.day_of_month {
+        pattern_body["dayOfMonth"] = json!(day_of_month);
+    }
+
+    if let Some(month) = pattern.month {
+        pattern_body["month"] = json!(month);
+    }
+
+    if let Some(index) = pattern.index {
+        pattern_body["index"] = json!(index);
+    }
+
+    let range = &recurrence.range;
+    let mut range_body = json!({
+        "type": range.range_type,
+        "startDate": range.start_date.format("%Y-%m-%d").to_string(),
+        "recurrenceTimeZone": user_time_zone().name()
+    });
+
+    if let Some(end_date) = range.end_date {
+        range_body["endDate"] = json!(end_date.format("%Y-%m-%d").to_string());
+    }
+
+    if let Some(number_of_occurrences) = range.number_of_occurrences {
+        range_body["numberOfOccurrences"] = json!(number_of_occurrences);
+    }
+
+    json!({
+        "pattern": pattern_body,
+        "range": range_body
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::microsoft_graph_mod::recurrence_response::PatternedRecurrenceResponse;
+    use chrono::Utc;
+
+    #[test]
+    fn graph_filter_combines_the_supported_conditions() {
+        assert_eq!(build_graph_filter(&TaskFilterQuery::default()), None);
+
+        let filter = TaskFilterQuery {
+            status: Some(TaskStatus::WaitingOnOthers),
+            importance: Some(TaskImportance::High),
+            has_reminder: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_graph_filter(&filter).as_deref(),
+            Some("status eq 'waitingOnOthers' and importance eq 'high' and isReminderOn eq false")
+        );
+    }
+
+    #[test]
+    fn graph_filter_leaves_local_options_out() {
+        let filter = TaskFilterQuery {
+            due_before: Some(Utc::now()),
+            search: Some("groceries".to_string()),
+            order_by: Some(TaskOrderBy::DueDateTime),
+            ..Default::default()
+        };
+
+        assert_eq!(build_graph_filter(&filter), None);
+        assert_eq!(build_graph_order_by(&filter), None);
+    }
+
+    #[test]
+    fn graph_order_by_defaults_to_ascending() {
+        let order_by = |order_by, order| {
+            build_graph_order_by(&TaskFilterQuery {
+                order_by: Some(order_by),
+                order,
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(build_graph_order_by(&TaskFilterQuery::default()), None);
+        assert_eq!(
+            order_by(TaskOrderBy::Title, None).as_deref(),
+            Some("title asc")
+        );
+        assert_eq!(
+            order_by(TaskOrderBy::CreatedDateTime, Some(SortOrder::Desc)).as_deref(),
+            Some("createdDateTime desc")
+        );
+        assert_eq!(
+            order_by(TaskOrderBy::LastModifiedDateTime, Some(SortOrder::Asc)).as_deref(),
+            Some("lastModifiedDateTime asc")
+        );
+        assert_eq!(
+            order_by(TaskOrderBy::Importance, Some(SortOrder::Desc)).as_deref(),
+            Some("importance desc")
+        );
+    }
+
+    #[test]
+    fn recurrence_body_only_sends_the_fields_set() {
+        let recurrence: TaskRecurrence = serde_json::from_value(json!({
+            "pattern": {
+                "type": "weekly",
+                "interval": 1,
+                "days_of_week": ["monday", "friday"],
+                "first_day_of_week": "sunday"
+            },
+            "range": {
+                "type": "numbered",
+                "start_date": "2024-01-01",
+                "number_of_occurrences": 10
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            recurrence_body(&recurrence),
+            json!({
+                "pattern": {
+                    "type": "weekly",
+                    "interval": 1,
+                    "daysOfWeek": ["monday", "friday"],
+                    "firstDayOfWeek": "sunday"
+                },
+                "range": {
+                    "type": "numbered",
+                    "startDate": "2024-01-01",
+                    "numberOfOccurrences": 10,
+                    "recurrenceTimeZone": user_time_zone().name()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn recurrence_body_round_trips_through_the_graph_response() {
+        let recurrence: TaskRecurrence = serde_json::from_value(json!({
+            "pattern": {
+                "type": "relativeMonthly",
+                "interval": 2,
+                "days_of_week": ["tuesday"],
+                "index": "last"
+            },
+            "range": {
+                "type": "endDate",
+                "start_date": "2024-02-27",
+                "end_date": "2025-02-25"
+            }
+        }))
+        .unwrap();
+
+        let response: PatternedRecurrenceResponse =
+            serde_json::from_value(recurrence_body(&recurrence)).unwrap();
+        let round_tripped = TaskRecurrence::try_from(response).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(round_tripped).unwrap(),
+            serde_json::to_value(recurrence).unwrap()
+        );
+    }
+
+    fn batch_item(status: u16, body: Option<serde_json::Value>) -> BatchResponseItem {
+        BatchResponseItem {
+            id: "0".to_string(),
+            status,
+            body,
+        }
+    }
+
+    #[test]
+    fn batch_item_result_carries_the_task_of_the_response() {
+        let body = serde_json::json!({
+            "id": "task-1",
+            "title": "Buy milk",
+            "status": "notStarted",
+            "importance": "normal",
+            "isReminderOn": false,
+            "createdDateTime": "2024-05-10T09:00:00Z",
+            "lastModifiedDateTime": "2024-05-10T09:00:00Z"
+        });
+
+        let result = task_batch_item_result(2, None, batch_item(201, Some(body)));
+
+        assert!(result.success);
+        assert_eq!(result.index, 2);
+        assert_eq!(result.status, 201);
+        assert_eq!(result.task_id.as_deref(), Some("task-1"));
+        assert_eq!(result.task.unwrap().title, "Buy milk");
+    }
+
+    #[test]
+    fn batch_item_result_without_a_body() {
+        let deleted = task_batch_item_result(0, Some("task-1".to_string()), batch_item(204, None));
+        assert!(deleted.success);
+        assert_eq!(deleted.task_id.as_deref(), Some("task-1"));
+        assert!(deleted.task.is_none());
+
+        let created = task_batch_item_result(0, None, batch_item(201, None));
+        assert!(!created.success);
+        assert_eq!(created.status, 502);
+    }
+
+    #[test]
+    fn batch_item_result_maps_graph_errors() {
+        let not_found =
+            task_batch_item_result(1, Some("task-1".to_string()), batch_item(404, None));
+        assert!(!not_found.success);
+        assert_eq!(not_found.status, 404);
+        assert_eq!(not_found.task_id.as_deref(), Some("task-1"));
+        assert_eq!(not_found.errors, vec!["Task not found"]);
+
+        let body = serde_json::json!({ "error": { "message": "Invalid title" } });
+        let bad_request = task_batch_item_result(1, None, batch_item(400, Some(body)));
+        assert_eq!(bad_request.status, 400);
+        assert_eq!(bad_request.errors, vec!["Invalid title"]);
+
+        let body = serde_json::json!({ "id": "task-1" });
+        let invalid_task = task_batch_item_result(1, None, batch_item(200, Some(body)));
+        assert!(!invalid_task.success);
+        assert_eq!(invalid_task.status, 502);
+    }
 }
@@ -15,4 +15,4 @@ pub struct TodoListResponse {
     pub created_date_time: Option<String>,
     #[serde(rename = "lastModifiedDateTime")]
     pub last_modified_date_time: Option<String>,
-}
\ No newline at end of file
+}
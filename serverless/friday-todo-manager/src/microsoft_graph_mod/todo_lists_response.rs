@@ -6,4 +6,6 @@ pub struct TodoListsResponse {
     #[serde(rename = "@odata.context")]
     pub odata_context: Option<String>,
     pub value: Vec<TodoListResponse>,
-}
\ No newline at end of file
+    #[serde(rename = "@odata.nextLink")]
+    pub odata_next_link: Option<String>,
+}
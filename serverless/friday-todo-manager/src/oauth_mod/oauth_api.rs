@@ -1,9 +1,21 @@
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
 use reqwest::Client;
+use serde_json::json;
+use std::sync::Mutex;
 use tracing::{error, info};
 
-use super::oauth_response::AccessTokenResponse;
+use super::oauth_response::{AccessTokenResponse, ServiceTokenResponse};
 use crate::ENV_CONFIG;
 
+/// Scope of the service token required by the provider proxy of the OAuth Manager
+const PROVIDER_PROXY_SCOPE: &str = "oauth.proxy";
+
+/// Service token and the instant it stops being reused
+type CachedServiceToken = (String, DateTime<Utc>);
+
+static SERVICE_TOKEN: Lazy<Mutex<Option<CachedServiceToken>>> = Lazy::new(|| Mutex::new(None));
+
 #[derive(Debug)]
 pub struct OAuthApi {
     client: Client,
@@ -75,4 +87,78 @@ impl OAuthApi {
             Err(format!("OAuth service HTTP error: {} - {}", status, error_text).into())
         }
     }
+
+    /// Service token allowed to call provider APIs through the OAuth Manager proxy, obtained
+    /// with the client-credentials grant of `SERVICE_CLIENT_ID` and `SERVICE_CLIENT_SECRET`
+    pub async fn generate_service_token(&self) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some((token, expires_at)) = SERVICE_TOKEN.lock().unwrap().as_ref() {
+            if *expires_at > Utc::now() {
+                return Ok(token.clone());
+            }
+        }
+
+        info!("OAuth layer: Generating service token");
+
+        let (Some(client_id), Some(client_secret)) = (
+            ENV_CONFIG.service_client_id.as_ref(),
+            ENV_CONFIG.service_client_secret.as_ref(),
+        ) else {
+            error!("OAuth layer: Service client credentials are not configured");
+            return Err("SERVICE_CLIENT_ID and SERVICE_CLIENT_SECRET are not configured".into());
+        };
+
+        let body = json!({
+            "client_id": client_id,
+            "client_secret": client_secret,
+            "scopes": [PROVIDER_PROXY_SCOPE]
+        });
+
+        let url = format!("{}/service-auth/token", self.base_url);
+        info!("OAuth layer: Making POST request to: {}", url);
+
+        let response = match self.client.post(&url).json(&body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                error!("OAuth layer: HTTP request failed: {}", e);
+                return Err(format!("Failed to connect to OAuth manager: {}", e).into());
+            }
+        };
+
+        let status = response.status();
+        info!("OAuth layer: Received response with status: {}", status);
+
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("OAuth layer: HTTP error ({}): {}", status, error_text);
+            return Err(format!("OAuth service HTTP error: {} - {}", status, error_text).into());
+        }
+
+        let token_response = match response.json::<ServiceTokenResponse>().await {
+            Ok(token_response) => token_response,
+            Err(e) => {
+                error!("OAuth layer: Failed to parse service token response: {}", e);
+                return Err(format!("Failed to parse service token response: {}", e).into());
+            }
+        };
+
+        match token_response.data.filter(|_| token_response.success) {
+            Some(service_token) => {
+                // Renewed a minute early so that no request goes out with an expired token
+                let expires_at = Utc::now() + Duration::seconds(service_token.expires_in - 60);
+                *SERVICE_TOKEN.lock().unwrap() =
+                    Some((service_token.access_token.clone(), expires_at));
+
+                info!("OAuth layer: Successfully generated service token");
+                Ok(service_token.access_token)
+            }
+            None => {
+                let error_msg = token_response.errors.join(", ");
+                error!("OAuth layer: OAuth service error: {}", error_msg);
+                Err(format!("OAuth service error: {}", error_msg).into())
+            }
+        }
+    }
 }
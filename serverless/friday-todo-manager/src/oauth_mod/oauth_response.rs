@@ -7,3 +7,17 @@ pub struct AccessTokenResponse {
     pub data: Option<String>, // The access token
     pub errors: Vec<String>,
 }
+
+/// Response of the client-credentials grant of the OAuth Manager service auth
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ServiceTokenResponse {
+    pub success: bool,
+    pub data: Option<ServiceToken>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ServiceToken {
+    pub access_token: String,
+    pub expires_in: i64,
+}
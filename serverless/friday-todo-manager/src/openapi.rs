@@ -3,10 +3,21 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     business_response::BusinessResponse,
+    provider_mod::todo_provider::TodoProviderKind,
+    subscription_mod::subscription::{
+        CreateSubscriptionRequest, Subscription, SubscriptionChangeType,
+    },
     todo_mod::{
+        checklist_item::{ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest},
+        recurrence::{
+            DayOfWeek, RecurrencePattern, RecurrencePatternType, RecurrenceRange,
+            RecurrenceRangeType, TaskRecurrence, WeekIndex,
+        },
         task::{
-            CreateTaskRequest, CreateTaskRequestBody, DeleteTaskRequest, Task, TaskImportance,
-            TaskStatus, UpdateTaskRequest, UpdateTaskRequestBody,
+            CreateTaskRequest, CreateTaskRequestBody, CreateTasksRequest, DeleteTaskRequest,
+            SortOrder, Task, TaskBatchItemResult, TaskBatchResult, TaskIdsRequest, TaskImportance,
+            TaskOrderBy, TaskStatus, UpdateTaskItem, UpdateTaskRequest, UpdateTaskRequestBody,
+            UpdateTasksRequest,
         },
         todo_list::{
             CreateTodoListRequest, DeleteTodoListRequest, TodoList, UpdateTodoListRequest,
@@ -19,13 +30,18 @@ use crate::{
     info(
         title = "Friday Todo Manager API",
         description = r#"
-A comprehensive todo list management service that integrates with Microsoft Graph API.
+A comprehensive todo list management service that integrates with Microsoft Graph API and
+Google Tasks, or stores the lists in Postgres to run without them.
 
 This API allows you to:
 - Create, read, update, and delete todo lists
 - Manage tasks within todo lists
+- Create, update, complete and delete many tasks at once
 - Track task status, importance, and due dates
+- Create recurring tasks (daily, weekly, monthly and yearly)
+- Break tasks down into checklist items
 - Set reminders for tasks
+- Get notified of task changes made anywhere through RabbitMQ
 - Synchronize with Microsoft To-Do through Graph API
 
 Perfect for AI agents and automation systems that need to manage personal or team productivity.
@@ -60,29 +76,72 @@ All endpoints return a consistent BusinessResponse format with success indicator
         crate::todo_mod::task_controller::create_task,
         crate::todo_mod::task_controller::update_task,
         crate::todo_mod::task_controller::delete_task,
+        crate::todo_mod::task_controller::create_tasks,
+        crate::todo_mod::task_controller::update_tasks,
+        crate::todo_mod::task_controller::complete_tasks,
+        crate::todo_mod::task_controller::delete_tasks,
+        crate::todo_mod::checklist_item_controller::get_checklist_items,
+        crate::todo_mod::checklist_item_controller::get_checklist_item,
+        crate::todo_mod::checklist_item_controller::create_checklist_item,
+        crate::todo_mod::checklist_item_controller::update_checklist_item,
+        crate::todo_mod::checklist_item_controller::delete_checklist_item,
+        crate::subscription_mod::subscription_controller::get_subscriptions,
+        crate::subscription_mod::subscription_controller::create_subscription,
+        crate::subscription_mod::subscription_controller::renew_subscription,
+        crate::subscription_mod::subscription_controller::delete_subscription,
+        crate::subscription_mod::subscription_controller::receive_notifications,
     ),
     components(schemas(
         BusinessResponse<TodoList>,
         BusinessResponse<Vec<TodoList>>,
         BusinessResponse<Task>,
         BusinessResponse<Vec<Task>>,
+        BusinessResponse<TaskBatchResult>,
+        BusinessResponse<ChecklistItem>,
+        BusinessResponse<Vec<ChecklistItem>>,
+        BusinessResponse<Subscription>,
+        BusinessResponse<Vec<Subscription>>,
         BusinessResponse<String>,
         TodoList,
         CreateTodoListRequest,
         UpdateTodoListRequest,
         DeleteTodoListRequest,
+        TodoProviderKind,
         Task,
         TaskStatus,
         TaskImportance,
+        TaskOrderBy,
+        SortOrder,
         CreateTaskRequest,
         CreateTaskRequestBody,
         UpdateTaskRequest,
         UpdateTaskRequestBody,
         DeleteTaskRequest,
+        CreateTasksRequest,
+        UpdateTasksRequest,
+        UpdateTaskItem,
+        TaskIdsRequest,
+        TaskBatchResult,
+        TaskBatchItemResult,
+        ChecklistItem,
+        CreateChecklistItemRequest,
+        UpdateChecklistItemRequest,
+        TaskRecurrence,
+        RecurrencePattern,
+        RecurrenceRange,
+        RecurrencePatternType,
+        RecurrenceRangeType,
+        DayOfWeek,
+        WeekIndex,
+        Subscription,
+        SubscriptionChangeType,
+        CreateSubscriptionRequest,
     )),
     tags(
         (name = "Todo Lists", description = "Operations for managing todo lists. Use these endpoints to organize tasks into collections."),
         (name = "Tasks", description = "Operations for managing individual tasks within todo lists. Control task lifecycle, status, and metadata."),
+        (name = "Checklist Items", description = "Operations for managing the checklist items (subtasks) of a task."),
+        (name = "Subscriptions", description = "Microsoft Graph change notifications on tasks, published as task events to RabbitMQ."),
         (name = "Operations", description = "Administrative and utility operations for system management.")
     )
 )]
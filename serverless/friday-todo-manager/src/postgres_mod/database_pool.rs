@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tracing::{error, info};
+
+use crate::ENV_CONFIG;
+
+const MAX_CONNECTIONS: u32 = 10;
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pool of `DATABASE_URL`, shared by every request; `None` when it is unset or invalid
+static DATABASE_POOL: Lazy<Option<PgPool>> = Lazy::new(|| {
+    let database_url = ENV_CONFIG.database_url.as_deref()?;
+
+    match create_pool(database_url) {
+        Ok(pool) => {
+            info!("Postgres pool created");
+            Some(pool)
+        }
+        Err(e) => {
+            error!("Failed to create the Postgres pool: {}", e);
+            None
+        }
+    }
+});
+
+pub fn get_pool() -> Option<&'static PgPool> {
+    DATABASE_POOL.as_ref()
+}
+
+/// Connections are opened on first use, so a database outage does not prevent startup
+fn create_pool(database_url: &str) -> Result<PgPool, Box<dyn std::error::Error>> {
+    let pool = PgPoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .acquire_timeout(ACQUIRE_TIMEOUT)
+        .connect_lazy(database_url)?;
+
+    Ok(pool)
+}
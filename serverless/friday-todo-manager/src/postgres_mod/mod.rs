@@ -0,0 +1,3 @@
+pub mod database_pool;
+pub mod postgres_todo_provider;
+pub mod todo_data;
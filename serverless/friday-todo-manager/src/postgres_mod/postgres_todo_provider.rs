@@ -0,0 +1,304 @@
+use crate::business_response::BusinessResponse;
+use crate::postgres_mod::todo_data;
+use crate::provider_mod::todo_provider::TodoProvider;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::task::{
+    CreateTaskRequest, Task, TaskFilterQuery, TaskPage, TaskPageQuery, UpdateTaskRequest,
+};
+use crate::todo_mod::task_filter::{apply_graph_filters, apply_local_filters, apply_page};
+use crate::todo_mod::todo_list::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+/// Lists stored in the Postgres of `DATABASE_URL`, which lets the service run without
+/// Microsoft Graph. Every filtering, ordering and paging option is applied here.
+#[derive(Debug)]
+pub struct PostgresTodoProvider {
+    pool: PgPool,
+}
+
+impl PostgresTodoProvider {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// UUIDs of the list and task, when the task exists in the list
+    async fn find_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<Option<(Uuid, Uuid)>, Box<dyn std::error::Error>> {
+        let (Some(id_todo_list), Ok(id_todo_task)) =
+            (todo_data::parse_list_id(list_id), Uuid::parse_str(task_id))
+        else {
+            return Ok(None);
+        };
+
+        let task = todo_data::get_task(&self.pool, id_todo_list, id_todo_task).await?;
+        Ok(task.map(|_| (id_todo_list, id_todo_task)))
+    }
+
+    /// UUID of the list, when it exists
+    async fn find_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<Option<Uuid>, Box<dyn std::error::Error>> {
+        let Some(id_todo_list) = todo_data::parse_list_id(list_id) else {
+            return Ok(None);
+        };
+
+        let todo_list = todo_data::get_todo_list(&self.pool, id_todo_list).await?;
+        Ok(todo_list.map(|_| id_todo_list))
+    }
+}
+
+#[async_trait(?Send)]
+impl TodoProvider for PostgresTodoProvider {
+    async fn get_todo_lists(
+        &self,
+    ) -> Result<BusinessResponse<Vec<TodoList>>, Box<dyn std::error::Error>> {
+        let todo_lists = todo_data::get_todo_lists(&self.pool).await?;
+        Ok(BusinessResponse::success(todo_lists))
+    }
+
+    async fn get_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let Some(id_todo_list) = todo_data::parse_list_id(list_id) else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        match todo_data::get_todo_list(&self.pool, id_todo_list).await? {
+            Some(todo_list) => Ok(BusinessResponse::success(todo_list)),
+            None => Ok(BusinessResponse::error("Todo list not found")),
+        }
+    }
+
+    async fn create_todo_list(
+        &self,
+        request: CreateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let id_todo_list = Uuid::new_v4();
+        todo_data::insert_todo_list(&self.pool, id_todo_list, &request.display_name).await?;
+
+        info!("Data layer: Created todo list {} in Postgres", id_todo_list);
+        self.get_todo_list(&todo_data::format_list_id(id_todo_list))
+            .await
+    }
+
+    async fn update_todo_list(
+        &self,
+        request: UpdateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>> {
+        let Some(id_todo_list) = self.find_todo_list(&request.id).await? else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        todo_data::update_todo_list(&self.pool, id_todo_list, &request.display_name).await?;
+        self.get_todo_list(&request.id).await
+    }
+
+    async fn delete_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let Some(id_todo_list) = self.find_todo_list(list_id).await? else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        todo_data::delete_todo_list(&self.pool, id_todo_list).await?;
+        Ok(BusinessResponse::success(
+            "Todo list deleted successfully".to_string(),
+        ))
+    }
+
+    async fn get_tasks(
+        &self,
+        list_id: &str,
+        page: &TaskPageQuery,
+        filter: &TaskFilterQuery,
+    ) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>> {
+        let Some(id_todo_list) = self.find_todo_list(list_id).await? else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let mut tasks = todo_data::get_tasks(&self.pool, id_todo_list).await?;
+        apply_graph_filters(&mut tasks, filter);
+        apply_local_filters(&mut tasks, filter);
+
+        match apply_page(tasks, page) {
+            Some(task_page) => Ok(BusinessResponse::success(task_page)),
+            None => Ok(BusinessResponse::error("Invalid cursor")),
+        }
+    }
+
+    async fn get_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        include_checklist: bool,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let (Some(id_todo_list), Ok(id_todo_task)) =
+            (todo_data::parse_list_id(list_id), Uuid::parse_str(task_id))
+        else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let Some(mut task) = todo_data::get_task(&self.pool, id_todo_list, id_todo_task).await?
+        else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        if include_checklist {
+            task.checklist_items =
+                Some(todo_data::get_checklist_items(&self.pool, id_todo_task).await?);
+        }
+
+        Ok(BusinessResponse::success(task))
+    }
+
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let Some(id_todo_list) = self.find_todo_list(&list_id).await? else {
+            return Ok(BusinessResponse::error("Todo list not found"));
+        };
+
+        let id_todo_task = Uuid::new_v4();
+        todo_data::insert_task(&self.pool, id_todo_task, id_todo_list, &request).await?;
+
+        info!("Data layer: Created task {} in Postgres", id_todo_task);
+        self.get_task(&list_id, &id_todo_task.to_string(), false)
+            .await
+    }
+
+    async fn update_task(
+        &self,
+        request: UpdateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
+        let list_id = request.list_id.clone().unwrap_or_default();
+        let task_id = request.id.clone().unwrap_or_default();
+        let Some((_, id_todo_task)) = self.find_task(&list_id, &task_id).await? else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        todo_data::update_task(&self.pool, id_todo_task, &request).await?;
+        self.get_task(&list_id, &task_id, false).await
+    }
+
+    async fn delete_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let Some((_, id_todo_task)) = self.find_task(list_id, task_id).await? else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        todo_data::delete_task(&self.pool, id_todo_task).await?;
+        Ok(BusinessResponse::success(
+            "Task deleted successfully".to_string(),
+        ))
+    }
+
+    async fn get_checklist_items(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+        let Some((_, id_todo_task)) = self.find_task(list_id, task_id).await? else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let checklist_items = todo_data::get_checklist_items(&self.pool, id_todo_task).await?;
+        Ok(BusinessResponse::success(checklist_items))
+    }
+
+    async fn get_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let (Some((_, id_todo_task)), Ok(id_todo_checklist_item)) = (
+            self.find_task(list_id, task_id).await?,
+            Uuid::parse_str(checklist_item_id),
+        ) else {
+            return Ok(BusinessResponse::error("Checklist item not found"));
+        };
+
+        match todo_data::get_checklist_item(&self.pool, id_todo_task, id_todo_checklist_item)
+            .await?
+        {
+            Some(checklist_item) => Ok(BusinessResponse::success(checklist_item)),
+            None => Ok(BusinessResponse::error("Checklist item not found")),
+        }
+    }
+
+    async fn create_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &CreateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let Some((_, id_todo_task)) = self.find_task(list_id, task_id).await? else {
+            return Ok(BusinessResponse::error("Task not found"));
+        };
+
+        let id_todo_checklist_item = Uuid::new_v4();
+        todo_data::insert_checklist_item(&self.pool, id_todo_checklist_item, id_todo_task, request)
+            .await?;
+
+        self.get_checklist_item(list_id, task_id, &id_todo_checklist_item.to_string())
+            .await
+    }
+
+    async fn update_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        request: &UpdateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+        let existing = self
+            .get_checklist_item(list_id, task_id, checklist_item_id)
+            .await?;
+        let Some(checklist_item) = existing.data else {
+            return Ok(existing);
+        };
+
+        let id_todo_checklist_item = Uuid::parse_str(&checklist_item.id)?;
+        todo_data::update_checklist_item(&self.pool, id_todo_checklist_item, request).await?;
+
+        self.get_checklist_item(list_id, task_id, checklist_item_id)
+            .await
+    }
+
+    async fn delete_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+        let existing = self
+            .get_checklist_item(list_id, task_id, checklist_item_id)
+            .await?;
+        let Some(checklist_item) = existing.data else {
+            return Ok(BusinessResponse::error("Checklist item not found"));
+        };
+
+        let id_todo_checklist_item = Uuid::parse_str(&checklist_item.id)?;
+        todo_data::delete_checklist_item(&self.pool, id_todo_checklist_item).await?;
+
+        Ok(BusinessResponse::success(
+            "Checklist item deleted successfully".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,360 @@
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{PgPool, Row};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::provider_mod::todo_provider::TodoProviderKind;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::recurrence::TaskRecurrence;
+use crate::todo_mod::task::{CreateTaskRequest, Task, TaskImportance, UpdateTaskRequest};
+use crate::todo_mod::todo_list::TodoList;
+
+/// ID of the list exposed by the API: the UUID prefixed with `postgres:`
+pub fn format_list_id(id_todo_list: Uuid) -> String {
+    format!(
+        "{}{}",
+        TodoProviderKind::Postgres
+            .list_id_prefix()
+            .unwrap_or_default(),
+        id_todo_list
+    )
+}
+
+/// UUID of a list ID exposed by the API; `None` when it is not a postgres list ID
+pub fn parse_list_id(list_id: &str) -> Option<Uuid> {
+    let prefix = TodoProviderKind::Postgres.list_id_prefix()?;
+    Uuid::parse_str(list_id.strip_prefix(prefix)?).ok()
+}
+
+pub async fn get_todo_lists(pool: &PgPool) -> Result<Vec<TodoList>, Box<dyn std::error::Error>> {
+    let rows = sqlx::query("SELECT * FROM fn_get_all_todo_lists()")
+        .fetch_all(pool)
+        .await?;
+
+    rows.iter().map(todo_list_from_row).collect()
+}
+
+pub async fn get_todo_list(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+) -> Result<Option<TodoList>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_todo_list_by_id($1)")
+        .bind(id_todo_list)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(todo_list_from_row).transpose()
+}
+
+pub async fn insert_todo_list(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+    display_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_ins_todo_list($1, $2)")
+        .bind(id_todo_list)
+        .bind(display_name)
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Todo list {} inserted", id_todo_list);
+
+    Ok(())
+}
+
+pub async fn update_todo_list(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+    display_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_upd_todo_list($1, $2)")
+        .bind(id_todo_list)
+        .bind(display_name)
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Todo list {} updated", id_todo_list);
+
+    Ok(())
+}
+
+/// Deletes the list with its tasks and their checklist items
+pub async fn delete_todo_list(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_del_todo_list($1)")
+        .bind(id_todo_list)
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Todo list {} deleted", id_todo_list);
+
+    Ok(())
+}
+
+/// Tasks of the list, oldest first, without their checklist items
+pub async fn get_tasks(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let rows = sqlx::query("SELECT * FROM fn_get_todo_tasks_by_list($1)")
+        .bind(id_todo_list)
+        .fetch_all(pool)
+        .await?;
+
+    rows.iter().map(task_from_row).collect()
+}
+
+pub async fn get_task(
+    pool: &PgPool,
+    id_todo_list: Uuid,
+    id_todo_task: Uuid,
+) -> Result<Option<Task>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_todo_task_by_id($1, $2)")
+        .bind(id_todo_list)
+        .bind(id_todo_task)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(task_from_row).transpose()
+}
+
+pub async fn insert_task(
+    pool: &PgPool,
+    id_todo_task: Uuid,
+    id_todo_list: Uuid,
+    request: &CreateTaskRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_ins_todo_task($1, $2, $3, $4, $5, $6, $7, $8, $9)")
+        .bind(id_todo_task)
+        .bind(id_todo_list)
+        .bind(&request.title)
+        .bind(&request.body)
+        .bind(
+            request
+                .importance
+                .unwrap_or(TaskImportance::Normal)
+                .as_str(),
+        )
+        .bind(request.is_reminder_on.unwrap_or(false))
+        .bind(request.reminder_date_time)
+        .bind(request.due_date_time)
+        .bind(request.recurrence.as_ref().map(Json))
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Task {} inserted", id_todo_task);
+
+    Ok(())
+}
+
+/// Updates the fields present in the request, keeping the others
+pub async fn update_task(
+    pool: &PgPool,
+    id_todo_task: Uuid,
+    request: &UpdateTaskRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_upd_todo_task($1, $2, $3, $4, $5, $6, $7, $8, $9)")
+        .bind(id_todo_task)
+        .bind(&request.title)
+        .bind(&request.body)
+        .bind(request.status.map(|status| status.as_str()))
+        .bind(request.importance.map(|importance| importance.as_str()))
+        .bind(request.is_reminder_on)
+        .bind(request.reminder_date_time)
+        .bind(request.due_date_time)
+        .bind(request.recurrence.as_ref().map(Json))
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Task {} updated", id_todo_task);
+
+    Ok(())
+}
+
+/// Deletes the task with its checklist items
+pub async fn delete_task(
+    pool: &PgPool,
+    id_todo_task: Uuid,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_del_todo_task($1)")
+        .bind(id_todo_task)
+        .execute(pool)
+        .await?;
+
+    debug!("Data layer: Task {} deleted", id_todo_task);
+
+    Ok(())
+}
+
+pub async fn get_checklist_items(
+    pool: &PgPool,
+    id_todo_task: Uuid,
+) -> Result<Vec<ChecklistItem>, Box<dyn std::error::Error>> {
+    let rows = sqlx::query("SELECT * FROM fn_get_todo_checklist_items_by_task($1)")
+        .bind(id_todo_task)
+        .fetch_all(pool)
+        .await?;
+
+    rows.iter().map(checklist_item_from_row).collect()
+}
+
+pub async fn get_checklist_item(
+    pool: &PgPool,
+    id_todo_task: Uuid,
+    id_todo_checklist_item: Uuid,
+) -> Result<Option<ChecklistItem>, Box<dyn std::error::Error>> {
+    let row = sqlx::query("SELECT * FROM fn_get_todo_checklist_item_by_id($1, $2)")
+        .bind(id_todo_task)
+        .bind(id_todo_checklist_item)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(checklist_item_from_row).transpose()
+}
+
+pub async fn insert_checklist_item(
+    pool: &PgPool,
+    id_todo_checklist_item: Uuid,
+    id_todo_task: Uuid,
+    request: &CreateChecklistItemRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_ins_todo_checklist_item($1, $2, $3, $4)")
+        .bind(id_todo_checklist_item)
+        .bind(id_todo_task)
+        .bind(&request.display_name)
+        .bind(request.is_checked.unwrap_or(false))
+        .execute(pool)
+        .await?;
+
+    debug!(
+        "Data layer: Checklist item {} inserted",
+        id_todo_checklist_item
+    );
+
+    Ok(())
+}
+
+pub async fn update_checklist_item(
+    pool: &PgPool,
+    id_todo_checklist_item: Uuid,
+    request: &UpdateChecklistItemRequest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_upd_todo_checklist_item($1, $2, $3)")
+        .bind(id_todo_checklist_item)
+        .bind(&request.display_name)
+        .bind(request.is_checked)
+        .execute(pool)
+        .await?;
+
+    debug!(
+        "Data layer: Checklist item {} updated",
+        id_todo_checklist_item
+    );
+
+    Ok(())
+}
+
+pub async fn delete_checklist_item(
+    pool: &PgPool,
+    id_todo_checklist_item: Uuid,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sqlx::query("CALL pr_del_todo_checklist_item($1)")
+        .bind(id_todo_checklist_item)
+        .execute(pool)
+        .await?;
+
+    debug!(
+        "Data layer: Checklist item {} deleted",
+        id_todo_checklist_item
+    );
+
+    Ok(())
+}
+
+fn todo_list_from_row(row: &PgRow) -> Result<TodoList, Box<dyn std::error::Error>> {
+    let id_todo_list: Uuid = row.try_get("id_todo_list")?;
+
+    let display_name: String = row.try_get("display_name")?;
+
+    let created_at: DateTime<Utc> = row.try_get("created_at")?;
+
+    let updated_at: DateTime<Utc> = row.try_get("updated_at")?;
+
+    Ok(TodoList {
+        id: format_list_id(id_todo_list),
+        display_name,
+        is_owner: true,
+        is_shared: false,
+        wellknown_list_name: None,
+        created_date_time: Some(created_at),
+        last_modified_date_time: Some(updated_at),
+    })
+}
+
+fn task_from_row(row: &PgRow) -> Result<Task, Box<dyn std::error::Error>> {
+    let id_todo_task: Uuid = row.try_get("id_todo_task")?;
+
+    let title: String = row.try_get("title")?;
+
+    let body: Option<String> = row.try_get("body")?;
+
+    let status: String = row.try_get("status")?;
+
+    let importance: String = row.try_get("importance")?;
+
+    let is_reminder_on: bool = row.try_get("is_reminder_on")?;
+
+    let reminder_date_time: Option<DateTime<Utc>> = row.try_get("reminder_date_time")?;
+
+    let due_date_time: Option<DateTime<Utc>> = row.try_get("due_date_time")?;
+
+    let recurrence: Option<Json<TaskRecurrence>> = row.try_get("recurrence")?;
+
+    let completed_at: Option<DateTime<Utc>> = row.try_get("completed_at")?;
+
+    let created_at: DateTime<Utc> = row.try_get("created_at")?;
+
+    let updated_at: DateTime<Utc> = row.try_get("updated_at")?;
+
+    Ok(Task {
+        id: id_todo_task.to_string(),
+        title,
+        body,
+        status: status.parse()?,
+        importance: importance.parse()?,
+        is_reminder_on,
+        reminder_date_time,
+        due_date_time,
+        recurrence: recurrence.map(|recurrence| recurrence.0),
+        completed_date_time: completed_at,
+        created_date_time: created_at,
+        last_modified_date_time: updated_at,
+        checklist_items: None,
+    })
+}
+
+fn checklist_item_from_row(row: &PgRow) -> Result<ChecklistItem, Box<dyn std::error::Error>> {
+    let id_todo_checklist_item: Uuid = row.try_get("id_todo_checklist_item")?;
+
+    let display_name: String = row.try_get("display_name")?;
+
+    let is_checked: bool = row.try_get("is_checked")?;
+
+    let created_at: DateTime<Utc> = row.try_get("created_at")?;
+
+    let checked_at: Option<DateTime<Utc>> = row.try_get("checked_at")?;
+
+    Ok(ChecklistItem {
+        id: id_todo_checklist_item.to_string(),
+        display_name,
+        is_checked,
+        created_date_time: created_at,
+        checked_date_time: checked_at,
+    })
+}
@@ -0,0 +1,2 @@
+pub mod todo_provider;
+pub mod todo_provider_logic;
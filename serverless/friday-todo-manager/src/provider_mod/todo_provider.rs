@@ -0,0 +1,312 @@
+use crate::business_response::BusinessResponse;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use crate::todo_mod::task::{
+    CreateTaskRequest, Task, TaskBatchItemResult, TaskFilterQuery, TaskPage, TaskPageQuery,
+    UpdateTaskRequest,
+};
+use crate::todo_mod::todo_list::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use async_trait::async_trait;
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+use tracing::error;
+use utoipa::ToSchema;
+
+/// Backend the lists, tasks and checklist items are stored in
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "TodoProviderKind")]
+#[serde(rename_all = "lowercase")]
+pub enum TodoProviderKind {
+    Microsoft,
+    Postgres,
+    Google,
+}
+
+impl TodoProviderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoProviderKind::Microsoft => "microsoft",
+            TodoProviderKind::Postgres => "postgres",
+            TodoProviderKind::Google => "google",
+        }
+    }
+
+    /// Prefix of the list IDs of the provider. Microsoft Graph IDs are not prefixed, so the
+    /// IDs handed out before other providers existed keep working.
+    pub fn list_id_prefix(&self) -> Option<&'static str> {
+        match self {
+            TodoProviderKind::Microsoft => None,
+            TodoProviderKind::Postgres => Some("postgres:"),
+            TodoProviderKind::Google => Some("google:"),
+        }
+    }
+
+    /// Provider that owns the list, from the prefix of its ID
+    pub fn of_list_id(list_id: &str) -> Self {
+        [TodoProviderKind::Postgres, TodoProviderKind::Google]
+            .into_iter()
+            .find(|kind| {
+                kind.list_id_prefix()
+                    .is_some_and(|prefix| list_id.starts_with(prefix))
+            })
+            .unwrap_or(TodoProviderKind::Microsoft)
+    }
+}
+
+impl FromStr for TodoProviderKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "microsoft" => Ok(TodoProviderKind::Microsoft),
+            "postgres" => Ok(TodoProviderKind::Postgres),
+            "google" => Ok(TodoProviderKind::Google),
+            other => Err(format!("Unknown todo provider: {}", other)),
+        }
+    }
+}
+
+/// Operations every backend implements. List IDs are received and returned as exposed by the
+/// API, i.e. with the prefix of the provider. Business errors containing "not found" are
+/// answered with 404.
+#[async_trait(?Send)]
+pub trait TodoProvider {
+    async fn get_todo_lists(
+        &self,
+    ) -> Result<BusinessResponse<Vec<TodoList>>, Box<dyn std::error::Error>>;
+
+    async fn get_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>>;
+
+    async fn create_todo_list(
+        &self,
+        request: CreateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>>;
+
+    async fn update_todo_list(
+        &self,
+        request: UpdateTodoListRequest,
+    ) -> Result<BusinessResponse<TodoList>, Box<dyn std::error::Error>>;
+
+    async fn delete_todo_list(
+        &self,
+        list_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>>;
+
+    /// Tasks of a list with every filtering, search and ordering option of `filter` applied.
+    /// A provider that cannot apply an option across pages rejects it on paged requests
+    /// instead of applying it within the page.
+    async fn get_tasks(
+        &self,
+        list_id: &str,
+        page: &TaskPageQuery,
+        filter: &TaskFilterQuery,
+    ) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>>;
+
+    async fn get_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        include_checklist: bool,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>>;
+
+    /// Creates the task alone; checklist items of the request are created by the caller
+    async fn create_task(
+        &self,
+        request: CreateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>>;
+
+    async fn update_task(
+        &self,
+        request: UpdateTaskRequest,
+    ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>>;
+
+    async fn delete_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>>;
+
+    async fn get_checklist_items(
+        &self,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>>;
+
+    async fn get_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>>;
+
+    async fn create_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &CreateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>>;
+
+    async fn update_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+        request: &UpdateChecklistItemRequest,
+    ) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>>;
+
+    async fn delete_checklist_item(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        checklist_item_id: &str,
+    ) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>>;
+
+    /// Creates the tasks one by one, reporting each outcome with the index it was given.
+    /// Providers with a bulk API override it.
+    async fn create_tasks(
+        &self,
+        _list_id: &str,
+        requests: Vec<(usize, CreateTaskRequest)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let mut results = Vec::with_capacity(requests.len());
+        for (index, request) in requests {
+            let result = self.create_task(request).await;
+            results.push(batch_item_result(index, None, 201, result));
+        }
+
+        Ok(BusinessResponse::success(results))
+    }
+
+    /// Updates the tasks one by one, see `create_tasks`
+    async fn update_tasks(
+        &self,
+        _list_id: &str,
+        requests: Vec<(usize, UpdateTaskRequest)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let mut results = Vec::with_capacity(requests.len());
+        for (index, request) in requests {
+            let task_id = request.id.clone();
+            let result = self.update_task(request).await;
+            results.push(batch_item_result(index, task_id, 200, result));
+        }
+
+        Ok(BusinessResponse::success(results))
+    }
+
+    /// Deletes the tasks one by one, see `create_tasks`
+    async fn delete_tasks(
+        &self,
+        list_id: &str,
+        task_ids: Vec<(usize, String)>,
+    ) -> Result<BusinessResponse<Vec<TaskBatchItemResult>>, Box<dyn std::error::Error>> {
+        let mut results = Vec::with_capacity(task_ids.len());
+        for (index, task_id) in task_ids {
+            let result =
+                self.delete_task(list_id, &task_id)
+                    .await
+                    .map(|response| BusinessResponse {
+                        success: response.success,
+                        data: None,
+                        errors: response.errors,
+                    });
+            results.push(batch_item_result(index, Some(task_id), 204, result));
+        }
+
+        Ok(BusinessResponse::success(results))
+    }
+}
+
+/// Result of one item of a bulk operation done with single calls, with the status the
+/// controllers would have answered the call with
+fn batch_item_result(
+    index: usize,
+    task_id: Option<String>,
+    success_status: u16,
+    result: Result<BusinessResponse<Task>, Box<dyn std::error::Error>>,
+) -> TaskBatchItemResult {
+    match result {
+        Ok(response) if response.success => {
+            let task_id = response
+                .data
+                .as_ref()
+                .map(|task| task.id.clone())
+                .or(task_id)
+                .unwrap_or_default();
+            TaskBatchItemResult::success(index, task_id, success_status, response.data)
+        }
+        Ok(response) => {
+            let status = if response.errors.iter().any(|e| e.contains("not found")) {
+                404
+            } else {
+                400
+            };
+            TaskBatchItemResult::error(index, task_id, status, &response.errors.join(", "))
+        }
+        Err(e) => {
+            error!(
+                "Provider layer: Error on item {} of bulk operation: {}",
+                index, e
+            );
+            TaskBatchItemResult::error(index, task_id, 500, "An internal error occurred")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_ids_without_a_prefix_belong_to_microsoft() {
+        assert_eq!(
+            TodoProviderKind::of_list_id("AAMkAGVmMDEzMTM4LTZmYWUtNDdkNC1hMDZiLTU1OGY5OTZhNGY2NAAuAAAAAAAiQ8W967B7TKBjgx9rVEURBwAiIsqMbYjsT5e-T_KzowKTAAAAAAESAAA"),
+            TodoProviderKind::Microsoft
+        );
+        assert_eq!(
+            TodoProviderKind::of_list_id(""),
+            TodoProviderKind::Microsoft
+        );
+        // The prefix must start the ID
+        assert_eq!(
+            TodoProviderKind::of_list_id("AAMkAD-postgres:1"),
+            TodoProviderKind::Microsoft
+        );
+    }
+
+    #[test]
+    fn postgres_list_ids_are_prefixed() {
+        assert_eq!(
+            TodoProviderKind::of_list_id("postgres:7f0c5f8e-3a43-4bb0-9d0b-2c1c3f1f5a10"),
+            TodoProviderKind::Postgres
+        );
+    }
+
+    #[test]
+    fn google_list_ids_are_prefixed() {
+        assert_eq!(
+            TodoProviderKind::of_list_id("google:MDEzMTM4MjQ1NTQ"),
+            TodoProviderKind::Google
+        );
+        assert_eq!(
+            TodoProviderKind::of_list_id("Google:MDEzMTM4MjQ1NTQ"),
+            TodoProviderKind::Microsoft
+        );
+    }
+
+    #[test]
+    fn parses_the_provider_names() {
+        assert_eq!(
+            " Postgres ".parse::<TodoProviderKind>(),
+            Ok(TodoProviderKind::Postgres)
+        );
+        assert_eq!(
+            "microsoft".parse::<TodoProviderKind>(),
+            Ok(TodoProviderKind::Microsoft)
+        );
+        assert!("dropbox".parse::<TodoProviderKind>().is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::business_response::BusinessResponse;
+use crate::google_tasks_mod::google_tasks_provider::GoogleTasksProvider;
+use crate::microsoft_graph_mod::microsoft_graph_provider::MicrosoftGraphProvider;
+use crate::postgres_mod::{database_pool, postgres_todo_provider::PostgresTodoProvider};
+use crate::provider_mod::todo_provider::{TodoProvider, TodoProviderKind};
+use crate::ENV_CONFIG;
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+/// Providers of `TODO_PROVIDERS`, the first one being the default of new lists
+static ENABLED_PROVIDERS: Lazy<Vec<TodoProviderKind>> = Lazy::new(|| {
+    let mut providers = Vec::new();
+    for value in ENV_CONFIG.todo_providers.split(',') {
+        let provider = value
+            .parse::<TodoProviderKind>()
+            .unwrap_or_else(|e| panic!("Invalid TODO_PROVIDERS: {}", e));
+        if !providers.contains(&provider) {
+            providers.push(provider);
+        }
+    }
+    providers
+});
+
+/// Checks the provider configuration, so that the service fails on startup rather than on
+/// the first request. Returns the enabled providers.
+pub fn check_configuration() -> &'static [TodoProviderKind] {
+    if is_enabled(TodoProviderKind::Postgres) && database_pool::get_pool().is_none() {
+        panic!("A valid DATABASE_URL is required by the postgres todo provider");
+    }
+
+    if is_enabled(TodoProviderKind::Google)
+        && (ENV_CONFIG.service_client_id.is_none() || ENV_CONFIG.service_client_secret.is_none())
+    {
+        panic!(
+            "SERVICE_CLIENT_ID and SERVICE_CLIENT_SECRET are required by the google todo provider"
+        );
+    }
+
+    &ENABLED_PROVIDERS
+}
+
+pub fn enabled_providers() -> &'static [TodoProviderKind] {
+    &ENABLED_PROVIDERS
+}
+
+pub fn default_provider() -> TodoProviderKind {
+    ENABLED_PROVIDERS[0]
+}
+
+pub fn is_enabled(kind: TodoProviderKind) -> bool {
+    ENABLED_PROVIDERS.contains(&kind)
+}
+
+pub fn get_provider<T>(
+    kind: TodoProviderKind,
+) -> Result<Box<dyn TodoProvider>, BusinessResponse<T>> {
+    if !is_enabled(kind) {
+        warn!(
+            "Logic layer: Todo provider {} is not enabled",
+            kind.as_str()
+        );
+        return Err(BusinessResponse::error(&format!(
+            "Todo provider {} is not enabled",
+            kind.as_str()
+        )));
+    }
+
+    match kind {
+        TodoProviderKind::Microsoft => Ok(Box::new(MicrosoftGraphProvider::new())),
+        TodoProviderKind::Postgres => match database_pool::get_pool() {
+            Some(pool) => Ok(Box::new(PostgresTodoProvider::new(pool.clone()))),
+            None => Err(BusinessResponse::error("Postgres is not configured")),
+        },
+        TodoProviderKind::Google => Ok(Box::new(GoogleTasksProvider::new())),
+    }
+}
+
+/// Provider owning the list, from the prefix of its ID
+pub fn get_list_provider<T>(list_id: &str) -> Result<Box<dyn TodoProvider>, BusinessResponse<T>> {
+    get_provider(TodoProviderKind::of_list_id(list_id))
+}
@@ -0,0 +1,53 @@
+use std::error::Error;
+
+use lapin::{
+    options::{BasicPublishOptions, QueueDeclareOptions},
+    types::FieldTable,
+    BasicProperties, Connection, ConnectionProperties,
+};
+use tracing::error;
+
+/// URL format: `amqp://<user>:<password>@<host>:5672/%2f`
+pub async fn connect(rabbitmq_url: &str) -> Result<Connection, Box<dyn Error>> {
+    Connection::connect(rabbitmq_url, ConnectionProperties::default())
+        .await
+        .map_err(|e| {
+            error!("Failed to connect to RabbitMQ: {}", e);
+            e.into()
+        })
+}
+
+/// Declares the queue and publishes the payload as a persistent JSON message
+pub async fn queue_publish(
+    connection: &Connection,
+    queue_name: &str,
+    payload: &str,
+) -> Result<(), Box<dyn Error>> {
+    let channel = connection.create_channel().await?;
+
+    channel
+        .queue_declare(
+            queue_name,
+            QueueDeclareOptions {
+                durable: true,
+                ..QueueDeclareOptions::default()
+            },
+            FieldTable::default(),
+        )
+        .await?;
+
+    channel
+        .basic_publish(
+            "",
+            queue_name,
+            BasicPublishOptions::default(),
+            payload.as_bytes(),
+            BasicProperties::default()
+                .with_content_type("application/json".into())
+                .with_delivery_mode(2),
+        )
+        .await?
+        .await?;
+
+    Ok(())
+}
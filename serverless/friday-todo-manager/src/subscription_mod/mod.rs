@@ -0,0 +1,4 @@
+mod message_broker;
+pub mod subscription;
+pub mod subscription_controller;
+pub mod subscription_logic;
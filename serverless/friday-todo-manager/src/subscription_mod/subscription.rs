@@ -0,0 +1,184 @@
+use crate::microsoft_graph_mod::graph_date_time::parse_graph_timestamp;
+use crate::microsoft_graph_mod::subscription_response::SubscriptionResponse;
+use crate::todo_mod::task::Task;
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Microsoft Graph change-notification subscription on the tasks of a list
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "Subscription")]
+pub struct Subscription {
+    #[schema(example = "7f105c7d-2dc5-4530-97cd-4e7ae6534c07")]
+    pub id: String,
+
+    /// List whose tasks are watched, when the resource is a task collection
+    pub list_id: Option<String>,
+
+    #[schema(example = "/me/todo/lists/AAMkAGVmMDEz/tasks")]
+    pub resource: String,
+
+    pub change_types: Vec<SubscriptionChangeType>,
+
+    #[schema(example = "https://k8s.z33p.com/api/friday-todo-manager/subscriptions/notifications")]
+    pub notification_url: String,
+
+    pub expiration_date_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "SubscriptionChangeType")]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionChangeType {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl SubscriptionChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionChangeType::Created => "created",
+            SubscriptionChangeType::Updated => "updated",
+            SubscriptionChangeType::Deleted => "deleted",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "created" => Some(SubscriptionChangeType::Created),
+            "updated" => Some(SubscriptionChangeType::Updated),
+            "deleted" => Some(SubscriptionChangeType::Deleted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "CreateSubscriptionRequest")]
+pub struct CreateSubscriptionRequest {
+    #[schema(
+        example = "AAMkAGVmMDEzMTM4LTZmYWUtNDdkNC1hMDZiLTU1OGY5OTZhNGY2NAAuAAAAAAAiQ8W967B7TKBjgx9rVEURBwAiIsqMbYjsT5e-T_KzowKTAAAAAAESAAA"
+    )]
+    pub list_id: String,
+
+    /// Changes to be notified of; all of them when omitted
+    pub change_types: Option<Vec<SubscriptionChangeType>>,
+}
+
+/// Query Microsoft Graph adds when validating the notification URL
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationQuery {
+    #[serde(rename = "validationToken")]
+    #[param(rename = "validationToken")]
+    pub validation_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TaskEventType {
+    #[serde(rename = "task.created")]
+    Created,
+    #[serde(rename = "task.updated")]
+    Updated,
+    /// An update that moved the task to `completed`
+    #[serde(rename = "task.completed")]
+    Completed,
+    #[serde(rename = "task.deleted")]
+    Deleted,
+}
+
+impl TaskEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskEventType::Created => "task.created",
+            TaskEventType::Updated => "task.updated",
+            TaskEventType::Completed => "task.completed",
+            TaskEventType::Deleted => "task.deleted",
+        }
+    }
+}
+
+/// Normalized task change published to RabbitMQ for every Graph change notification
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskEvent {
+    /// Unique identifier of the event, for idempotent consumers
+    pub id_event: Uuid,
+    pub event_type: TaskEventType,
+    pub list_id: String,
+    pub task_id: String,
+    pub subscription_id: String,
+    /// Task as of when the notification was processed; absent on deletions
+    pub task: Option<Task>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl TaskEvent {
+    pub fn new(
+        event_type: TaskEventType,
+        list_id: &str,
+        task_id: &str,
+        subscription_id: &str,
+        task: Option<Task>,
+    ) -> Self {
+        Self {
+            id_event: Uuid::new_v4(),
+            event_type,
+            list_id: list_id.to_string(),
+            task_id: task_id.to_string(),
+            subscription_id: subscription_id.to_string(),
+            task,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl TryFrom<SubscriptionResponse> for Subscription {
+    type Error = String;
+
+    fn try_from(response: SubscriptionResponse) -> Result<Self, Self::Error> {
+        let change_types = response
+            .change_type
+            .split(',')
+            .map(|change_type| {
+                SubscriptionChangeType::parse(change_type)
+                    .ok_or_else(|| format!("Unknown change type: {}", change_type))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Subscription {
+            id: response.id,
+            list_id: list_id_of_resource(&response.resource),
+            expiration_date_time: parse_graph_timestamp(&response.expiration_date_time)?,
+            resource: response.resource,
+            change_types,
+            notification_url: response.notification_url,
+        })
+    }
+}
+
+/// List id in a todo resource path such as `/me/todo/lists/{list-id}/tasks`,
+/// `Users/{user-id}/todo/lists/{list-id}/tasks/{task-id}` or the OData key form
+/// `Users('{user-id}')/todo/lists('{list-id}')/tasks('{task-id}')`
+pub fn list_id_of_resource(resource: &str) -> Option<String> {
+    let mut segments = resource.split('/');
+
+    while let Some(segment) = segments.next() {
+        let lowercase = segment.to_ascii_lowercase();
+
+        let list_id = if lowercase == "lists" {
+            segments.next()
+        } else if lowercase.starts_with("lists('") && segment.ends_with("')") {
+            Some(&segment["lists('".len()..segment.len() - 2])
+        } else {
+            continue;
+        };
+
+        return list_id
+            .filter(|list_id| !list_id.is_empty())
+            .map(|list_id| list_id.to_string());
+    }
+
+    None
+}
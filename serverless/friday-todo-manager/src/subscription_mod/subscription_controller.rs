@@ -0,0 +1,175 @@
+use crate::business_response::BusinessResponse;
+use crate::microsoft_graph_mod::change_notification::ChangeNotificationCollection;
+use crate::subscription_mod::subscription::{CreateSubscriptionRequest, NotificationQuery};
+use crate::subscription_mod::subscription_logic;
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+#[utoipa::path(
+    get,
+    path = "/api/friday-todo-manager/subscriptions",
+    responses(
+        (status = 200, description = "Subscriptions notifying this service retrieved successfully", body = BusinessResponse<Vec<Subscription>>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    tag = "Subscriptions"
+)]
+#[get("/api/friday-todo-manager/subscriptions")]
+pub async fn get_subscriptions() -> impl Responder {
+    info!("Controller layer: GET /api/friday-todo-manager/subscriptions");
+
+    to_http_response(
+        subscription_logic::get_subscriptions().await,
+        "retrieving subscriptions",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/subscriptions",
+    request_body = CreateSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription on the tasks of the list created successfully", body = BusinessResponse<Subscription>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    tag = "Subscriptions"
+)]
+#[post("/api/friday-todo-manager/subscriptions")]
+pub async fn create_subscription(request: web::Json<CreateSubscriptionRequest>) -> impl Responder {
+    info!("Controller layer: POST /api/friday-todo-manager/subscriptions");
+
+    to_http_response(
+        subscription_logic::create_subscription(request.into_inner()).await,
+        "creating the subscription",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/subscriptions/{subscription_id}/renew",
+    responses(
+        (status = 200, description = "Subscription expiration extended successfully", body = BusinessResponse<Subscription>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Subscription not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("subscription_id" = String, Path, description = "Subscription unique identifier")
+    ),
+    tag = "Subscriptions"
+)]
+#[post("/api/friday-todo-manager/subscriptions/{subscription_id}/renew")]
+pub async fn renew_subscription(subscription_id: web::Path<String>) -> impl Responder {
+    info!(
+        "Controller layer: POST /api/friday-todo-manager/subscriptions/{}/renew",
+        subscription_id
+    );
+
+    to_http_response(
+        subscription_logic::renew_subscription(&subscription_id).await,
+        "renewing the subscription",
+    )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/friday-todo-manager/subscriptions/{subscription_id}",
+    responses(
+        (status = 200, description = "Subscription deleted successfully", body = BusinessResponse<String>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Subscription not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("subscription_id" = String, Path, description = "Subscription unique identifier")
+    ),
+    tag = "Subscriptions"
+)]
+#[delete("/api/friday-todo-manager/subscriptions/{subscription_id}")]
+pub async fn delete_subscription(subscription_id: web::Path<String>) -> impl Responder {
+    info!(
+        "Controller layer: DELETE /api/friday-todo-manager/subscriptions/{}",
+        subscription_id
+    );
+
+    to_http_response(
+        subscription_logic::delete_subscription(&subscription_id).await,
+        "deleting the subscription",
+    )
+}
+
+/// Notification URL called by Microsoft Graph. Echoes `validationToken` when Graph validates
+/// the URL; otherwise accepts the notifications and processes them in the background.
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/subscriptions/notifications",
+    params(NotificationQuery),
+    responses(
+        (status = 200, description = "Validation token echoed as text/plain", body = String),
+        (status = 202, description = "Notifications accepted for processing"),
+        (status = 400, description = "Body is not a Microsoft Graph notification collection")
+    ),
+    tag = "Subscriptions"
+)]
+#[post("/api/friday-todo-manager/subscriptions/notifications")]
+pub async fn receive_notifications(
+    query: web::Query<NotificationQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    if let Some(validation_token) = query.into_inner().validation_token {
+        info!("Controller layer: Answering notification URL validation");
+        return HttpResponse::Ok()
+            .content_type("text/plain")
+            .body(validation_token);
+    }
+
+    let collection = match serde_json::from_slice::<ChangeNotificationCollection>(&body) {
+        Ok(collection) => collection,
+        Err(e) => {
+            warn!("Controller layer: Invalid notification body: {}", e);
+            return HttpResponse::BadRequest().finish();
+        }
+    };
+
+    let received = collection.value.len();
+    let accepted = subscription_logic::handle_notifications(collection);
+    info!(
+        "Controller layer: Accepted {} of {} notifications",
+        accepted, received
+    );
+
+    HttpResponse::Accepted().finish()
+}
+
+fn to_http_response<T: Serialize>(
+    result: Result<BusinessResponse<T>, Box<dyn std::error::Error>>,
+    action: &str,
+) -> HttpResponse {
+    match result {
+        Ok(response) => {
+            if response.success {
+                info!("Controller layer: Successfully finished {}", action);
+                HttpResponse::Ok().json(response)
+            } else if response.errors.iter().any(|e| e.contains("not found")) {
+                error!("Controller layer: Not found: {:?}", response.errors);
+                HttpResponse::NotFound().json(response)
+            } else {
+                error!(
+                    "Controller layer: Business logic error: {:?}",
+                    response.errors
+                );
+                HttpResponse::BadRequest().json(response)
+            }
+        }
+        Err(e) => {
+            error!("Controller layer: System error {}: {}", action, e);
+            HttpResponse::InternalServerError().json(BusinessResponse::<String>::error(&format!(
+                "An internal error occurred while {}",
+                action
+            )))
+        }
+    }
+}
@@ -0,0 +1,691 @@
+use crate::business_response::BusinessResponse;
+use crate::cache_mod::task_cache_logic;
+use crate::microsoft_graph_mod::change_notification::{
+    ChangeNotification, ChangeNotificationCollection,
+};
+use crate::microsoft_graph_mod::subscriptions_microsoft_graph_api::SubscriptionsMicrosoftGraphApi;
+use crate::microsoft_graph_mod::tasks_microsoft_graph_api::TasksMicrosoftGraphApi;
+use crate::oauth_mod::oauth_api::OAuthApi;
+use crate::provider_mod::todo_provider::TodoProviderKind;
+use crate::subscription_mod::message_broker;
+use crate::subscription_mod::subscription::{
+    list_id_of_resource, CreateSubscriptionRequest, Subscription, SubscriptionChangeType,
+    TaskEvent, TaskEventType,
+};
+use crate::todo_mod::task::{Task, TaskStatus};
+use crate::ENV_CONFIG;
+use chrono::{Duration, Utc};
+use tracing::{debug, error, info, warn};
+
+/// RabbitMQ queue that receives the task events
+const TASK_EVENTS_QUEUE: &str = "TODO.TASK.EVENTS";
+
+/// Lifetime requested on create and renew; Graph allows at most 4230 minutes for tasks
+const SUBSCRIPTION_LIFETIME_MINUTES: i64 = 4200;
+
+/// Largest gap between the completion and the last modification of a task for an update to
+/// be reported as its completion
+const COMPLETION_WINDOW_SECONDS: i64 = 60;
+
+/// Subscriptions expiring within this window are renewed by the background task
+const RENEWAL_WINDOW_HOURS: i64 = 24;
+
+pub async fn create_subscription(
+    request: CreateSubscriptionRequest,
+) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Creating subscription on tasks of list: {}",
+        request.list_id
+    );
+
+    if request.list_id.trim().is_empty() {
+        warn!("Logic layer: Invalid list_id provided (empty or whitespace)");
+        return Ok(BusinessResponse::error("List ID cannot be empty"));
+    }
+
+    if TodoProviderKind::of_list_id(&request.list_id) != TodoProviderKind::Microsoft {
+        warn!("Logic layer: Subscription requested on a list not stored in Microsoft To Do");
+        return Ok(BusinessResponse::error(
+            "Change notifications are only available for Microsoft lists",
+        ));
+    }
+
+    let requested_change_types = request.change_types.unwrap_or_else(|| {
+        vec![
+            SubscriptionChangeType::Created,
+            SubscriptionChangeType::Updated,
+            SubscriptionChangeType::Deleted,
+        ]
+    });
+
+    let mut change_types = Vec::new();
+    for change_type in requested_change_types {
+        if !change_types.contains(&change_type) {
+            change_types.push(change_type);
+        }
+    }
+
+    if change_types.is_empty() {
+        warn!("Logic layer: No change types provided");
+        return Ok(BusinessResponse::error(
+            "At least one change type is required",
+        ));
+    }
+
+    let (Some(notification_url), Some(client_state)) = (
+        ENV_CONFIG.notification_url.as_deref(),
+        ENV_CONFIG.subscription_client_state.as_deref(),
+    ) else {
+        warn!("Logic layer: NOTIFICATION_URL or SUBSCRIPTION_CLIENT_STATE not set");
+        return Ok(BusinessResponse::error(
+            "Change notifications are not configured",
+        ));
+    };
+
+    let access_token = match get_access_token().await {
+        Ok(token) => token,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    let graph_client = SubscriptionsMicrosoftGraphApi::new();
+    match graph_client
+        .create_subscription(
+            &request.list_id,
+            &change_types,
+            notification_url,
+            client_state,
+            Utc::now() + Duration::minutes(SUBSCRIPTION_LIFETIME_MINUTES),
+            &access_token,
+        )
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully created subscription");
+            } else {
+                warn!(
+                    "Logic layer: Failed to create subscription in Microsoft Graph: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!(
+                "Logic layer: Error creating subscription in Microsoft Graph: {}",
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Subscriptions notifying this service, i.e. pointing at the configured notification URL
+pub async fn get_subscriptions(
+) -> Result<BusinessResponse<Vec<Subscription>>, Box<dyn std::error::Error>> {
+    info!("Logic layer: Getting all subscriptions");
+
+    let access_token = match get_access_token().await {
+        Ok(token) => token,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    let graph_client = SubscriptionsMicrosoftGraphApi::new();
+    match graph_client.get_subscriptions(&access_token).await {
+        Ok(mut response) => {
+            if let Some(subscriptions) = response.data.as_mut() {
+                subscriptions.retain(|subscription| {
+                    ENV_CONFIG.notification_url.as_deref()
+                        == Some(subscription.notification_url.as_str())
+                });
+            }
+
+            if response.success {
+                info!(
+                    "Logic layer: Successfully retrieved {} subscriptions",
+                    response.data.as_ref().map(|s| s.len()).unwrap_or(0)
+                );
+            } else {
+                warn!(
+                    "Logic layer: Failed to retrieve subscriptions from Microsoft Graph: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!(
+                "Logic layer: Error getting subscriptions from Microsoft Graph: {}",
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+pub async fn renew_subscription(
+    subscription_id: &str,
+) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Renewing subscription with ID: {}",
+        subscription_id
+    );
+
+    if subscription_id.trim().is_empty() {
+        warn!("Logic layer: Invalid subscription_id provided (empty or whitespace)");
+        return Ok(BusinessResponse::error("Subscription ID cannot be empty"));
+    }
+
+    let access_token = match get_access_token().await {
+        Ok(token) => token,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    renew(subscription_id, &access_token).await
+}
+
+pub async fn delete_subscription(
+    subscription_id: &str,
+) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Deleting subscription with ID: {}",
+        subscription_id
+    );
+
+    if subscription_id.trim().is_empty() {
+        warn!("Logic layer: Invalid subscription_id provided (empty or whitespace)");
+        return Ok(BusinessResponse::error("Subscription ID cannot be empty"));
+    }
+
+    let access_token = match get_access_token().await {
+        Ok(token) => token,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    let graph_client = SubscriptionsMicrosoftGraphApi::new();
+    match graph_client
+        .delete_subscription(subscription_id, &access_token)
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully deleted subscription");
+            } else {
+                warn!(
+                    "Logic layer: Failed to delete subscription from Microsoft Graph: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!(
+                "Logic layer: Error deleting subscription from Microsoft Graph: {}",
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Renews the subscriptions of this service before they expire
+pub fn spawn_subscription_renewal() {
+    if ENV_CONFIG.notification_url.is_none() {
+        info!("Logic layer: NOTIFICATION_URL not set, subscription renewal disabled");
+        return;
+    }
+
+    let renewal_interval =
+        std::time::Duration::from_secs(ENV_CONFIG.subscription_renewal_interval_seconds.max(1));
+
+    actix_web::rt::spawn(async move {
+        let mut interval = tokio::time::interval(renewal_interval);
+        loop {
+            interval.tick().await;
+            renew_expiring_subscriptions().await;
+        }
+    });
+}
+
+async fn renew_expiring_subscriptions() {
+    let subscriptions = match get_subscriptions().await {
+        Ok(response) => match response.data {
+            Some(subscriptions) => subscriptions,
+            None => {
+                warn!(
+                    "Logic layer: Failed to list subscriptions to renew: {:?}",
+                    response.errors
+                );
+                return;
+            }
+        },
+        Err(e) => {
+            error!("Logic layer: Error listing subscriptions to renew: {}", e);
+            return;
+        }
+    };
+
+    let renew_before = Utc::now() + Duration::hours(RENEWAL_WINDOW_HOURS);
+    let expiring: Vec<_> = subscriptions
+        .into_iter()
+        .filter(|subscription| subscription.expiration_date_time <= renew_before)
+        .collect();
+
+    if expiring.is_empty() {
+        debug!("Logic layer: No subscription to renew");
+        return;
+    }
+
+    let Ok(access_token) = get_access_token::<()>().await else {
+        return;
+    };
+
+    for subscription in expiring {
+        match renew(&subscription.id, &access_token).await {
+            Ok(response) if response.success => {}
+            Ok(response) => warn!(
+                "Logic layer: Failed to renew subscription {}: {:?}",
+                subscription.id, response.errors
+            ),
+            Err(e) => error!(
+                "Logic layer: Error renewing subscription {}: {}",
+                subscription.id, e
+            ),
+        }
+    }
+}
+
+/// Accepts the notifications carrying the configured client state and processes them in the
+/// background, so that Graph gets its response within the few seconds it waits. Returns how
+/// many were accepted.
+pub fn handle_notifications(collection: ChangeNotificationCollection) -> usize {
+    let (accepted, rejected) = partition_by_client_state(
+        collection.value,
+        ENV_CONFIG.subscription_client_state.as_deref(),
+    );
+
+    for notification in &rejected {
+        warn!(
+            "Logic layer: Discarding notification of subscription {} with unexpected client state",
+            notification.subscription_id
+        );
+    }
+
+    let accepted_count = accepted.len();
+    if accepted_count > 0 {
+        actix_web::rt::spawn(process_notifications(accepted));
+    }
+
+    accepted_count
+}
+
+/// Splits notifications into those carrying `client_state` and the rest; every notification
+/// is rejected when no client state is configured
+fn partition_by_client_state(
+    notifications: Vec<ChangeNotification>,
+    client_state: Option<&str>,
+) -> (Vec<ChangeNotification>, Vec<ChangeNotification>) {
+    notifications.into_iter().partition(|notification| {
+        client_state.is_some() && notification.client_state.as_deref() == client_state
+    })
+}
+
+async fn process_notifications(notifications: Vec<ChangeNotification>) {
+    let Ok(access_token) = get_access_token::<()>().await else {
+        return;
+    };
+
+    let mut events = Vec::new();
+    for notification in notifications {
+        if let Some(lifecycle_event) = notification.lifecycle_event.as_deref() {
+            handle_lifecycle_event(
+                &notification.subscription_id,
+                lifecycle_event,
+                &access_token,
+            )
+            .await;
+            continue;
+        }
+
+        match to_task_event(&notification, &access_token).await {
+            Ok(Some(event)) => events.push(event),
+            Ok(None) => {}
+            Err(e) => warn!(
+                "Logic layer: Ignoring notification of subscription {}: {}",
+                notification.subscription_id, e
+            ),
+        }
+    }
+
+    publish_task_events(&events).await;
+}
+
+async fn handle_lifecycle_event(subscription_id: &str, lifecycle_event: &str, access_token: &str) {
+    match lifecycle_event {
+        // Renewing a subscription also reauthorizes it
+        "reauthorizationRequired" => match renew(subscription_id, access_token).await {
+            Ok(response) if response.success => {}
+            Ok(response) => warn!(
+                "Logic layer: Failed to reauthorize subscription {}: {:?}",
+                subscription_id, response.errors
+            ),
+            Err(e) => error!(
+                "Logic layer: Error reauthorizing subscription {}: {}",
+                subscription_id, e
+            ),
+        },
+        // Changes that were not notified are picked up by the cache delta sync
+        "missed" => warn!(
+            "Logic layer: Microsoft Graph missed notifications of subscription {}",
+            subscription_id
+        ),
+        _ => warn!(
+            "Logic layer: Lifecycle event {} on subscription {}",
+            lifecycle_event, subscription_id
+        ),
+    }
+}
+
+/// Normalizes a change notification. Created and updated tasks are fetched so that the
+/// event carries the current task; `None` when the task no longer exists.
+async fn to_task_event(
+    notification: &ChangeNotification,
+    access_token: &str,
+) -> Result<Option<TaskEvent>, String> {
+    let resource = notification.resource.as_deref().ok_or("Missing resource")?;
+    let list_id =
+        list_id_of_resource(resource).ok_or_else(|| format!("No list in resource {}", resource))?;
+    let task_id = notification
+        .resource_data
+        .as_ref()
+        .and_then(|resource_data| resource_data.id.as_deref())
+        .ok_or("Missing task id")?;
+
+    let change_type = notification.change_type.as_deref().unwrap_or_default();
+    if change_type == "deleted" {
+        task_cache_logic::remove_task(&list_id, task_id).await;
+        return Ok(Some(TaskEvent::new(
+            task_event_type(change_type, None)?,
+            &list_id,
+            task_id,
+            &notification.subscription_id,
+            None,
+        )));
+    }
+
+    // Unknown change types are rejected before calling Graph
+    task_event_type(change_type, None)?;
+
+    let graph_client = TasksMicrosoftGraphApi::new();
+    let response = graph_client
+        .get_task(&list_id, task_id, false, access_token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let Some(task) = response.data else {
+        info!(
+            "Logic layer: Task {} no longer available: {:?}",
+            task_id, response.errors
+        );
+        return Ok(None);
+    };
+
+    let event_type = task_event_type(change_type, Some(&task))?;
+
+    task_cache_logic::store_task(&list_id, &task).await;
+
+    Ok(Some(TaskEvent::new(
+        event_type,
+        &list_id,
+        task_id,
+        &notification.subscription_id,
+        Some(task),
+    )))
+}
+
+/// Event of a Graph change notification. An update is a completion when the task was
+/// completed by that change, i.e. it was last modified when it was completed; later edits
+/// of a completed task are updates. `task` is the task as fetched after the change.
+fn task_event_type(change_type: &str, task: Option<&Task>) -> Result<TaskEventType, String> {
+    match change_type {
+        "created" => Ok(TaskEventType::Created),
+        "deleted" => Ok(TaskEventType::Deleted),
+        "updated" if task.is_some_and(was_completed_by_last_change) => Ok(TaskEventType::Completed),
+        "updated" => Ok(TaskEventType::Updated),
+        _ => Err(format!("Unknown change type {}", change_type)),
+    }
+}
+
+fn was_completed_by_last_change(task: &Task) -> bool {
+    task.status == TaskStatus::Completed
+        && task.completed_date_time.is_some_and(|completed_date_time| {
+            (task.last_modified_date_time - completed_date_time).abs()
+                <= Duration::seconds(COMPLETION_WINDOW_SECONDS)
+        })
+}
+
+/// Publishes the events to RabbitMQ over a single connection. Failures are only logged.
+async fn publish_task_events(events: &[TaskEvent]) {
+    if events.is_empty() {
+        return;
+    }
+
+    let Some(rabbitmq_url) = ENV_CONFIG.rabbitmq_url.as_deref() else {
+        debug!(
+            "Logic layer: RABBITMQ_URL not set, dropping {} task events",
+            events.len()
+        );
+        return;
+    };
+
+    let connection = match message_broker::connect(rabbitmq_url).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            error!("Logic layer: Failed to publish task events: {}", e);
+            return;
+        }
+    };
+
+    for event in events {
+        let result = match serde_json::to_string(event) {
+            Ok(payload) => {
+                message_broker::queue_publish(&connection, TASK_EVENTS_QUEUE, &payload).await
+            }
+            Err(e) => Err(e.into()),
+        };
+
+        match result {
+            Ok(_) => info!(
+                "Logic layer: Event {} of task {} published to {}",
+                event.event_type.as_str(),
+                event.task_id,
+                TASK_EVENTS_QUEUE
+            ),
+            Err(e) => error!(
+                "Logic layer: Failed to publish event {} of task {}: {}",
+                event.event_type.as_str(),
+                event.task_id,
+                e
+            ),
+        }
+    }
+
+    if let Err(e) = connection.close(0, "").await {
+        debug!("Logic layer: Failed to close RabbitMQ connection: {}", e);
+    }
+}
+
+async fn renew(
+    subscription_id: &str,
+    access_token: &str,
+) -> Result<BusinessResponse<Subscription>, Box<dyn std::error::Error>> {
+    let graph_client = SubscriptionsMicrosoftGraphApi::new();
+    match graph_client
+        .renew_subscription(
+            subscription_id,
+            Utc::now() + Duration::minutes(SUBSCRIPTION_LIFETIME_MINUTES),
+            access_token,
+        )
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!(
+                    "Logic layer: Successfully renewed subscription {}",
+                    subscription_id
+                );
+            } else {
+                warn!(
+                    "Logic layer: Failed to renew subscription in Microsoft Graph: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!(
+                "Logic layer: Error renewing subscription in Microsoft Graph: {}",
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+async fn get_access_token<T>() -> Result<String, BusinessResponse<T>> {
+    let oauth_client = OAuthApi::new();
+    oauth_client.generate_access_token().await.map_err(|e| {
+        error!(
+            "Logic layer: Failed to get access token from OAuth manager: {}",
+            e
+        );
+        BusinessResponse::error("Failed to authenticate with Microsoft Graph")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo_mod::task::TaskImportance;
+
+    fn notification(subscription_id: &str, client_state: Option<&str>) -> ChangeNotification {
+        ChangeNotification {
+            subscription_id: subscription_id.to_string(),
+            client_state: client_state.map(str::to_string),
+            change_type: Some("updated".to_string()),
+            resource: None,
+            resource_data: None,
+            lifecycle_event: None,
+        }
+    }
+
+    fn subscription_ids(notifications: &[ChangeNotification]) -> Vec<&str> {
+        notifications
+            .iter()
+            .map(|notification| notification.subscription_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_only_notifications_with_the_configured_client_state() {
+        let notifications = vec![
+            notification("matching", Some("secret")),
+            notification("different", Some("other")),
+            notification("different-case", Some("SECRET")),
+            notification("missing", None),
+        ];
+
+        let (accepted, rejected) = partition_by_client_state(notifications, Some("secret"));
+
+        assert_eq!(subscription_ids(&accepted), vec!["matching"]);
+        assert_eq!(
+            subscription_ids(&rejected),
+            vec!["different", "different-case", "missing"]
+        );
+    }
+
+    #[test]
+    fn rejects_every_notification_without_a_configured_client_state() {
+        let notifications = vec![
+            notification("with-state", Some("secret")),
+            notification("without-state", None),
+        ];
+
+        let (accepted, rejected) = partition_by_client_state(notifications, None);
+
+        assert!(accepted.is_empty());
+        assert_eq!(
+            subscription_ids(&rejected),
+            vec!["with-state", "without-state"]
+        );
+    }
+
+    fn task(status: TaskStatus, minutes_modified_after_completion: Option<i64>) -> Task {
+        let completed_date_time = Utc::now() - Duration::hours(1);
+
+        Task {
+            id: "task-1".to_string(),
+            title: "Buy milk".to_string(),
+            body: None,
+            status,
+            importance: TaskImportance::Normal,
+            is_reminder_on: false,
+            reminder_date_time: None,
+            due_date_time: None,
+            recurrence: None,
+            completed_date_time: minutes_modified_after_completion.map(|_| completed_date_time),
+            created_date_time: completed_date_time - Duration::days(1),
+            last_modified_date_time: completed_date_time
+                + Duration::minutes(minutes_modified_after_completion.unwrap_or(0)),
+            checklist_items: None,
+        }
+    }
+
+    #[test]
+    fn created_and_deleted_changes_keep_their_type() {
+        let completed = task(TaskStatus::Completed, Some(0));
+
+        assert_eq!(
+            task_event_type("created", Some(&completed)),
+            Ok(TaskEventType::Created)
+        );
+        assert_eq!(task_event_type("deleted", None), Ok(TaskEventType::Deleted));
+    }
+
+    #[test]
+    fn an_update_that_completed_the_task_is_a_completion() {
+        let completed = task(TaskStatus::Completed, Some(0));
+
+        assert_eq!(
+            task_event_type("updated", Some(&completed)),
+            Ok(TaskEventType::Completed)
+        );
+    }
+
+    #[test]
+    fn other_updates_are_updates() {
+        let in_progress = task(TaskStatus::InProgress, None);
+        assert_eq!(
+            task_event_type("updated", Some(&in_progress)),
+            Ok(TaskEventType::Updated)
+        );
+
+        // Edited well after it was completed
+        let edited_after_completion = task(TaskStatus::Completed, Some(30));
+        assert_eq!(
+            task_event_type("updated", Some(&edited_after_completion)),
+            Ok(TaskEventType::Updated)
+        );
+
+        let completed_without_date = task(TaskStatus::Completed, None);
+        assert_eq!(
+            task_event_type("updated", Some(&completed_without_date)),
+            Ok(TaskEventType::Updated)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_change_types() {
+        assert!(task_event_type("moved", None).is_err());
+    }
+}
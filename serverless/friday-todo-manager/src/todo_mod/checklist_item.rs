@@ -0,0 +1,155 @@
+use crate::google_tasks_mod::google_date_time::parse_google_timestamp;
+use crate::google_tasks_mod::google_task_response::GoogleTaskResponse;
+use crate::microsoft_graph_mod::checklist_item_response::ChecklistItemResponse;
+use crate::microsoft_graph_mod::graph_date_time::parse_graph_timestamp;
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "ChecklistItem")]
+pub struct ChecklistItem {
+    #[schema(example = "51d8a471-2e9d-4f53-9937-c33a8742d28f")]
+    pub id: String,
+
+    #[schema(example = "Milk")]
+    pub display_name: String,
+
+    #[schema(example = false)]
+    pub is_checked: bool,
+
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    #[schema(example = "1699876543000")]
+    pub created_date_time: DateTime<Utc>,
+
+    pub checked_date_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "CreateChecklistItemRequest")]
+pub struct CreateChecklistItemRequest {
+    #[schema(example = "Milk", min_length = 1, max_length = 255)]
+    pub display_name: String,
+
+    #[schema(example = false)]
+    pub is_checked: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "UpdateChecklistItemRequest")]
+pub struct UpdateChecklistItemRequest {
+    #[schema(example = "Oat milk", min_length = 1, max_length = 255)]
+    pub display_name: Option<String>,
+
+    #[schema(example = true)]
+    pub is_checked: Option<bool>,
+}
+
+impl TryFrom<ChecklistItemResponse> for ChecklistItem {
+    type Error = String;
+
+    fn try_from(response: ChecklistItemResponse) -> Result<Self, Self::Error> {
+        let created_date_time = response
+            .created_date_time
+            .as_deref()
+            .ok_or_else(|| format!("Checklist item {} has no creation date", response.id))
+            .and_then(parse_graph_timestamp)?;
+
+        let checked_date_time = response
+            .checked_date_time
+            .as_deref()
+            .map(parse_graph_timestamp)
+            .transpose()?;
+
+        Ok(ChecklistItem {
+            id: response.id,
+            display_name: response.display_name,
+            is_checked: response.is_checked,
+            created_date_time,
+            checked_date_time,
+        })
+    }
+}
+
+/// Google Tasks subtasks are the checklist items of their parent
+impl TryFrom<GoogleTaskResponse> for ChecklistItem {
+    type Error = String;
+
+    fn try_from(response: GoogleTaskResponse) -> Result<Self, Self::Error> {
+        let created_date_time = response
+            .updated
+            .as_deref()
+            .ok_or_else(|| format!("Checklist item {} has no modification date", response.id))
+            .and_then(parse_google_timestamp)?;
+
+        let checked_date_time = response
+            .completed
+            .as_deref()
+            .map(parse_google_timestamp)
+            .transpose()?;
+
+        Ok(ChecklistItem {
+            id: response.id,
+            display_name: response.title.unwrap_or_default(),
+            is_checked: response.status == "completed",
+            created_date_time,
+            checked_date_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn google_subtask(status: &str, completed: Option<&str>) -> GoogleTaskResponse {
+        GoogleTaskResponse {
+            id: "c3VidGFzay0x".to_string(),
+            title: Some("Milk".to_string()),
+            notes: None,
+            status: status.to_string(),
+            due: None,
+            completed: completed.map(str::to_string),
+            updated: Some("2024-05-08T14:30:00.000Z".to_string()),
+            parent: Some("dGFzay0x".to_string()),
+        }
+    }
+
+    #[test]
+    fn maps_a_google_subtask() {
+        let item = ChecklistItem::try_from(google_subtask("needsAction", None)).unwrap();
+
+        assert_eq!(item.id, "c3VidGFzay0x");
+        assert_eq!(item.display_name, "Milk");
+        assert!(!item.is_checked);
+        assert_eq!(
+            item.created_date_time,
+            "2024-05-08T14:30:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(item.checked_date_time, None);
+    }
+
+    #[test]
+    fn maps_a_completed_google_subtask() {
+        let item = ChecklistItem::try_from(google_subtask(
+            "completed",
+            Some("2024-05-09T08:00:00.000Z"),
+        ))
+        .unwrap();
+
+        assert!(item.is_checked);
+        assert_eq!(
+            item.checked_date_time,
+            Some("2024-05-09T08:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_google_subtasks() {
+        let mut without_update = google_subtask("needsAction", None);
+        without_update.updated = None;
+        assert!(ChecklistItem::try_from(without_update).is_err());
+
+        assert!(ChecklistItem::try_from(google_subtask("completed", Some("today"))).is_err());
+    }
+}
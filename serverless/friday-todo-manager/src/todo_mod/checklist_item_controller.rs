@@ -0,0 +1,198 @@
+use crate::business_response::BusinessResponse;
+use crate::todo_mod::checklist_item::{CreateChecklistItemRequest, UpdateChecklistItemRequest};
+use crate::todo_mod::checklist_item_logic;
+use actix_web::{delete, get, patch, post, web, HttpResponse, Responder};
+use serde::Serialize;
+use tracing::{error, info};
+
+#[utoipa::path(
+    get,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist",
+    responses(
+        (status = 200, description = "Checklist items (subtasks) of the task retrieved successfully", body = BusinessResponse<Vec<ChecklistItem>>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Task not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier"),
+        ("task_id" = String, Path, description = "Task unique identifier")
+    ),
+    tag = "Checklist Items"
+)]
+#[get("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist")]
+pub async fn get_checklist_items(path: web::Path<(String, String)>) -> impl Responder {
+    let (list_id, task_id) = path.into_inner();
+    info!(
+        "Controller layer: GET /api/friday-todo-manager/lists/{}/tasks/{}/checklist",
+        list_id, task_id
+    );
+
+    to_http_response(
+        checklist_item_logic::get_checklist_items(&list_id, &task_id).await,
+        "retrieving checklist items",
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}",
+    responses(
+        (status = 200, description = "Checklist item retrieved successfully", body = BusinessResponse<ChecklistItem>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Checklist item not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier"),
+        ("task_id" = String, Path, description = "Task unique identifier"),
+        ("checklist_item_id" = String, Path, description = "Checklist item unique identifier")
+    ),
+    tag = "Checklist Items"
+)]
+#[get("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}")]
+pub async fn get_checklist_item(path: web::Path<(String, String, String)>) -> impl Responder {
+    let (list_id, task_id, checklist_item_id) = path.into_inner();
+    info!(
+        "Controller layer: GET /api/friday-todo-manager/lists/{}/tasks/{}/checklist/{}",
+        list_id, task_id, checklist_item_id
+    );
+
+    to_http_response(
+        checklist_item_logic::get_checklist_item(&list_id, &task_id, &checklist_item_id).await,
+        "retrieving the checklist item",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist",
+    request_body = CreateChecklistItemRequest,
+    responses(
+        (status = 200, description = "Checklist item created successfully", body = BusinessResponse<ChecklistItem>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Task not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier"),
+        ("task_id" = String, Path, description = "Task unique identifier")
+    ),
+    tag = "Checklist Items"
+)]
+#[post("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist")]
+pub async fn create_checklist_item(
+    path: web::Path<(String, String)>,
+    request: web::Json<CreateChecklistItemRequest>,
+) -> impl Responder {
+    let (list_id, task_id) = path.into_inner();
+    info!(
+        "Controller layer: POST /api/friday-todo-manager/lists/{}/tasks/{}/checklist",
+        list_id, task_id
+    );
+
+    to_http_response(
+        checklist_item_logic::create_checklist_item(&list_id, &task_id, request.into_inner()).await,
+        "creating the checklist item",
+    )
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}",
+    request_body = UpdateChecklistItemRequest,
+    responses(
+        (status = 200, description = "Checklist item updated successfully", body = BusinessResponse<ChecklistItem>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Checklist item not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier"),
+        ("task_id" = String, Path, description = "Task unique identifier"),
+        ("checklist_item_id" = String, Path, description = "Checklist item unique identifier")
+    ),
+    tag = "Checklist Items"
+)]
+#[patch("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}")]
+pub async fn update_checklist_item(
+    path: web::Path<(String, String, String)>,
+    request: web::Json<UpdateChecklistItemRequest>,
+) -> impl Responder {
+    let (list_id, task_id, checklist_item_id) = path.into_inner();
+    info!(
+        "Controller layer: PATCH /api/friday-todo-manager/lists/{}/tasks/{}/checklist/{}",
+        list_id, task_id, checklist_item_id
+    );
+
+    to_http_response(
+        checklist_item_logic::update_checklist_item(
+            &list_id,
+            &task_id,
+            &checklist_item_id,
+            request.into_inner(),
+        )
+        .await,
+        "updating the checklist item",
+    )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}",
+    responses(
+        (status = 200, description = "Checklist item deleted successfully", body = BusinessResponse<String>),
+        (status = 400, description = "Bad request", body = BusinessResponse<String>),
+        (status = 404, description = "Checklist item not found", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier"),
+        ("task_id" = String, Path, description = "Task unique identifier"),
+        ("checklist_item_id" = String, Path, description = "Checklist item unique identifier")
+    ),
+    tag = "Checklist Items"
+)]
+#[delete("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}/checklist/{checklist_item_id}")]
+pub async fn delete_checklist_item(path: web::Path<(String, String, String)>) -> impl Responder {
+    let (list_id, task_id, checklist_item_id) = path.into_inner();
+    info!(
+        "Controller layer: DELETE /api/friday-todo-manager/lists/{}/tasks/{}/checklist/{}",
+        list_id, task_id, checklist_item_id
+    );
+
+    to_http_response(
+        checklist_item_logic::delete_checklist_item(&list_id, &task_id, &checklist_item_id).await,
+        "deleting the checklist item",
+    )
+}
+
+fn to_http_response<T: Serialize>(
+    result: Result<BusinessResponse<T>, Box<dyn std::error::Error>>,
+    action: &str,
+) -> HttpResponse {
+    match result {
+        Ok(response) => {
+            if response.success {
+                info!("Controller layer: Successfully finished {}", action);
+                HttpResponse::Ok().json(response)
+            } else if response.errors.iter().any(|e| e.contains("not found")) {
+                error!("Controller layer: Not found: {:?}", response.errors);
+                HttpResponse::NotFound().json(response)
+            } else {
+                error!(
+                    "Controller layer: Business logic error: {:?}",
+                    response.errors
+                );
+                HttpResponse::BadRequest().json(response)
+            }
+        }
+        Err(e) => {
+            error!("Controller layer: System error {}: {}", action, e);
+            HttpResponse::InternalServerError().json(BusinessResponse::<String>::error(&format!(
+                "An internal error occurred while {}",
+                action
+            )))
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::business_response::BusinessResponse;
+use crate::provider_mod::todo_provider_logic;
+use crate::todo_mod::checklist_item::{
+    ChecklistItem, CreateChecklistItemRequest, UpdateChecklistItemRequest,
+};
+use tracing::{error, info, warn};
+
+pub async fn get_checklist_items(
+    list_id: &str,
+    task_id: &str,
+) -> Result<BusinessResponse<Vec<ChecklistItem>>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Getting checklist items of task: {} from list: {}",
+        task_id, list_id
+    );
+
+    if let Some(error_response) = validate_ids(list_id, task_id, None) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    match provider.get_checklist_items(list_id, task_id).await {
+        Ok(response) => {
+            if response.success {
+                info!(
+                    "Logic layer: Successfully retrieved {} checklist items",
+                    response.data.as_ref().map(|items| items.len()).unwrap_or(0)
+                );
+            } else {
+                warn!(
+                    "Logic layer: Failed to retrieve checklist items: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Logic layer: Error getting checklist items: {}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn get_checklist_item(
+    list_id: &str,
+    task_id: &str,
+    checklist_item_id: &str,
+) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Getting checklist item: {} of task: {}",
+        checklist_item_id, task_id
+    );
+
+    if let Some(error_response) = validate_ids(list_id, task_id, Some(checklist_item_id)) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    match provider
+        .get_checklist_item(list_id, task_id, checklist_item_id)
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully retrieved checklist item");
+            } else {
+                warn!(
+                    "Logic layer: Failed to retrieve checklist item: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Logic layer: Error getting checklist item: {}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn create_checklist_item(
+    list_id: &str,
+    task_id: &str,
+    request: CreateChecklistItemRequest,
+) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Creating checklist item: {} in task: {}",
+        request.display_name, task_id
+    );
+
+    if let Some(error_response) = validate_ids(list_id, task_id, None) {
+        return Ok(error_response);
+    }
+
+    if let Some(error_response) = validate_display_name(Some(&request.display_name)) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    match provider
+        .create_checklist_item(list_id, task_id, &request)
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully created checklist item");
+            } else {
+                warn!(
+                    "Logic layer: Failed to create checklist item: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Logic layer: Error creating checklist item: {}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_checklist_item(
+    list_id: &str,
+    task_id: &str,
+    checklist_item_id: &str,
+    request: UpdateChecklistItemRequest,
+) -> Result<BusinessResponse<ChecklistItem>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Updating checklist item: {} of task: {}",
+        checklist_item_id, task_id
+    );
+
+    if let Some(error_response) = validate_ids(list_id, task_id, Some(checklist_item_id)) {
+        return Ok(error_response);
+    }
+
+    if let Some(error_response) = validate_display_name(request.display_name.as_deref()) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    match provider
+        .update_checklist_item(list_id, task_id, checklist_item_id, &request)
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully updated checklist item");
+            } else {
+                warn!(
+                    "Logic layer: Failed to update checklist item: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Logic layer: Error updating checklist item: {}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete_checklist_item(
+    list_id: &str,
+    task_id: &str,
+    checklist_item_id: &str,
+) -> Result<BusinessResponse<String>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Deleting checklist item: {} of task: {}",
+        checklist_item_id, task_id
+    );
+
+    if let Some(error_response) = validate_ids(list_id, task_id, Some(checklist_item_id)) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
+    };
+
+    match provider
+        .delete_checklist_item(list_id, task_id, checklist_item_id)
+        .await
+    {
+        Ok(response) => {
+            if response.success {
+                info!("Logic layer: Successfully deleted checklist item");
+            } else {
+                warn!(
+                    "Logic layer: Failed to delete checklist item: {:?}",
+                    response.errors
+                );
+            }
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Logic layer: Error deleting checklist item: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Validates the display name of a new or renamed checklist item
+pub fn validate_display_name<T>(display_name: Option<&str>) -> Option<BusinessResponse<T>> {
+    let display_name = display_name?;
+
+    if display_name.trim().is_empty() {
+        warn!("Logic layer: Invalid checklist item name provided (empty or whitespace)");
+        return Some(BusinessResponse::error(
+            "Checklist item name cannot be empty",
+        ));
+    }
+
+    if display_name.len() > 255 {
+        warn!(
+            "Logic layer: Checklist item name too long: {} characters",
+            display_name.len()
+        );
+        return Some(BusinessResponse::error(
+            "Checklist item name cannot exceed 255 characters",
+        ));
+    }
+
+    None
+}
+
+fn validate_ids<T>(
+    list_id: &str,
+    task_id: &str,
+    checklist_item_id: Option<&str>,
+) -> Option<BusinessResponse<T>> {
+    if list_id.trim().is_empty() {
+        warn!("Logic layer: Invalid list_id provided (empty or whitespace)");
+        return Some(BusinessResponse::error("List ID cannot be empty"));
+    }
+
+    if task_id.trim().is_empty() {
+        warn!("Logic layer: Invalid task_id provided (empty or whitespace)");
+        return Some(BusinessResponse::error("Task ID cannot be empty"));
+    }
+
+    if checklist_item_id.is_some_and(|id| id.trim().is_empty()) {
+        warn!("Logic layer: Invalid checklist_item_id provided (empty or whitespace)");
+        return Some(BusinessResponse::error("Checklist item ID cannot be empty"));
+    }
+
+    None
+}
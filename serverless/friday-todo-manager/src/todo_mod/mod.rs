@@ -1,6 +1,12 @@
+pub mod checklist_item;
+pub mod checklist_item_controller;
+pub mod checklist_item_logic;
+pub mod recurrence;
 pub mod task;
 pub mod task_controller;
+pub mod task_cursor;
+pub mod task_filter;
 pub mod task_logic;
 pub mod todo_list;
 pub mod todo_list_controller;
-pub mod todo_list_logic;
\ No newline at end of file
+pub mod todo_list_logic;
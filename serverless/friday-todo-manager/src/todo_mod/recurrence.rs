@@ -0,0 +1,335 @@
+use crate::microsoft_graph_mod::recurrence_response::{
+    PatternedRecurrenceResponse, RecurrencePatternResponse, RecurrenceRangeResponse,
+};
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How often a task repeats (`pattern`) and for how long (`range`), following Microsoft
+/// Graph's `patternedRecurrence`
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "TaskRecurrence")]
+pub struct TaskRecurrence {
+    pub pattern: RecurrencePattern,
+    pub range: RecurrenceRange,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "RecurrencePattern")]
+pub struct RecurrencePattern {
+    #[serde(rename = "type")]
+    pub pattern_type: RecurrencePatternType,
+
+    /// Number of days, weeks, months or years between occurrences
+    #[schema(example = 1, minimum = 1)]
+    pub interval: u32,
+
+    /// Days of the week of weekly and relative monthly/yearly patterns
+    pub days_of_week: Option<Vec<DayOfWeek>>,
+
+    /// First day of the week of weekly patterns
+    pub first_day_of_week: Option<DayOfWeek>,
+
+    /// Day of the month of absolute monthly/yearly patterns
+    #[schema(example = 15, minimum = 1, maximum = 31)]
+    pub day_of_month: Option<u32>,
+
+    /// Month of yearly patterns
+    #[schema(example = 6, minimum = 1, maximum = 12)]
+    pub month: Option<u32>,
+
+    /// Week of the month of relative monthly/yearly patterns
+    pub index: Option<WeekIndex>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[schema(title = "RecurrenceRange")]
+pub struct RecurrenceRange {
+    #[serde(rename = "type")]
+    pub range_type: RecurrenceRangeType,
+
+    #[schema(value_type = String, example = "2024-01-01")]
+    pub start_date: NaiveDate,
+
+    /// Last date of `endDate` ranges
+    #[schema(value_type = Option<String>, example = "2024-12-31")]
+    pub end_date: Option<NaiveDate>,
+
+    /// Number of occurrences of `numbered` ranges
+    #[schema(example = 10, minimum = 1)]
+    pub number_of_occurrences: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "RecurrencePatternType")]
+pub enum RecurrencePatternType {
+    #[serde(rename = "daily")]
+    Daily,
+    #[serde(rename = "weekly")]
+    Weekly,
+    #[serde(rename = "absoluteMonthly")]
+    AbsoluteMonthly,
+    #[serde(rename = "relativeMonthly")]
+    RelativeMonthly,
+    #[serde(rename = "absoluteYearly")]
+    AbsoluteYearly,
+    #[serde(rename = "relativeYearly")]
+    RelativeYearly,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "RecurrenceRangeType")]
+pub enum RecurrenceRangeType {
+    #[serde(rename = "endDate")]
+    EndDate,
+    #[serde(rename = "noEnd")]
+    NoEnd,
+    #[serde(rename = "numbered")]
+    Numbered,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "DayOfWeek")]
+#[serde(rename_all = "lowercase")]
+pub enum DayOfWeek {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "WeekIndex")]
+#[serde(rename_all = "lowercase")]
+pub enum WeekIndex {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Last,
+}
+
+impl RecurrencePatternType {
+    pub fn is_relative(&self) -> bool {
+        matches!(self, Self::RelativeMonthly | Self::RelativeYearly)
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        matches!(self, Self::AbsoluteMonthly | Self::AbsoluteYearly)
+    }
+
+    pub fn is_yearly(&self) -> bool {
+        matches!(self, Self::AbsoluteYearly | Self::RelativeYearly)
+    }
+}
+
+impl TryFrom<PatternedRecurrenceResponse> for TaskRecurrence {
+    type Error = String;
+
+    fn try_from(response: PatternedRecurrenceResponse) -> Result<Self, Self::Error> {
+        Ok(TaskRecurrence {
+            pattern: RecurrencePattern::try_from(response.pattern)?,
+            range: RecurrenceRange::try_from(response.range)?,
+        })
+    }
+}
+
+impl TryFrom<RecurrencePatternResponse> for RecurrencePattern {
+    type Error = String;
+
+    fn try_from(response: RecurrencePatternResponse) -> Result<Self, Self::Error> {
+        let pattern_type: RecurrencePatternType = parse_graph_value(&response.pattern_type)?;
+
+        let days_of_week = response
+            .days_of_week
+            .iter()
+            .map(|day| parse_graph_value(day))
+            .collect::<Result<Vec<DayOfWeek>, String>>()?;
+
+        // Only keep the fields that apply to the pattern type, Graph zeroes the others
+        let first_day_of_week = match (&response.first_day_of_week, pattern_type) {
+            (Some(day), RecurrencePatternType::Weekly) => Some(parse_graph_value(day)?),
+            _ => None,
+        };
+
+        let index = match &response.index {
+            Some(index) if pattern_type.is_relative() => Some(parse_graph_value(index)?),
+            _ => None,
+        };
+
+        Ok(RecurrencePattern {
+            pattern_type,
+            interval: response.interval,
+            days_of_week: Some(days_of_week).filter(|days| !days.is_empty()),
+            first_day_of_week,
+            day_of_month: Some(response.day_of_month).filter(|day| *day > 0),
+            month: Some(response.month).filter(|month| *month > 0),
+            index,
+        })
+    }
+}
+
+impl TryFrom<RecurrenceRangeResponse> for RecurrenceRange {
+    type Error = String;
+
+    fn try_from(response: RecurrenceRangeResponse) -> Result<Self, Self::Error> {
+        let range_type: RecurrenceRangeType = parse_graph_value(&response.range_type)?;
+
+        let start_date = parse_graph_date(&response.start_date)?;
+
+        let end_date = match &response.end_date {
+            Some(end_date) if range_type == RecurrenceRangeType::EndDate => {
+                Some(parse_graph_date(end_date)?)
+            }
+            _ => None,
+        };
+
+        Ok(RecurrenceRange {
+            range_type,
+            start_date,
+            end_date,
+            number_of_occurrences: Some(response.number_of_occurrences)
+                .filter(|_| range_type == RecurrenceRangeType::Numbered),
+        })
+    }
+}
+
+/// Parses a Graph enum string into the enum with the same serde name
+fn parse_graph_value<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| format!("Unsupported recurrence value: {}", value))
+}
+
+fn parse_graph_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid recurrence date: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn from_graph(value: serde_json::Value) -> Result<TaskRecurrence, String> {
+        TaskRecurrence::try_from(
+            serde_json::from_value::<PatternedRecurrenceResponse>(value).unwrap(),
+        )
+    }
+
+    #[test]
+    fn drops_the_fields_graph_zeroes_for_the_pattern_type() {
+        let recurrence = from_graph(json!({
+            "pattern": {
+                "type": "weekly",
+                "interval": 2,
+                "month": 0,
+                "dayOfMonth": 0,
+                "daysOfWeek": ["monday", "thursday"],
+                "firstDayOfWeek": "sunday",
+                "index": "first"
+            },
+            "range": {
+                "type": "noEnd",
+                "startDate": "2024-01-01",
+                "endDate": "0001-01-01",
+                "numberOfOccurrences": 0,
+                "recurrenceTimeZone": "E. South America Standard Time"
+            }
+        }))
+        .unwrap();
+
+        let pattern = &recurrence.pattern;
+        assert_eq!(pattern.pattern_type, RecurrencePatternType::Weekly);
+        assert_eq!(pattern.interval, 2);
+        assert_eq!(
+            pattern.days_of_week,
+            Some(vec![DayOfWeek::Monday, DayOfWeek::Thursday])
+        );
+        assert_eq!(pattern.first_day_of_week, Some(DayOfWeek::Sunday));
+        assert_eq!(pattern.day_of_month, None);
+        assert_eq!(pattern.month, None);
+        assert_eq!(pattern.index, None);
+
+        let range = &recurrence.range;
+        assert_eq!(range.range_type, RecurrenceRangeType::NoEnd);
+        assert_eq!(
+            range.start_date,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+        assert_eq!(range.end_date, None);
+        assert_eq!(range.number_of_occurrences, None);
+    }
+
+    #[test]
+    fn keeps_the_fields_of_relative_yearly_and_numbered_recurrences() {
+        let recurrence = from_graph(json!({
+            "pattern": {
+                "type": "relativeYearly",
+                "interval": 1,
+                "month": 11,
+                "dayOfMonth": 0,
+                "daysOfWeek": ["thursday"],
+                "firstDayOfWeek": "sunday",
+                "index": "fourth"
+            },
+            "range": {
+                "type": "numbered",
+                "startDate": "2024-11-28",
+                "endDate": "0001-01-01",
+                "numberOfOccurrences": 5
+            }
+        }))
+        .unwrap();
+
+        let pattern = &recurrence.pattern;
+        assert_eq!(pattern.month, Some(11));
+        assert_eq!(pattern.index, Some(WeekIndex::Fourth));
+        assert_eq!(pattern.first_day_of_week, None);
+        assert_eq!(recurrence.range.number_of_occurrences, Some(5));
+        assert_eq!(recurrence.range.end_date, None);
+    }
+
+    #[test]
+    fn keeps_the_end_date_of_end_date_ranges() {
+        let recurrence = from_graph(json!({
+            "pattern": { "type": "absoluteMonthly", "interval": 1, "dayOfMonth": 15 },
+            "range": { "type": "endDate", "startDate": "2024-01-15", "endDate": "2024-12-15" }
+        }))
+        .unwrap();
+
+        assert_eq!(recurrence.pattern.day_of_month, Some(15));
+        assert_eq!(recurrence.pattern.days_of_week, None);
+        assert_eq!(
+            recurrence.range.end_date,
+            Some(NaiveDate::from_ymd_opt(2024, 12, 15).unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_values_and_dates() {
+        let recurrence = |pattern_type: &str, day: &str, start_date: &str| {
+            from_graph(json!({
+                "pattern": { "type": pattern_type, "interval": 1, "daysOfWeek": [day] },
+                "range": { "type": "noEnd", "startDate": start_date }
+            }))
+        };
+
+        assert!(recurrence("daily", "monday", "2024-01-01").is_ok());
+        assert_eq!(
+            recurrence("hourly", "monday", "2024-01-01").unwrap_err(),
+            "Unsupported recurrence value: hourly"
+        );
+        assert_eq!(
+            recurrence("daily", "Monday", "2024-01-01").unwrap_err(),
+            "Unsupported recurrence value: Monday"
+        );
+        assert_eq!(
+            recurrence("daily", "monday", "01/01/2024").unwrap_err(),
+            "Invalid recurrence date: 01/01/2024"
+        );
+    }
+}
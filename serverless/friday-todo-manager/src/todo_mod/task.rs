@@ -1,7 +1,13 @@
+use crate::google_tasks_mod::google_date_time::{parse_google_due_date, parse_google_timestamp};
+use crate::google_tasks_mod::google_task_response::GoogleTaskResponse;
+use crate::microsoft_graph_mod::graph_date_time::{parse_graph_date_time, parse_graph_timestamp};
 use crate::microsoft_graph_mod::task_response::TaskResponse;
+use crate::todo_mod::checklist_item::{ChecklistItem, CreateChecklistItemRequest};
+use crate::todo_mod::recurrence::TaskRecurrence;
 use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
 #[schema(title = "Task")]
@@ -26,6 +32,11 @@ pub struct Task {
     pub reminder_date_time: Option<DateTime<Utc>>,
     pub due_date_time: Option<DateTime<Utc>>,
 
+    pub recurrence: Option<TaskRecurrence>,
+
+    /// When the task was completed; absent while it is not
+    pub completed_date_time: Option<DateTime<Utc>>,
+
     #[serde(with = "chrono::serde::ts_milliseconds")]
     #[schema(example = "1699876543000")]
     pub created_date_time: DateTime<Utc>,
@@ -33,9 +44,13 @@ pub struct Task {
     #[serde(with = "chrono::serde::ts_milliseconds")]
     #[schema(example = "1699876543000")]
     pub last_modified_date_time: DateTime<Utc>,
+
+    /// Only present when the checklist was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checklist_items: Option<Vec<ChecklistItem>>,
 }
 
-#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
 #[schema(title = "TaskStatus")]
 pub enum TaskStatus {
     #[serde(rename = "notStarted")]
@@ -50,7 +65,8 @@ pub enum TaskStatus {
     Deferred,
 }
 
-#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+/// Ordered from low to high, as Microsoft Graph orders by importance
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[schema(title = "TaskImportance")]
 pub enum TaskImportance {
     #[serde(rename = "low")]
@@ -61,6 +77,120 @@ pub enum TaskImportance {
     High,
 }
 
+/// Paging options of `GET /lists/{list_id}/tasks`. Without any of them every page is
+/// fetched from Microsoft Graph and the whole list is returned.
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskPageQuery {
+    /// Maximum number of tasks in the page
+    #[param(example = 50, minimum = 1)]
+    pub top: Option<u32>,
+
+    /// Number of tasks to skip
+    #[param(example = 100)]
+    pub skip: Option<u32>,
+
+    /// Opaque cursor returned in the `X-Next-Cursor` header of the previous page. Send the
+    /// same filters and ordering with it; the page size is kept in the cursor. On Microsoft
+    /// To Do lists, paging cannot be combined with `due_before`, `due_after`, `search` or
+    /// ordering by `due_date_time`.
+    pub cursor: Option<String>,
+}
+
+impl TaskPageQuery {
+    pub fn is_paged(&self) -> bool {
+        self.top.is_some() || self.skip.is_some() || self.cursor.is_some()
+    }
+}
+
+/// Filtering, search and ordering options of `GET /lists/{list_id}/tasks`. Status,
+/// importance, reminder and ordering by title, importance or creation/modification dates
+/// are sent to Microsoft Graph; the due date range, ordering by due date and text search
+/// are applied to the returned tasks, so Microsoft To Do lists reject them on paged
+/// requests.
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskFilterQuery {
+    /// Only tasks with this status
+    pub status: Option<TaskStatus>,
+
+    /// Only tasks with this importance
+    pub importance: Option<TaskImportance>,
+
+    /// Only tasks due before this instant. Not supported with `top`, `skip` or `cursor` on
+    /// Microsoft To Do lists.
+    #[param(value_type = Option<String>, example = "2024-12-31T23:59:59Z")]
+    pub due_before: Option<DateTime<Utc>>,
+
+    /// Only tasks due after this instant. Not supported with `top`, `skip` or `cursor` on
+    /// Microsoft To Do lists.
+    #[param(value_type = Option<String>, example = "2024-12-01T00:00:00Z")]
+    pub due_after: Option<DateTime<Utc>>,
+
+    /// Only tasks with (true) or without (false) a reminder
+    pub has_reminder: Option<bool>,
+
+    /// Case-insensitive text searched in the title and body. Not supported with `top`,
+    /// `skip` or `cursor` on Microsoft To Do lists.
+    #[param(example = "groceries")]
+    pub search: Option<String>,
+
+    /// Field used to order the tasks. `due_date_time` is not supported with `top`, `skip` or
+    /// `cursor` on Microsoft To Do lists.
+    pub order_by: Option<TaskOrderBy>,
+
+    /// Ordering direction, ascending by default
+    pub order: Option<SortOrder>,
+}
+
+impl TaskFilterQuery {
+    /// Whether any option Microsoft Graph cannot apply in its queries is set
+    pub fn has_local_options(&self) -> bool {
+        self.due_before.is_some()
+            || self.due_after.is_some()
+            || self
+                .search
+                .as_deref()
+                .is_some_and(|search| !search.trim().is_empty())
+            || self.order_by == Some(TaskOrderBy::DueDateTime)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "TaskOrderBy")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskOrderBy {
+    Title,
+    Importance,
+    DueDateTime,
+    CreatedDateTime,
+    LastModifiedDateTime,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq)]
+#[schema(title = "SortOrder")]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Options of `GET /lists/{list_id}/tasks/{task_id}`
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskDetailQuery {
+    /// Include the checklist items of the task
+    #[param(example = true)]
+    pub include_checklist: Option<bool>,
+}
+
+/// One page of tasks and the cursor of the next one, if any
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TaskPage {
+    pub tasks: Vec<Task>,
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 #[schema(title = "CreateTaskRequest")]
 pub struct CreateTaskRequest {
@@ -80,6 +210,12 @@ pub struct CreateTaskRequest {
 
     pub reminder_date_time: Option<DateTime<Utc>>,
     pub due_date_time: Option<DateTime<Utc>>,
+
+    /// Makes the task repeat; requires a due date
+    pub recurrence: Option<TaskRecurrence>,
+
+    /// Checklist items created together with the task
+    pub checklist_items: Option<Vec<CreateChecklistItemRequest>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -98,6 +234,12 @@ pub struct CreateTaskRequestBody {
 
     pub reminder_date_time: Option<DateTime<Utc>>,
     pub due_date_time: Option<DateTime<Utc>>,
+
+    /// Makes the task repeat; requires a due date
+    pub recurrence: Option<TaskRecurrence>,
+
+    /// Checklist items created together with the task
+    pub checklist_items: Option<Vec<CreateChecklistItemRequest>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -123,6 +265,9 @@ pub struct UpdateTaskRequest {
 
     pub reminder_date_time: Option<DateTime<Utc>>,
     pub due_date_time: Option<DateTime<Utc>>,
+
+    /// Replaces the recurrence of the task
+    pub recurrence: Option<TaskRecurrence>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -142,6 +287,9 @@ pub struct UpdateTaskRequestBody {
 
     pub reminder_date_time: Option<DateTime<Utc>>,
     pub due_date_time: Option<DateTime<Utc>>,
+
+    /// Replaces the recurrence of the task
+    pub recurrence: Option<TaskRecurrence>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -158,48 +306,203 @@ pub struct DeleteTaskRequest {
     pub list_id: String,
 }
 
-impl From<TaskResponse> for Task {
-    fn from(response: TaskResponse) -> Self {
-        let default_time = Utc::now();
+/// Body of `POST /lists/{list_id}/tasks/batch`
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "CreateTasksRequest")]
+pub struct CreateTasksRequest {
+    /// Tasks to create; checklist items are not supported in bulk
+    pub tasks: Vec<CreateTaskRequestBody>,
+}
 
-        let created_date_time = DateTime::parse_from_rfc3339(&response.created_date_time)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or(default_time);
+/// Body of `PATCH /lists/{list_id}/tasks/batch`
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "UpdateTasksRequest")]
+pub struct UpdateTasksRequest {
+    pub tasks: Vec<UpdateTaskItem>,
+}
 
-        let last_modified_date_time =
-            DateTime::parse_from_rfc3339(&response.last_modified_date_time)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or(default_time);
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "UpdateTaskItem")]
+pub struct UpdateTaskItem {
+    #[schema(
+        example = "AAMkAGVmMDEzMTM4LTZmYWUtNDdkNC1hMDZiLTU1OGY5OTZhNGY2NAAuAAAAAAAiQ8W967B7TKBjgx9rVEURBwAiIsqMbYjsT5e-T_KzowKTAAAAAAEKAAA"
+    )]
+    pub id: String,
 
-        let status = match response.status.as_str() {
-            "notStarted" => TaskStatus::NotStarted,
-            "inProgress" => TaskStatus::InProgress,
-            "completed" => TaskStatus::Completed,
-            "waitingOnOthers" => TaskStatus::WaitingOnOthers,
-            "deferred" => TaskStatus::Deferred,
-            _ => TaskStatus::NotStarted,
-        };
+    #[serde(flatten)]
+    pub changes: UpdateTaskRequestBody,
+}
 
-        let importance = match response.importance.as_str() {
-            "low" => TaskImportance::Low,
-            "normal" => TaskImportance::Normal,
-            "high" => TaskImportance::High,
-            _ => TaskImportance::Normal,
-        };
+/// Body of `POST /lists/{list_id}/tasks/batch/complete` and `DELETE /lists/{list_id}/tasks/batch`
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "TaskIdsRequest")]
+pub struct TaskIdsRequest {
+    pub task_ids: Vec<String>,
+}
+
+/// Outcome of every item of a bulk operation, in the order of the request
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "TaskBatchResult")]
+pub struct TaskBatchResult {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<TaskBatchItemResult>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[schema(title = "TaskBatchItemResult")]
+pub struct TaskBatchItemResult {
+    /// Position of the item in the request
+    #[schema(example = 0)]
+    pub index: usize,
+
+    /// Id of the task, when known
+    pub task_id: Option<String>,
+
+    pub success: bool,
+
+    /// HTTP status of the item; 400 for items rejected before reaching Microsoft Graph
+    #[schema(example = 201)]
+    pub status: u16,
+
+    /// Created or updated task; absent on deletions and failures
+    pub task: Option<Task>,
+
+    pub errors: Vec<String>,
+}
+
+impl TaskBatchItemResult {
+    pub fn success(index: usize, task_id: String, status: u16, task: Option<Task>) -> Self {
+        Self {
+            index,
+            task_id: Some(task_id),
+            success: true,
+            status,
+            task,
+            errors: vec![],
+        }
+    }
+
+    pub fn error(index: usize, task_id: Option<String>, status: u16, message: &str) -> Self {
+        Self {
+            index,
+            task_id,
+            success: false,
+            status,
+            task: None,
+            errors: vec![message.to_string()],
+        }
+    }
+}
+
+impl TaskStatus {
+    /// Name used by Microsoft Graph, also stored by the postgres provider
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::NotStarted => "notStarted",
+            TaskStatus::InProgress => "inProgress",
+            TaskStatus::Completed => "completed",
+            TaskStatus::WaitingOnOthers => "waitingOnOthers",
+            TaskStatus::Deferred => "deferred",
+        }
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "notStarted" => Ok(TaskStatus::NotStarted),
+            "inProgress" => Ok(TaskStatus::InProgress),
+            "completed" => Ok(TaskStatus::Completed),
+            "waitingOnOthers" => Ok(TaskStatus::WaitingOnOthers),
+            "deferred" => Ok(TaskStatus::Deferred),
+            other => Err(format!("Unknown task status: {}", other)),
+        }
+    }
+}
+
+impl TaskImportance {
+    /// Name used by Microsoft Graph, also stored by the postgres provider
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskImportance::Low => "low",
+            TaskImportance::Normal => "normal",
+            TaskImportance::High => "high",
+        }
+    }
+}
+
+impl FromStr for TaskImportance {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(TaskImportance::Low),
+            "normal" => Ok(TaskImportance::Normal),
+            "high" => Ok(TaskImportance::High),
+            other => Err(format!("Unknown task importance: {}", other)),
+        }
+    }
+}
+
+impl TaskBatchResult {
+    pub fn new(mut results: Vec<TaskBatchItemResult>) -> Self {
+        results.sort_by_key(|result| result.index);
+        let succeeded = results.iter().filter(|result| result.success).count();
+
+        Self {
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+}
+
+impl TryFrom<TaskResponse> for Task {
+    type Error = String;
+
+    fn try_from(response: TaskResponse) -> Result<Self, Self::Error> {
+        let created_date_time = parse_graph_timestamp(&response.created_date_time)?;
+        let last_modified_date_time = parse_graph_timestamp(&response.last_modified_date_time)?;
+
+        let status = response.status.parse::<TaskStatus>()?;
+        let importance = response.importance.parse::<TaskImportance>()?;
 
         let body = response.body.map(|b| b.content);
 
         let reminder_date_time = response
             .reminder_date_time
-            .and_then(|rdt| DateTime::parse_from_rfc3339(&rdt.date_time).ok())
-            .map(|dt| dt.with_timezone(&Utc));
+            .map(|rdt| parse_graph_date_time(&rdt.date_time, &rdt.time_zone))
+            .transpose()?;
 
         let due_date_time = response
             .due_date_time
-            .and_then(|ddt| DateTime::parse_from_rfc3339(&ddt.date_time).ok())
-            .map(|dt| dt.with_timezone(&Utc));
-
-        Task {
+            .map(|ddt| parse_graph_date_time(&ddt.date_time, &ddt.time_zone))
+            .transpose()?;
+
+        let recurrence = response
+            .recurrence
+            .map(TaskRecurrence::try_from)
+            .transpose()?;
+
+        let completed_date_time = response
+            .completed_date_time
+            .map(|cdt| parse_graph_date_time(&cdt.date_time, &cdt.time_zone))
+            .transpose()?;
+
+        let checklist_items = response
+            .checklist_items
+            .map(|items| {
+                items
+                    .into_iter()
+                    .map(ChecklistItem::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(Task {
             id: response.id,
             title: response.title,
             body,
@@ -208,8 +511,187 @@ impl From<TaskResponse> for Task {
             is_reminder_on: response.is_reminder_on,
             reminder_date_time,
             due_date_time,
+            recurrence,
+            completed_date_time,
             created_date_time,
             last_modified_date_time,
+            checklist_items,
+        })
+    }
+}
+
+/// Google Tasks has no importance, reminder, recurrence or creation date: tasks are normal,
+/// without reminder, and created when last modified
+impl TryFrom<GoogleTaskResponse> for Task {
+    type Error = String;
+
+    fn try_from(response: GoogleTaskResponse) -> Result<Self, Self::Error> {
+        let last_modified_date_time = response
+            .updated
+            .as_deref()
+            .ok_or_else(|| format!("Task {} has no modification date", response.id))
+            .and_then(parse_google_timestamp)?;
+
+        let status = match response.status.as_str() {
+            "needsAction" => TaskStatus::NotStarted,
+            "completed" => TaskStatus::Completed,
+            other => return Err(format!("Unknown task status: {}", other)),
+        };
+
+        let due_date_time = response
+            .due
+            .as_deref()
+            .map(parse_google_due_date)
+            .transpose()?;
+
+        let completed_date_time = response
+            .completed
+            .as_deref()
+            .map(parse_google_timestamp)
+            .transpose()?;
+
+        Ok(Task {
+            id: response.id,
+            title: response.title.unwrap_or_default(),
+            body: response.notes,
+            status,
+            importance: TaskImportance::Normal,
+            is_reminder_on: false,
+            reminder_date_time: None,
+            due_date_time,
+            recurrence: None,
+            completed_date_time,
+            created_date_time: last_modified_date_time,
+            last_modified_date_time,
+            checklist_items: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::microsoft_graph_mod::graph_date_time::user_time_zone;
+    use chrono::NaiveDate;
+
+    fn google_task(status: &str, due: Option<&str>, updated: Option<&str>) -> GoogleTaskResponse {
+        GoogleTaskResponse {
+            id: "dGFzay0x".to_string(),
+            title: Some("Buy milk".to_string()),
+            notes: Some("Whole milk".to_string()),
+            status: status.to_string(),
+            due: due.map(str::to_string),
+            completed: None,
+            updated: updated.map(str::to_string),
+            parent: None,
         }
     }
+
+    #[test]
+    fn options_graph_cannot_apply_are_local() {
+        assert!(!TaskFilterQuery::default().has_local_options());
+        assert!(!TaskFilterQuery {
+            status: Some(TaskStatus::Completed),
+            order_by: Some(TaskOrderBy::Title),
+            search: Some("  ".to_string()),
+            ..Default::default()
+        }
+        .has_local_options());
+
+        assert!(TaskFilterQuery {
+            due_before: Some(Utc::now()),
+            ..Default::default()
+        }
+        .has_local_options());
+        assert!(TaskFilterQuery {
+            search: Some("milk".to_string()),
+            ..Default::default()
+        }
+        .has_local_options());
+        assert!(TaskFilterQuery {
+            order_by: Some(TaskOrderBy::DueDateTime),
+            ..Default::default()
+        }
+        .has_local_options());
+    }
+
+    #[test]
+    fn maps_the_completion_date_of_a_graph_task() {
+        let response: TaskResponse = serde_json::from_value(serde_json::json!({
+            "id": "task-1",
+            "title": "Buy milk",
+            "status": "completed",
+            "importance": "normal",
+            "isReminderOn": false,
+            "completedDateTime": {
+                "dateTime": "2024-05-08T14:30:00.0000000",
+                "timeZone": "UTC"
+            },
+            "createdDateTime": "2024-05-01T09:00:00Z",
+            "lastModifiedDateTime": "2024-05-08T14:30:01Z"
+        }))
+        .unwrap();
+
+        let task = Task::try_from(response).unwrap();
+
+        assert_eq!(
+            task.completed_date_time,
+            Some("2024-05-08T14:30:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn maps_a_google_task() {
+        let response = google_task(
+            "needsAction",
+            Some("2024-05-10T00:00:00.000Z"),
+            Some("2024-05-08T14:30:00.000Z"),
+        );
+
+        let task = Task::try_from(response).unwrap();
+
+        let updated = "2024-05-08T14:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let due_date = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        assert_eq!(task.id, "dGFzay0x");
+        assert_eq!(task.title, "Buy milk");
+        assert_eq!(task.body.as_deref(), Some("Whole milk"));
+        assert_eq!(task.status, TaskStatus::NotStarted);
+        assert_eq!(task.importance, TaskImportance::Normal);
+        assert_eq!(
+            task.due_date_time
+                .unwrap()
+                .with_timezone(&user_time_zone())
+                .date_naive(),
+            due_date
+        );
+        assert_eq!(task.created_date_time, updated);
+        assert_eq!(task.last_modified_date_time, updated);
+    }
+
+    #[test]
+    fn maps_completed_google_tasks_without_title() {
+        let mut response = google_task("completed", None, Some("2024-05-08T14:30:00.000Z"));
+        response.title = None;
+        response.completed = Some("2024-05-08T14:30:00.000Z".to_string());
+
+        let task = Task::try_from(response).unwrap();
+
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(
+            task.completed_date_time,
+            Some("2024-05-08T14:30:00Z".parse().unwrap())
+        );
+        assert_eq!(task.title, "");
+        assert_eq!(task.due_date_time, None);
+    }
+
+    #[test]
+    fn rejects_invalid_google_tasks() {
+        let updated = Some("2024-05-08T14:30:00.000Z");
+
+        assert!(Task::try_from(google_task("needsAction", None, None)).is_err());
+        assert!(Task::try_from(google_task("needsAction", None, Some("yesterday"))).is_err());
+        assert!(Task::try_from(google_task("deleted", None, updated)).is_err());
+        assert!(Task::try_from(google_task("needsAction", Some("tomorrow"), updated)).is_err());
+    }
 }
@@ -1,37 +1,65 @@
 use crate::business_response::BusinessResponse;
 use crate::todo_mod::task::{
-    CreateTaskRequest, CreateTaskRequestBody, Task, UpdateTaskRequest, UpdateTaskRequestBody,
+    CreateTaskRequest, CreateTaskRequestBody, CreateTasksRequest, Task, TaskBatchResult,
+    TaskDetailQuery, TaskFilterQuery, TaskIdsRequest, TaskPageQuery, UpdateTaskRequest,
+    UpdateTaskRequestBody, UpdateTasksRequest,
 };
 use crate::todo_mod::task_logic;
 use actix_web::{delete, get, patch, post, web, HttpResponse, Responder};
 use tracing::{error, info};
 
+/// Response header carrying the cursor of the next page of tasks
+const NEXT_CURSOR_HEADER: &str = "X-Next-Cursor";
+
 #[utoipa::path(
     get,
     path = "/api/friday-todo-manager/lists/{list_id}/tasks",
     responses(
-        (status = 200, description = "All tasks from the specified todo list retrieved successfully. Returns an array of tasks with their current status, due dates, and other metadata.", body = BusinessResponse<Vec<Task>>),
-        (status = 400, description = "Bad request - invalid list ID format or missing required parameters", body = BusinessResponse<String>),
+        (status = 200, description = "Tasks from the specified todo list retrieved successfully. Without paging parameters every page is fetched from Microsoft Graph and the whole list is returned; with top, skip or cursor a single page is returned. Status, importance, has_reminder, due date range, search and ordering narrow and sort the tasks.", body = BusinessResponse<Vec<Task>>,
+            headers(
+                ("X-Next-Cursor" = String, description = "Cursor of the next page, only present for paged requests with more tasks to fetch")
+            )
+        ),
+        (status = 400, description = "Bad request - invalid list ID format, invalid paging or filter parameters or missing required parameters", body = BusinessResponse<String>),
         (status = 500, description = "Internal server error - Microsoft Graph API error or system failure", body = BusinessResponse<String>)
     ),
     params(
-        ("list_id" = String, Path, description = "The unique identifier of the todo list to retrieve tasks from. This is typically a Microsoft Graph ID.")
+        ("list_id" = String, Path, description = "The unique identifier of the todo list to retrieve tasks from. This is typically a Microsoft Graph ID."),
+        TaskPageQuery,
+        TaskFilterQuery
     ),
     tag = "Tasks"
 )]
 #[get("/api/friday-todo-manager/lists/{list_id}/tasks")]
-pub async fn get_all_tasks(path: web::Path<String>) -> impl Responder {
+pub async fn get_all_tasks(
+    path: web::Path<String>,
+    query: web::Query<TaskPageQuery>,
+    filter: web::Query<TaskFilterQuery>,
+) -> impl Responder {
     let list_id = path.into_inner();
     info!(
         "Controller layer: GET /api/friday-todo-manager/lists/{}/tasks",
         list_id
     );
 
-    match task_logic::get_all_tasks(&list_id).await {
+    match task_logic::get_all_tasks(&list_id, query.into_inner(), filter.into_inner()).await {
         Ok(response) => {
             if response.success {
                 info!("Controller layer: Successfully retrieved tasks");
-                HttpResponse::Ok().json(response)
+
+                let page = response.data;
+                let next_cursor = page.as_ref().and_then(|page| page.next_cursor.clone());
+                let response = BusinessResponse {
+                    success: response.success,
+                    data: page.map(|page| page.tasks),
+                    errors: response.errors,
+                };
+
+                let mut http_response = HttpResponse::Ok();
+                if let Some(next_cursor) = next_cursor {
+                    http_response.insert_header((NEXT_CURSOR_HEADER, next_cursor));
+                }
+                http_response.json(response)
             } else {
                 error!(
                     "Controller layer: Business logic error: {:?}",
@@ -60,19 +88,23 @@ pub async fn get_all_tasks(path: web::Path<String>) -> impl Responder {
     ),
     params(
         ("list_id" = String, Path, description = "Todo list unique identifier"),
-        ("task_id" = String, Path, description = "Task unique identifier")
+        ("task_id" = String, Path, description = "Task unique identifier"),
+        TaskDetailQuery
     ),
     tag = "Tasks"
 )]
 #[get("/api/friday-todo-manager/lists/{list_id}/tasks/{task_id}")]
-pub async fn get_task(path: web::Path<(String, String)>) -> impl Responder {
+pub async fn get_task(
+    path: web::Path<(String, String)>,
+    query: web::Query<TaskDetailQuery>,
+) -> impl Responder {
     let (list_id, task_id) = path.into_inner();
     info!(
         "Controller layer: GET /api/friday-todo-manager/lists/{}/tasks/{}",
         list_id, task_id
     );
 
-    match task_logic::get_task(&list_id, &task_id).await {
+    match task_logic::get_task(&list_id, &task_id, query.include_checklist.unwrap_or(false)).await {
         Ok(response) => {
             if response.success {
                 info!("Controller layer: Successfully retrieved task");
@@ -104,7 +136,7 @@ pub async fn get_task(path: web::Path<(String, String)>) -> impl Responder {
     path = "/api/friday-todo-manager/lists/{list_id}/tasks",
     request_body = CreateTaskRequestBody,
     responses(
-        (status = 200, description = "Task created successfully", body = BusinessResponse<Task>),
+        (status = 200, description = "Task created successfully, together with its checklist items when given. Checklist items that could not be created are listed in errors.", body = BusinessResponse<Task>),
         (status = 400, description = "Bad request", body = BusinessResponse<String>),
         (status = 500, description = "Internal server error", body = BusinessResponse<String>)
     ),
@@ -124,10 +156,12 @@ pub async fn create_task(
         list_id: Some(list_id.clone()),
         title: request.title.clone(),
         body: request.body.clone(),
-        importance: request.importance.clone(),
+        importance: request.importance,
         is_reminder_on: request.is_reminder_on,
         reminder_date_time: request.reminder_date_time,
         due_date_time: request.due_date_time,
+        recurrence: request.recurrence.clone(),
+        checklist_items: request.checklist_items.clone(),
     };
 
     info!(
@@ -185,11 +219,12 @@ pub async fn update_task(
         list_id: Some(list_id.clone()),
         title: request.title.clone(),
         body: request.body.clone(),
-        status: request.status.clone(),
-        importance: request.importance.clone(),
+        status: request.status,
+        importance: request.importance,
         is_reminder_on: request.is_reminder_on,
         reminder_date_time: request.reminder_date_time,
         due_date_time: request.due_date_time,
+        recurrence: request.recurrence.clone(),
     };
 
     info!(
@@ -273,3 +308,169 @@ pub async fn delete_task(path: web::Path<(String, String)>) -> impl Responder {
         }
     }
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/batch",
+    request_body = CreateTasksRequest,
+    responses(
+        (status = 200, description = "Every task created", body = BusinessResponse<TaskBatchResult>),
+        (status = 207, description = "Some tasks failed; see the result of each item", body = BusinessResponse<TaskBatchResult>),
+        (status = 400, description = "Bad request - empty list ID or no tasks or more than 100", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier")
+    ),
+    tag = "Tasks"
+)]
+#[post("/api/friday-todo-manager/lists/{list_id}/tasks/batch")]
+pub async fn create_tasks(
+    path: web::Path<String>,
+    request: web::Json<CreateTasksRequest>,
+) -> impl Responder {
+    let list_id = path.into_inner();
+    info!(
+        "Controller layer: POST /api/friday-todo-manager/lists/{}/tasks/batch",
+        list_id
+    );
+
+    to_batch_http_response(
+        task_logic::create_tasks(&list_id, request.into_inner()).await,
+        "creating tasks",
+    )
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/batch",
+    request_body = UpdateTasksRequest,
+    responses(
+        (status = 200, description = "Every task updated", body = BusinessResponse<TaskBatchResult>),
+        (status = 207, description = "Some tasks failed; see the result of each item", body = BusinessResponse<TaskBatchResult>),
+        (status = 400, description = "Bad request - empty list ID or no tasks or more than 100", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier")
+    ),
+    tag = "Tasks"
+)]
+#[patch("/api/friday-todo-manager/lists/{list_id}/tasks/batch")]
+pub async fn update_tasks(
+    path: web::Path<String>,
+    request: web::Json<UpdateTasksRequest>,
+) -> impl Responder {
+    let list_id = path.into_inner();
+    info!(
+        "Controller layer: PATCH /api/friday-todo-manager/lists/{}/tasks/batch",
+        list_id
+    );
+
+    to_batch_http_response(
+        task_logic::update_tasks(&list_id, request.into_inner()).await,
+        "updating tasks",
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/batch/complete",
+    request_body = TaskIdsRequest,
+    responses(
+        (status = 200, description = "Every task marked as completed", body = BusinessResponse<TaskBatchResult>),
+        (status = 207, description = "Some tasks failed; see the result of each item", body = BusinessResponse<TaskBatchResult>),
+        (status = 400, description = "Bad request - empty list ID or no tasks or more than 100", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier")
+    ),
+    tag = "Tasks"
+)]
+#[post("/api/friday-todo-manager/lists/{list_id}/tasks/batch/complete")]
+pub async fn complete_tasks(
+    path: web::Path<String>,
+    request: web::Json<TaskIdsRequest>,
+) -> impl Responder {
+    let list_id = path.into_inner();
+    info!(
+        "Controller layer: POST /api/friday-todo-manager/lists/{}/tasks/batch/complete",
+        list_id
+    );
+
+    to_batch_http_response(
+        task_logic::complete_tasks(&list_id, request.into_inner()).await,
+        "completing tasks",
+    )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/friday-todo-manager/lists/{list_id}/tasks/batch",
+    request_body = TaskIdsRequest,
+    responses(
+        (status = 200, description = "Every task deleted", body = BusinessResponse<TaskBatchResult>),
+        (status = 207, description = "Some tasks failed; see the result of each item", body = BusinessResponse<TaskBatchResult>),
+        (status = 400, description = "Bad request - empty list ID or no tasks or more than 100", body = BusinessResponse<String>),
+        (status = 500, description = "Internal server error", body = BusinessResponse<String>)
+    ),
+    params(
+        ("list_id" = String, Path, description = "Todo list unique identifier")
+    ),
+    tag = "Tasks"
+)]
+#[delete("/api/friday-todo-manager/lists/{list_id}/tasks/batch")]
+pub async fn delete_tasks(
+    path: web::Path<String>,
+    request: web::Json<TaskIdsRequest>,
+) -> impl Responder {
+    let list_id = path.into_inner();
+    info!(
+        "Controller layer: DELETE /api/friday-todo-manager/lists/{}/tasks/batch",
+        list_id
+    );
+
+    to_batch_http_response(
+        task_logic::delete_tasks(&list_id, request.into_inner()).await,
+        "deleting tasks",
+    )
+}
+
+/// 200 when every item succeeded, 207 Multi-Status when some failed
+fn to_batch_http_response(
+    result: Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>>,
+    action: &str,
+) -> HttpResponse {
+    match result {
+        Ok(response) => match &response.data {
+            Some(batch_result) if batch_result.failed == 0 => {
+                info!("Controller layer: Successfully finished {}", action);
+                HttpResponse::Ok().json(response)
+            }
+            Some(batch_result) => {
+                error!(
+                    "Controller layer: {} of {} items failed while {}",
+                    batch_result.failed,
+                    batch_result.results.len(),
+                    action
+                );
+                HttpResponse::MultiStatus().json(response)
+            }
+            None => {
+                error!(
+                    "Controller layer: Business logic error: {:?}",
+                    response.errors
+                );
+                HttpResponse::BadRequest().json(response)
+            }
+        },
+        Err(e) => {
+            error!("Controller layer: System error {}: {}", action, e);
+            HttpResponse::InternalServerError().json(BusinessResponse::<String>::error(&format!(
+                "An internal error occurred while {}",
+                action
+            )))
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use crate::todo_mod::task::{
+    SortOrder, Task, TaskFilterQuery, TaskOrderBy, TaskPage, TaskPageQuery,
+};
+use crate::todo_mod::task_cursor::TaskCursor;
+use std::cmp::Ordering;
+
+/// Applies the options Microsoft Graph applies in its queries, for tasks not fetched with
+/// them: status, importance, reminder and every ordering but the one by due date
+pub fn apply_graph_filters(tasks: &mut Vec<Task>, filter: &TaskFilterQuery) {
+    tasks.retain(|task| {
+        filter.status.is_none_or(|status| task.status == status)
+            && filter
+                .importance
+                .is_none_or(|importance| task.importance == importance)
+            && filter
+                .has_reminder
+                .is_none_or(|has_reminder| task.is_reminder_on == has_reminder)
+    });
+
+    let compare: fn(&Task, &Task) -> Ordering = match filter.order_by {
+        Some(TaskOrderBy::Title) => |a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        Some(TaskOrderBy::Importance) => |a, b| a.importance.cmp(&b.importance),
+        Some(TaskOrderBy::CreatedDateTime) => |a, b| a.created_date_time.cmp(&b.created_date_time),
+        Some(TaskOrderBy::LastModifiedDateTime) => {
+            |a, b| a.last_modified_date_time.cmp(&b.last_modified_date_time)
+        }
+        Some(TaskOrderBy::DueDateTime) | None => return,
+    };
+
+    if filter.order == Some(SortOrder::Desc) {
+        tasks.sort_by(|a, b| compare(b, a));
+    } else {
+        tasks.sort_by(compare);
+    }
+}
+
+/// Applies the due date range, the text search and the ordering by due date, which are not
+/// delegated to Microsoft Graph
+pub fn apply_local_filters(tasks: &mut Vec<Task>, filter: &TaskFilterQuery) {
+    let search = filter
+        .search
+        .as_deref()
+        .map(|search| search.trim().to_lowercase())
+        .filter(|search| !search.is_empty());
+
+    tasks.retain(|task| {
+        let due_date_time = task.due_date_time;
+        let in_due_range = filter
+            .due_before
+            .is_none_or(|due_before| due_date_time.is_some_and(|due| due < due_before))
+            && filter
+                .due_after
+                .is_none_or(|due_after| due_date_time.is_some_and(|due| due > due_after));
+
+        if !in_due_range {
+            return false;
+        }
+
+        match &search {
+            Some(search) => {
+                task.title.to_lowercase().contains(search)
+                    || task
+                        .body
+                        .as_deref()
+                        .is_some_and(|body| body.to_lowercase().contains(search))
+            }
+            None => true,
+        }
+    });
+
+    if filter.order_by == Some(TaskOrderBy::DueDateTime) {
+        // Tasks without a due date go last in both directions
+        let descending = filter.order == Some(SortOrder::Desc);
+        tasks.sort_by(|a, b| match (a.due_date_time, b.due_date_time) {
+            (Some(a), Some(b)) if descending => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+    }
+}
+
+/// Pages tasks already filtered and ordered, for providers without paging at the source.
+/// The cursor holds the number of tasks already returned and the page size; `None` when it
+/// is invalid.
+pub fn apply_page(tasks: Vec<Task>, page: &TaskPageQuery) -> Option<TaskPage> {
+    let (skip, top) = match page.cursor.as_deref() {
+        Some(cursor) => {
+            let cursor = TaskCursor::decode(cursor).filter(|cursor| cursor.skip_token.is_none())?;
+            (cursor.skip.unwrap_or(0), cursor.top)
+        }
+        None => (page.skip.unwrap_or(0), page.top),
+    };
+
+    let mut tasks: Vec<Task> = tasks.into_iter().skip(skip as usize).collect();
+    let next_cursor = match top {
+        Some(top) if tasks.len() > top as usize => {
+            tasks.truncate(top as usize);
+            let cursor = TaskCursor {
+                top: Some(top),
+                skip: Some(skip + top),
+                skip_token: None,
+            };
+            Some(cursor.encode())
+        }
+        _ => None,
+    };
+
+    Some(TaskPage { tasks, next_cursor })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo_mod::task::{TaskImportance, TaskStatus};
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn task(title: &str, body: Option<&str>, due_date_time: Option<DateTime<Utc>>) -> Task {
+        let created_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+
+        Task {
+            id: title.to_string(),
+            title: title.to_string(),
+            body: body.map(str::to_string),
+            status: TaskStatus::NotStarted,
+            importance: TaskImportance::Normal,
+            is_reminder_on: false,
+            reminder_date_time: None,
+            due_date_time,
+            recurrence: None,
+            completed_date_time: None,
+            created_date_time,
+            last_modified_date_time: created_date_time,
+            checklist_items: None,
+        }
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, 0, 0, 0).unwrap()
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    #[test]
+    fn due_range_is_exclusive_and_drops_tasks_without_due_date() {
+        let mut tasks = vec![
+            task("first", None, Some(day(1))),
+            task("second", None, Some(day(2))),
+            task("third", None, Some(day(3))),
+            task("undated", None, None),
+        ];
+
+        apply_local_filters(
+            &mut tasks,
+            &TaskFilterQuery {
+                due_after: Some(day(1)),
+                due_before: Some(day(3)),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(titles(&tasks), vec!["second"]);
+    }
+
+    #[test]
+    fn search_matches_title_or_body_ignoring_case() {
+        let mut tasks = vec![
+            task("Buy Groceries", None, None),
+            task("Call mom", Some("ask about the GROCERIES list"), None),
+            task("Pay bills", Some("electricity"), None),
+        ];
+
+        apply_local_filters(
+            &mut tasks,
+            &TaskFilterQuery {
+                search: Some("  groceries ".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(titles(&tasks), vec!["Buy Groceries", "Call mom"]);
+    }
+
+    #[test]
+    fn blank_search_keeps_every_task() {
+        let mut tasks = vec![task("a", None, None), task("b", None, None)];
+
+        apply_local_filters(
+            &mut tasks,
+            &TaskFilterQuery {
+                search: Some("   ".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[test]
+    fn due_date_ordering_puts_undated_tasks_last_in_both_directions() {
+        let tasks = vec![
+            task("undated", None, None),
+            task("second", None, Some(day(2))),
+            task("first", None, Some(day(1))),
+        ];
+
+        let mut ascending = tasks.clone();
+        apply_local_filters(
+            &mut ascending,
+            &TaskFilterQuery {
+                order_by: Some(TaskOrderBy::DueDateTime),
+                ..Default::default()
+            },
+        );
+        assert_eq!(titles(&ascending), vec!["first", "second", "undated"]);
+
+        let mut descending = tasks;
+        apply_local_filters(
+            &mut descending,
+            &TaskFilterQuery {
+                order_by: Some(TaskOrderBy::DueDateTime),
+                order: Some(SortOrder::Desc),
+                ..Default::default()
+            },
+        );
+        assert_eq!(titles(&descending), vec!["second", "first", "undated"]);
+    }
+
+    #[test]
+    fn other_orderings_are_left_to_graph() {
+        let mut tasks = vec![task("b", None, None), task("a", None, None)];
+
+        apply_local_filters(
+            &mut tasks,
+            &TaskFilterQuery {
+                order_by: Some(TaskOrderBy::Title),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(titles(&tasks), vec!["b", "a"]);
+    }
+
+    fn page(top: Option<u32>, cursor: Option<String>) -> TaskPageQuery {
+        TaskPageQuery {
+            top,
+            cursor,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cursor_pages_keep_the_page_size() {
+        let tasks: Vec<Task> = ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .map(|title| task(title, None, None))
+            .collect();
+
+        let first = apply_page(tasks.clone(), &page(Some(2), None)).unwrap();
+        assert_eq!(titles(&first.tasks), vec!["a", "b"]);
+
+        let second = apply_page(tasks.clone(), &page(None, first.next_cursor)).unwrap();
+        assert_eq!(titles(&second.tasks), vec!["c", "d"]);
+
+        let last = apply_page(tasks, &page(None, second.next_cursor)).unwrap();
+        assert_eq!(titles(&last.tasks), vec!["e"]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn rejects_cursors_not_issued_by_the_service() {
+        let tasks = vec![task("a", None, None)];
+
+        assert!(apply_page(tasks.clone(), &page(None, Some("2".to_string()))).is_none());
+
+        let skip_token = TaskCursor {
+            top: None,
+            skip: None,
+            skip_token: Some("token".to_string()),
+        };
+        assert!(apply_page(tasks, &page(None, Some(skip_token.encode()))).is_none());
+    }
+}
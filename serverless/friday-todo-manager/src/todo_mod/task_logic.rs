@@ -1,54 +1,79 @@
 use crate::business_response::BusinessResponse;
-use crate::microsoft_graph_mod::tasks_microsoft_graph_api::TasksMicrosoftGraphApi;
-use crate::oauth_mod::oauth_api::OAuthApi;
-use crate::todo_mod::task::{CreateTaskRequest, Task, UpdateTaskRequest};
+use crate::provider_mod::todo_provider_logic;
+use crate::todo_mod::checklist_item_logic;
+use crate::todo_mod::recurrence::{RecurrencePatternType, RecurrenceRangeType, TaskRecurrence};
+use crate::todo_mod::task::{
+    CreateTaskRequest, CreateTasksRequest, Task, TaskBatchItemResult, TaskBatchResult,
+    TaskFilterQuery, TaskIdsRequest, TaskPage, TaskPageQuery, TaskStatus, UpdateTaskRequest,
+    UpdateTasksRequest,
+};
 use tracing::{debug, error, info, warn};
 
+/// Upper bound of tasks in a bulk request, sent to Graph in `$batch` calls of 20
+const MAX_BATCH_ITEMS: usize = 100;
+
+/// Tasks of a list, filtered, searched and ordered as requested by the provider owning the
+/// list. For Microsoft lists the options Graph does not support are applied within the
+/// requested page, and unpaged requests are served from the cache when it is enabled.
 pub async fn get_all_tasks(
     list_id: &str,
-) -> Result<BusinessResponse<Vec<Task>>, Box<dyn std::error::Error>> {
-    info!("Logic layer: Getting all tasks from list ID: {}", list_id);
+    page: TaskPageQuery,
+    filter: TaskFilterQuery,
+) -> Result<BusinessResponse<TaskPage>, Box<dyn std::error::Error>> {
+    info!("Logic layer: Getting tasks from list ID: {}", list_id);
 
     if list_id.trim().is_empty() {
         warn!("Logic layer: Invalid list_id provided (empty or whitespace)");
         return Ok(BusinessResponse::error("List ID cannot be empty"));
     }
 
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
+    if page.top == Some(0) {
+        warn!("Logic layer: Invalid top provided (zero)");
+        return Ok(BusinessResponse::error("Top must be greater than zero"));
+    }
+
+    if page.cursor.is_some() && (page.top.is_some() || page.skip.is_some()) {
+        warn!("Logic layer: Cursor combined with top or skip");
+        return Ok(BusinessResponse::error(
+            "Cursor cannot be combined with top or skip",
+        ));
+    }
+
+    if let (Some(due_after), Some(due_before)) = (filter.due_after, filter.due_before) {
+        if due_after >= due_before {
+            warn!("Logic layer: Invalid due date range provided");
             return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
+                "due_after must be before due_before",
             ));
         }
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = TasksMicrosoftGraphApi::new();
-    match graph_client.get_tasks(list_id, &access_token).await {
+    match provider.get_tasks(list_id, &page, &filter).await {
         Ok(response) => {
             if response.success {
                 info!(
                     "Logic layer: Successfully retrieved {} tasks",
-                    response.data.as_ref().map(|tasks| tasks.len()).unwrap_or(0)
+                    response
+                        .data
+                        .as_ref()
+                        .map(|page| page.tasks.len())
+                        .unwrap_or(0)
                 );
             } else {
                 warn!(
-                    "Logic layer: Failed to retrieve tasks from Microsoft Graph: {:?}",
+                    "Logic layer: Failed to retrieve tasks: {:?}",
                     response.errors
                 );
             }
             Ok(response)
         }
         Err(e) => {
-            error!(
-                "Logic layer: Error getting tasks from Microsoft Graph: {}",
-                e
-            );
+            error!("Logic layer: Error getting tasks: {}", e);
             Err(e)
         }
     }
@@ -57,6 +82,7 @@ pub async fn get_all_tasks(
 pub async fn get_task(
     list_id: &str,
     task_id: &str,
+    include_checklist: bool,
 ) -> Result<BusinessResponse<Task>, Box<dyn std::error::Error>> {
     info!(
         "Logic layer: Getting task with ID: {} from list: {}",
@@ -73,38 +99,25 @@ pub async fn get_task(
         return Ok(BusinessResponse::error("Task ID cannot be empty"));
     }
 
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = TasksMicrosoftGraphApi::new();
-    match graph_client.get_task(list_id, task_id, &access_token).await {
+    match provider.get_task(list_id, task_id, include_checklist).await {
         Ok(response) => {
             if response.success {
                 info!("Logic layer: Successfully retrieved task");
             } else {
                 warn!(
-                    "Logic layer: Failed to retrieve task from Microsoft Graph: {:?}",
+                    "Logic layer: Failed to retrieve task: {:?}",
                     response.errors
                 );
             }
             Ok(response)
         }
         Err(e) => {
-            error!(
-                "Logic layer: Error getting task from Microsoft Graph: {}",
-                e
-            );
+            error!("Logic layer: Error getting task: {}", e);
             Err(e)
         }
     }
@@ -136,38 +149,92 @@ pub async fn create_task(
         return Ok(BusinessResponse::error("List ID cannot be empty"));
     }
 
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
+    if let Some(recurrence) = &request.recurrence {
+        if request.due_date_time.is_none() {
+            warn!("Logic layer: Recurrence provided without a due date");
             return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
+                "A recurring task requires a due date",
             ));
         }
+
+        if let Some(error_response) = validate_recurrence(recurrence) {
+            return Ok(error_response);
+        }
+    }
+
+    for checklist_item in request.checklist_items.iter().flatten() {
+        if let Some(error_response) =
+            checklist_item_logic::validate_display_name(Some(&checklist_item.display_name))
+        {
+            return Ok(error_response);
+        }
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = TasksMicrosoftGraphApi::new();
-    match graph_client.create_task(request, &access_token).await {
+    let list_id = list_id.clone();
+    let checklist_items = request.checklist_items.clone().unwrap_or_default();
+
+    let mut result = match provider.create_task(request).await {
         Ok(result) => {
             if result.success {
                 debug!("Logic layer: Successfully created task");
             } else {
-                warn!(
-                    "Logic layer: Failed to create task in Microsoft Graph: {:?}",
-                    result.errors
-                );
+                warn!("Logic layer: Failed to create task: {:?}", result.errors);
             }
-            Ok(result)
+            result
         }
         Err(e) => {
-            error!("Logic layer: Error creating task in Microsoft Graph: {}", e);
-            Err(e)
+            error!("Logic layer: Error creating task: {}", e);
+            return Err(e);
+        }
+    };
+
+    let Some(task) = result.data.as_mut().filter(|_| !checklist_items.is_empty()) else {
+        return Ok(result);
+    };
+
+    // The task already exists: checklist items that fail are reported without failing the call
+    let mut created_items = Vec::with_capacity(checklist_items.len());
+    for checklist_item in &checklist_items {
+        match provider
+            .create_checklist_item(&list_id, &task.id, checklist_item)
+            .await
+        {
+            Ok(response) => match response.data {
+                Some(created_item) => created_items.push(created_item),
+                None => {
+                    warn!(
+                        "Logic layer: Failed to create checklist item {}: {:?}",
+                        checklist_item.display_name, response.errors
+                    );
+                    result.errors.extend(response.errors);
+                }
+            },
+            Err(e) => {
+                error!(
+                    "Logic layer: Error creating checklist item {}: {}",
+                    checklist_item.display_name, e
+                );
+                result.errors.push(format!(
+                    "Failed to create checklist item {}",
+                    checklist_item.display_name
+                ));
+            }
         }
     }
+
+    info!(
+        "Logic layer: Created {} of {} checklist items",
+        created_items.len(),
+        checklist_items.len()
+    );
+    task.checklist_items = Some(created_items);
+
+    Ok(result)
 }
 
 pub async fn update_task(
@@ -204,35 +271,26 @@ pub async fn update_task(
         return Ok(BusinessResponse::error("List ID cannot be empty"));
     }
 
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    if let Some(error_response) = request.recurrence.as_ref().and_then(validate_recurrence) {
+        return Ok(error_response);
+    }
+
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = TasksMicrosoftGraphApi::new();
-    match graph_client.update_task(request, &access_token).await {
+    match provider.update_task(request).await {
         Ok(response) => {
             if response.success {
                 info!("Logic layer: Successfully updated task");
             } else {
-                warn!(
-                    "Logic layer: Failed to update task in Microsoft Graph: {:?}",
-                    response.errors
-                );
+                warn!("Logic layer: Failed to update task: {:?}", response.errors);
             }
             Ok(response)
         }
         Err(e) => {
-            error!("Logic layer: Error updating task in Microsoft Graph: {}", e);
+            error!("Logic layer: Error updating task: {}", e);
             Err(e)
         }
     }
@@ -257,42 +315,378 @@ pub async fn delete_task(
         return Ok(BusinessResponse::error("Task ID cannot be empty"));
     }
 
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = TasksMicrosoftGraphApi::new();
-    match graph_client
-        .delete_task(list_id, task_id, &access_token)
-        .await
-    {
+    match provider.delete_task(list_id, task_id).await {
         Ok(response) => {
             if response.success {
                 info!("Logic layer: Successfully deleted task");
             } else {
-                warn!(
-                    "Logic layer: Failed to delete task from Microsoft Graph: {:?}",
-                    response.errors
-                );
+                warn!("Logic layer: Failed to delete task: {:?}", response.errors);
             }
             Ok(response)
         }
         Err(e) => {
-            error!(
-                "Logic layer: Error deleting task from Microsoft Graph: {}",
-                e
-            );
+            error!("Logic layer: Error deleting task: {}", e);
             Err(e)
         }
     }
 }
+
+/// Creates the tasks in bulk, through `$batch` for Microsoft lists. Items are validated one by
+/// one: invalid items and items the provider rejects are reported in the result without
+/// failing the rest.
+pub async fn create_tasks(
+    list_id: &str,
+    request: CreateTasksRequest,
+) -> Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Creating {} tasks in list: {}",
+        request.tasks.len(),
+        list_id
+    );
+
+    if let Some(error_response) = validate_batch(list_id, request.tasks.len()) {
+        return Ok(error_response);
+    }
+
+    let mut results = Vec::new();
+    let mut requests = Vec::new();
+    for (index, task) in request.tasks.into_iter().enumerate() {
+        let error = if task.title.trim().is_empty() {
+            Some("Task title cannot be empty".to_string())
+        } else if task.checklist_items.is_some() {
+            Some("Checklist items are not supported in bulk creation".to_string())
+        } else if task.recurrence.is_some() && task.due_date_time.is_none() {
+            Some("A recurring task requires a due date".to_string())
+        } else {
+            task.recurrence
+                .as_ref()
+                .and_then(validate_recurrence::<()>)
+                .map(|error_response| error_response.errors.join(", "))
+        };
+
+        if let Some(error) = error {
+            warn!("Logic layer: Invalid task at index {}: {}", index, error);
+            results.push(TaskBatchItemResult::error(index, None, 400, &error));
+            continue;
+        }
+
+        requests.push((
+            index,
+            CreateTaskRequest {
+                list_id: Some(list_id.to_string()),
+                title: task.title,
+                body: task.body,
+                importance: task.importance,
+                is_reminder_on: task.is_reminder_on,
+                reminder_date_time: task.reminder_date_time,
+                due_date_time: task.due_date_time,
+                recurrence: task.recurrence,
+                checklist_items: None,
+            },
+        ));
+    }
+
+    if !requests.is_empty() {
+        let provider = match todo_provider_logic::get_list_provider(list_id) {
+            Ok(provider) => provider,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        match provider_results(provider.create_tasks(list_id, requests).await?) {
+            Ok(provider_results) => results.extend(provider_results),
+            Err(error_response) => return Ok(error_response),
+        }
+    }
+
+    Ok(finish_batch("created", results))
+}
+
+/// Updates the tasks in bulk, see `create_tasks`
+pub async fn update_tasks(
+    list_id: &str,
+    request: UpdateTasksRequest,
+) -> Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Updating {} tasks in list: {}",
+        request.tasks.len(),
+        list_id
+    );
+
+    if let Some(error_response) = validate_batch(list_id, request.tasks.len()) {
+        return Ok(error_response);
+    }
+
+    let mut results = Vec::new();
+    let mut requests = Vec::new();
+    for (index, task) in request.tasks.into_iter().enumerate() {
+        let changes = task.changes;
+        let error = if task.id.trim().is_empty() {
+            Some("Task ID cannot be empty".to_string())
+        } else if changes
+            .title
+            .as_deref()
+            .is_some_and(|title| title.trim().is_empty())
+        {
+            Some("Task title cannot be empty".to_string())
+        } else {
+            changes
+                .recurrence
+                .as_ref()
+                .and_then(validate_recurrence::<()>)
+                .map(|error_response| error_response.errors.join(", "))
+        };
+
+        if let Some(error) = error {
+            warn!("Logic layer: Invalid task at index {}: {}", index, error);
+            results.push(TaskBatchItemResult::error(
+                index,
+                Some(task.id),
+                400,
+                &error,
+            ));
+            continue;
+        }
+
+        requests.push((
+            index,
+            UpdateTaskRequest {
+                id: Some(task.id),
+                list_id: Some(list_id.to_string()),
+                title: changes.title,
+                body: changes.body,
+                status: changes.status,
+                importance: changes.importance,
+                is_reminder_on: changes.is_reminder_on,
+                reminder_date_time: changes.reminder_date_time,
+                due_date_time: changes.due_date_time,
+                recurrence: changes.recurrence,
+            },
+        ));
+    }
+
+    send_updates(list_id, requests, results, "updated").await
+}
+
+/// Marks the tasks as completed in bulk, see `create_tasks`
+pub async fn complete_tasks(
+    list_id: &str,
+    request: TaskIdsRequest,
+) -> Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Completing {} tasks in list: {}",
+        request.task_ids.len(),
+        list_id
+    );
+
+    if let Some(error_response) = validate_batch(list_id, request.task_ids.len()) {
+        return Ok(error_response);
+    }
+
+    let (task_ids, results) = split_task_ids(request.task_ids);
+    let requests = task_ids
+        .into_iter()
+        .map(|(index, task_id)| {
+            (
+                index,
+                UpdateTaskRequest {
+                    id: Some(task_id),
+                    list_id: Some(list_id.to_string()),
+                    title: None,
+                    body: None,
+                    status: Some(TaskStatus::Completed),
+                    importance: None,
+                    is_reminder_on: None,
+                    reminder_date_time: None,
+                    due_date_time: None,
+                    recurrence: None,
+                },
+            )
+        })
+        .collect();
+
+    send_updates(list_id, requests, results, "completed").await
+}
+
+/// Deletes the tasks in bulk, see `create_tasks`
+pub async fn delete_tasks(
+    list_id: &str,
+    request: TaskIdsRequest,
+) -> Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>> {
+    info!(
+        "Logic layer: Deleting {} tasks from list: {}",
+        request.task_ids.len(),
+        list_id
+    );
+
+    if let Some(error_response) = validate_batch(list_id, request.task_ids.len()) {
+        return Ok(error_response);
+    }
+
+    let (task_ids, mut results) = split_task_ids(request.task_ids);
+
+    if !task_ids.is_empty() {
+        let provider = match todo_provider_logic::get_list_provider(list_id) {
+            Ok(provider) => provider,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        match provider_results(provider.delete_tasks(list_id, task_ids).await?) {
+            Ok(provider_results) => results.extend(provider_results),
+            Err(error_response) => return Ok(error_response),
+        }
+    }
+
+    Ok(finish_batch("deleted", results))
+}
+
+async fn send_updates(
+    list_id: &str,
+    requests: Vec<(usize, UpdateTaskRequest)>,
+    mut results: Vec<TaskBatchItemResult>,
+    action: &str,
+) -> Result<BusinessResponse<TaskBatchResult>, Box<dyn std::error::Error>> {
+    if !requests.is_empty() {
+        let provider = match todo_provider_logic::get_list_provider(list_id) {
+            Ok(provider) => provider,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        match provider_results(provider.update_tasks(list_id, requests).await?) {
+            Ok(provider_results) => results.extend(provider_results),
+            Err(error_response) => return Ok(error_response),
+        }
+    }
+
+    Ok(finish_batch(action, results))
+}
+
+/// Results of the items sent to the provider, or the error that failed the whole call
+fn provider_results(
+    response: BusinessResponse<Vec<TaskBatchItemResult>>,
+) -> Result<Vec<TaskBatchItemResult>, BusinessResponse<TaskBatchResult>> {
+    match response.data {
+        Some(results) if response.success => Ok(results),
+        _ => {
+            warn!("Logic layer: Bulk operation failed: {:?}", response.errors);
+            Err(BusinessResponse {
+                success: false,
+                data: None,
+                errors: response.errors,
+            })
+        }
+    }
+}
+
+fn finish_batch(
+    action: &str,
+    results: Vec<TaskBatchItemResult>,
+) -> BusinessResponse<TaskBatchResult> {
+    let batch_result = TaskBatchResult::new(results);
+    if batch_result.failed > 0 {
+        warn!(
+            "Logic layer: {} tasks {}, {} failed",
+            batch_result.succeeded, action, batch_result.failed
+        );
+    } else {
+        info!(
+            "Logic layer: Successfully {} {} tasks",
+            action, batch_result.succeeded
+        );
+    }
+
+    BusinessResponse::success(batch_result)
+}
+
+/// Ids to send, paired with their index, and the results of the empty ones
+fn split_task_ids(task_ids: Vec<String>) -> (Vec<(usize, String)>, Vec<TaskBatchItemResult>) {
+    let mut valid_ids = Vec::new();
+    let mut results = Vec::new();
+
+    for (index, task_id) in task_ids.into_iter().enumerate() {
+        if task_id.trim().is_empty() {
+            results.push(TaskBatchItemResult::error(
+                index,
+                None,
+                400,
+                "Task ID cannot be empty",
+            ));
+        } else {
+            valid_ids.push((index, task_id));
+        }
+    }
+
+    (valid_ids, results)
+}
+
+fn validate_batch<T>(list_id: &str, item_count: usize) -> Option<BusinessResponse<T>> {
+    if list_id.trim().is_empty() {
+        warn!("Logic layer: Invalid list_id provided (empty or whitespace)");
+        return Some(BusinessResponse::error("List ID cannot be empty"));
+    }
+
+    if item_count == 0 {
+        warn!("Logic layer: Empty bulk request");
+        return Some(BusinessResponse::error("At least one task is required"));
+    }
+
+    if item_count > MAX_BATCH_ITEMS {
+        warn!(
+            "Logic layer: Too many tasks in bulk request: {}",
+            item_count
+        );
+        return Some(BusinessResponse::error(&format!(
+            "At most {} tasks can be sent at once",
+            MAX_BATCH_ITEMS
+        )));
+    }
+
+    None
+}
+
+/// Checks that the recurrence sets the fields its pattern and range types need
+fn validate_recurrence<T>(recurrence: &TaskRecurrence) -> Option<BusinessResponse<T>> {
+    let pattern = &recurrence.pattern;
+    let range = &recurrence.range;
+
+    let error = if pattern.interval == 0 {
+        "Recurrence interval must be at least 1"
+    } else if (pattern.pattern_type == RecurrencePatternType::Weekly
+        || pattern.pattern_type.is_relative())
+        && pattern
+            .days_of_week
+            .as_ref()
+            .is_none_or(|days| days.is_empty())
+    {
+        "Weekly and relative recurrences require days_of_week"
+    } else if pattern.pattern_type.is_relative() && pattern.index.is_none() {
+        "Relative recurrences require index"
+    } else if pattern.pattern_type.is_absolute()
+        && !pattern
+            .day_of_month
+            .is_some_and(|day| (1..=31).contains(&day))
+    {
+        "Absolute recurrences require day_of_month between 1 and 31"
+    } else if pattern.pattern_type.is_yearly()
+        && !pattern.month.is_some_and(|month| (1..=12).contains(&month))
+    {
+        "Yearly recurrences require month between 1 and 12"
+    } else if range.range_type == RecurrenceRangeType::EndDate
+        && range.end_date.is_none_or(|end| end < range.start_date)
+    {
+        "Recurrences with an end date require end_date on or after start_date"
+    } else if range.range_type == RecurrenceRangeType::Numbered
+        && range.number_of_occurrences.is_none_or(|count| count == 0)
+    {
+        "Numbered recurrences require number_of_occurrences of at least 1"
+    } else {
+        return None;
+    };
+
+    warn!("Logic layer: Invalid recurrence: {}", error);
+    Some(BusinessResponse::error(error))
+}
@@ -1,4 +1,9 @@
+use crate::google_tasks_mod::google_date_time::parse_google_timestamp;
+use crate::google_tasks_mod::google_task_list_response::GoogleTaskListResponse;
+use crate::google_tasks_mod::google_tasks_api::format_list_id;
+use crate::microsoft_graph_mod::graph_date_time::parse_graph_timestamp;
 use crate::microsoft_graph_mod::todo_list_response::TodoListResponse;
+use crate::provider_mod::todo_provider::TodoProviderKind;
 use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -23,13 +28,15 @@ pub struct TodoList {
     #[schema(example = "defaultList")]
     pub wellknown_list_name: Option<String>,
 
-    #[serde(with = "chrono::serde::ts_milliseconds")]
-    #[schema(example = "1699876543000")]
-    pub created_date_time: DateTime<Utc>,
+    /// Absent when Microsoft Graph does not report it
+    #[serde(with = "chrono::serde::ts_milliseconds_option")]
+    #[schema(value_type = Option<i64>, example = "1699876543000")]
+    pub created_date_time: Option<DateTime<Utc>>,
 
-    #[serde(with = "chrono::serde::ts_milliseconds")]
-    #[schema(example = "1699876543000")]
-    pub last_modified_date_time: DateTime<Utc>,
+    /// Absent when Microsoft Graph does not report it
+    #[serde(with = "chrono::serde::ts_milliseconds_option")]
+    #[schema(value_type = Option<i64>, example = "1699876543000")]
+    pub last_modified_date_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -37,6 +44,9 @@ pub struct TodoList {
 pub struct CreateTodoListRequest {
     #[schema(example = "Shopping List", min_length = 1, max_length = 255)]
     pub display_name: String,
+
+    /// Backend the list is stored in; the first of `TODO_PROVIDERS` when absent
+    pub provider: Option<TodoProviderKind>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -60,25 +70,23 @@ pub struct DeleteTodoListRequest {
     pub id: String,
 }
 
-impl From<TodoListResponse> for TodoList {
-    fn from(response: TodoListResponse) -> Self {
-        let default_time = Utc::now();
+impl TryFrom<TodoListResponse> for TodoList {
+    type Error = String;
 
+    fn try_from(response: TodoListResponse) -> Result<Self, Self::Error> {
         let created_date_time = response
             .created_date_time
-            .as_ref()
-            .and_then(|dt| DateTime::parse_from_rfc3339(dt).ok())
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or(default_time);
+            .as_deref()
+            .map(parse_graph_timestamp)
+            .transpose()?;
 
         let last_modified_date_time = response
             .last_modified_date_time
-            .as_ref()
-            .and_then(|dt| DateTime::parse_from_rfc3339(dt).ok())
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or(default_time);
+            .as_deref()
+            .map(parse_graph_timestamp)
+            .transpose()?;
 
-        TodoList {
+        Ok(TodoList {
             id: response.id,
             display_name: response.display_name,
             is_owner: response.is_owner,
@@ -86,6 +94,92 @@ impl From<TodoListResponse> for TodoList {
             wellknown_list_name: response.wellknown_list_name,
             created_date_time,
             last_modified_date_time,
+        })
+    }
+}
+
+impl TryFrom<GoogleTaskListResponse> for TodoList {
+    type Error = String;
+
+    fn try_from(response: GoogleTaskListResponse) -> Result<Self, Self::Error> {
+        let last_modified_date_time = response
+            .updated
+            .as_deref()
+            .ok_or_else(|| format!("Todo list {} has no modification date", response.id))
+            .and_then(parse_google_timestamp)?;
+
+        Ok(TodoList {
+            id: format_list_id(&response.id),
+            display_name: response.title,
+            is_owner: true,
+            is_shared: false,
+            wellknown_list_name: None,
+            created_date_time: Some(last_modified_date_time),
+            last_modified_date_time: Some(last_modified_date_time),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_response(created_date_time: Option<&str>) -> TodoListResponse {
+        TodoListResponse {
+            id: "AAMkAD=".to_string(),
+            display_name: "Shopping List".to_string(),
+            is_owner: true,
+            is_shared: false,
+            wellknown_list_name: Some("defaultList".to_string()),
+            created_date_time: created_date_time.map(str::to_string),
+            last_modified_date_time: None,
         }
     }
+
+    #[test]
+    fn keeps_the_timestamps_reported_by_graph() {
+        let todo_list = TodoList::try_from(list_response(Some("2024-05-10T09:00:00Z"))).unwrap();
+
+        assert_eq!(todo_list.id, "AAMkAD=");
+        assert_eq!(
+            todo_list.created_date_time,
+            Some("2024-05-10T09:00:00Z".parse().unwrap())
+        );
+        assert_eq!(todo_list.last_modified_date_time, None);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps_instead_of_using_now() {
+        assert!(TodoList::try_from(list_response(Some("yesterday"))).is_err());
+    }
+
+    #[test]
+    fn maps_a_google_task_list_with_the_prefixed_id() {
+        let response = GoogleTaskListResponse {
+            id: "MDEzMTM4".to_string(),
+            title: "Groceries".to_string(),
+            updated: Some("2024-05-08T14:30:00.000Z".to_string()),
+        };
+
+        let todo_list = TodoList::try_from(response).unwrap();
+
+        let updated = "2024-05-08T14:30:00Z".parse().unwrap();
+        assert_eq!(todo_list.id, "google:MDEzMTM4");
+        assert_eq!(todo_list.display_name, "Groceries");
+        assert!(todo_list.is_owner);
+        assert!(!todo_list.is_shared);
+        assert_eq!(todo_list.created_date_time, Some(updated));
+        assert_eq!(todo_list.last_modified_date_time, Some(updated));
+    }
+
+    #[test]
+    fn rejects_google_task_lists_without_modification_date() {
+        let response = GoogleTaskListResponse {
+            id: "MDEzMTM4".to_string(),
+            title: "Groceries".to_string(),
+            updated: None,
+        };
+
+        assert!(TodoList::try_from(response).is_err());
+    }
 }
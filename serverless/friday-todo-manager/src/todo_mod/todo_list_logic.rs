@@ -1,52 +1,48 @@
 use crate::business_response::BusinessResponse;
-use crate::microsoft_graph_mod::lists_microsoft_graph_api::MicrosoftGraphApi;
-use crate::oauth_mod::oauth_api::OAuthApi;
+use crate::provider_mod::todo_provider_logic;
 use crate::todo_mod::todo_list::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
 use tracing::{debug, error, info, warn};
 
+/// Lists of every enabled provider, those of the default provider first
 pub async fn get_all_todo_lists(
 ) -> Result<BusinessResponse<Vec<TodoList>>, Box<dyn std::error::Error>> {
     info!("Logic layer: Getting all todo lists");
 
-    // Get access token from OAuth manager
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
-    };
-
-    let graph_client = MicrosoftGraphApi::new();
-    match graph_client.get_todo_lists(&access_token).await {
-        Ok(response) => {
-            if response.success {
-                info!(
-                    "Logic layer: Successfully retrieved {} todo lists",
-                    response.data.as_ref().map(|lists| lists.len()).unwrap_or(0)
-                );
-            } else {
-                warn!(
-                    "Logic layer: Failed to retrieve todo lists from Microsoft Graph: {:?}",
-                    response.errors
+    let mut todo_lists = Vec::new();
+    for kind in todo_provider_logic::enabled_providers() {
+        let provider = match todo_provider_logic::get_provider(*kind) {
+            Ok(provider) => provider,
+            Err(error_response) => return Ok(error_response),
+        };
+
+        match provider.get_todo_lists().await {
+            Ok(response) => {
+                if !response.success {
+                    warn!(
+                        "Logic layer: Failed to retrieve todo lists from {}: {:?}",
+                        kind.as_str(),
+                        response.errors
+                    );
+                    return Ok(response);
+                }
+                todo_lists.extend(response.data.unwrap_or_default());
+            }
+            Err(e) => {
+                error!(
+                    "Logic layer: Error getting todo lists from {}: {}",
+                    kind.as_str(),
+                    e
                 );
+                return Err(e);
             }
-            Ok(response)
-        }
-        Err(e) => {
-            error!(
-                "Logic layer: Error getting todo lists from Microsoft Graph: {}",
-                e
-            );
-            Err(e)
         }
     }
+
+    info!(
+        "Logic layer: Successfully retrieved {} todo lists",
+        todo_lists.len()
+    );
+    Ok(BusinessResponse::success(todo_lists))
 }
 
 pub async fn get_todo_list(
@@ -59,23 +55,12 @@ pub async fn get_todo_list(
         return Ok(BusinessResponse::error("List ID cannot be empty"));
     }
 
-    // Get access token from OAuth manager
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = MicrosoftGraphApi::new();
-    match graph_client.get_todo_list(list_id, &access_token).await {
+    match provider.get_todo_list(list_id).await {
         Ok(response) => {
             if response.success {
                 info!(
@@ -124,26 +109,21 @@ pub async fn create_todo_list(
         ));
     }
 
-    // Get access token from OAuth manager
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let kind = request
+        .provider
+        .unwrap_or_else(todo_provider_logic::default_provider);
+    let provider = match todo_provider_logic::get_provider(kind) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = MicrosoftGraphApi::new();
-    match graph_client.create_todo_list(request, &access_token).await {
+    match provider.create_todo_list(request).await {
         Ok(result) => {
             if result.success {
-                debug!("Logic layer: Successfully created todo list");
+                debug!(
+                    "Logic layer: Successfully created todo list in {}",
+                    kind.as_str()
+                );
             } else {
                 warn!(
                     "Logic layer: Failed to create todo list: {:?}",
@@ -188,23 +168,12 @@ pub async fn update_todo_list(
         ));
     }
 
-    // Get access token from OAuth manager
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let provider = match todo_provider_logic::get_list_provider(&request.id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = MicrosoftGraphApi::new();
-    match graph_client.update_todo_list(request, &access_token).await {
+    match provider.update_todo_list(request).await {
         Ok(result) => {
             if result.success {
                 debug!("Logic layer: Successfully updated todo list");
@@ -233,23 +202,12 @@ pub async fn delete_todo_list(
         return Ok(BusinessResponse::error("List ID cannot be empty"));
     }
 
-    // Get access token from OAuth manager
-    let oauth_client = OAuthApi::new();
-    let access_token = match oauth_client.generate_access_token().await {
-        Ok(token) => token,
-        Err(e) => {
-            error!(
-                "Logic layer: Failed to get access token from OAuth manager: {}",
-                e
-            );
-            return Ok(BusinessResponse::error(
-                "Failed to authenticate with Microsoft Graph",
-            ));
-        }
+    let provider = match todo_provider_logic::get_list_provider(list_id) {
+        Ok(provider) => provider,
+        Err(error_response) => return Ok(error_response),
     };
 
-    let graph_client = MicrosoftGraphApi::new();
-    match graph_client.delete_todo_list(list_id, &access_token).await {
+    match provider.delete_todo_list(list_id).await {
         Ok(response) => {
             if response.success {
                 info!(